@@ -15,18 +15,18 @@ pub struct State {
     pub owner: Addr,
 }
 
-pub fn config(storage: &mut dyn Storage) -> Singleton<State> {
+pub fn config(storage: &mut dyn Storage) -> Singleton<'_, State> {
     singleton(storage, CONFIG_KEY)
 }
 
-pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<State> {
+pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<'_, State> {
     singleton_read(storage, CONFIG_KEY)
 }
 
-pub fn replies(storage: &mut dyn Storage) -> Bucket<Reply> {
+pub fn replies(storage: &mut dyn Storage) -> Bucket<'_, Reply> {
     bucket(storage, RESULT_PREFIX)
 }
 
-pub fn replies_read(storage: &dyn Storage) -> ReadonlyBucket<Reply> {
+pub fn replies_read(storage: &dyn Storage) -> ReadonlyBucket<'_, Reply> {
     bucket_read(storage, RESULT_PREFIX)
 }
@@ -1,68 +1,153 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    coin, from_binary, to_binary, to_vec, Attribute, BankMsg, Binary, Coin, ContractResult, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, QueryRequest, Reply, Response, StdError, StdResult,
+    SubMsgResult, SystemResult, Timestamp, Uint128,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use serde::de::DeserializeOwned;
 
 use crate::error::TokenFactoryError;
-use crate::msg::{ExecuteMsg, GetDenomResponse, InstantiateMsg, QueryMsg};
-use crate::state::{State, STATE};
-use token_bindings::{TokenFactoryMsg, TokenFactoryQuery, TokenMsg, TokenQuerier};
+use crate::msg::{
+    BurnResult, ChangeAdminResult, ConfigResponse, CreateDenomResult, CreateDenomsResult,
+    CreatedDenomsResponse, DenomStatusResponse, ExecuteMsg, ForceTransferResult, GetAdminResponse,
+    GetDenomResponse, GetMetadataResponse, InstantiateMsg, MetadataDriftResponse, MintBatchResult,
+    MintRateLimit, MintResult, MintWindow, MintWindowResponse, OwnerResponse, QueryMsg,
+    ReservedSymbolsResponse, SetMetadataResult,
+};
+use crate::simulate::simulate_execute;
+use crate::state::{
+    State, CREATED_DENOMS, CREATE_DENOMS_REPLIES, LAST_SET_METADATA, MINT_RATE_LIMITS,
+    MINT_WINDOWS, PENDING_PLAN, RESERVED_SYMBOLS, STATE,
+};
+use token_bindings::{
+    create_denoms_submsgs, derive_subdenom, is_factory_denom, short_denom, validate_full_denom,
+    AdminResponse, ChangeAdminCheck, CreateDenomPlan, CreateDenomResponse, InitialMint, Metadata,
+    MetadataResponse, PageRequest, PageResponse, Params, TokenFactoryMsg, TokenFactoryQuery,
+    TokenMsg, TokenQuerier, TokenQuery,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:tokenfactory-demo";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const CREATE_TOKEN_REPLY_ID: u64 = 1;
+/// Most denoms a single CreateDenoms may create
+pub const MAX_CREATE_DENOMS: usize = 10;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<TokenFactoryQuery>,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
-) -> Result<Response, TokenFactoryError> {
+    msg: InstantiateMsg,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
     let state = State {
         owner: info.sender.clone(),
+        enable_force_transfer: msg.enable_force_transfer,
     };
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     STATE.save(deps.storage, &state)?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
-        .add_attribute("owner", info.sender))
+        .add_attribute("owner", info.sender)
+        .add_attribute(
+            "enable_force_transfer",
+            msg.enable_force_transfer.to_string(),
+        ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<TokenFactoryQuery>,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    // all messages act on the contract's denoms, so only the owner may call them
+    let state = STATE.load(deps.storage)?;
+    if info.sender != state.owner {
+        return Err(TokenFactoryError::Unauthorized {});
+    }
+
     match msg {
-        ExecuteMsg::CreateDenom { subdenom } => create_denom(subdenom),
+        ExecuteMsg::CreateDenom { subdenom } => create_denom(deps, env, info, subdenom),
+        ExecuteMsg::CreateDenoms { subdenoms } => create_denoms(deps, env, info, subdenoms),
+        ExecuteMsg::CreateDenomFromLabel { label } => {
+            create_denom(deps, env, info, derive_subdenom(&label))
+        }
+        ExecuteMsg::CreateToken {
+            subdenom,
+            metadata,
+            initial_mint,
+            metadata_in_create,
+            allow_reserved_symbol,
+        } => create_token(
+            deps,
+            env,
+            info,
+            subdenom,
+            metadata,
+            initial_mint,
+            metadata_in_create,
+            allow_reserved_symbol,
+        ),
         ExecuteMsg::ChangeAdmin {
             denom,
             new_admin_address,
-        } => change_admin(deps, denom, new_admin_address),
+            allow_module_account,
+        } => change_admin(deps, denom, new_admin_address, allow_module_account),
         ExecuteMsg::MintTokens {
             denom,
             amount,
             mint_to_address,
-        } => mint_tokens(deps, denom, amount, mint_to_address),
+        } => mint_tokens(deps, env, denom, amount, mint_to_address),
+        ExecuteMsg::MintBatch { denom, recipients } => mint_batch(deps, env, denom, recipients),
         ExecuteMsg::BurnTokens {
             denom,
             amount,
             burn_from_address,
         } => burn_tokens(deps, denom, amount, burn_from_address),
+        ExecuteMsg::ForceTransfer {
+            denom,
+            amount,
+            from_address,
+            to_address,
+        } => force_transfer(deps, denom, amount, from_address, to_address),
+        ExecuteMsg::SetMetadata {
+            denom,
+            metadata,
+            allow_reserved_symbol,
+        } => set_metadata(deps, denom, metadata, allow_reserved_symbol),
+        ExecuteMsg::SetMetadataUri {
+            denom,
+            uri,
+            uri_hash,
+        } => set_metadata_uri(deps, denom, uri, uri_hash),
+        ExecuteMsg::ReserveSymbols { symbols } => reserve_symbols(deps, symbols),
+        ExecuteMsg::UnreserveSymbols { symbols } => unreserve_symbols(deps, symbols),
+        ExecuteMsg::SetMintRateLimit { denom, limit } => set_mint_rate_limit(deps, denom, limit),
     }
 }
 
-pub fn create_denom(subdenom: String) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
-    if subdenom.eq("") {
+pub fn create_denom(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    subdenom: String,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    if subdenom.is_empty() {
         return Err(TokenFactoryError::InvalidSubdenom { subdenom });
     }
 
+    let refund = pay_creation_fee(deps.as_ref(), &info, 1)?;
+    let denom = new_denom(deps.as_ref(), &env, subdenom.clone())?;
+    let data = to_binary(&CreateDenomResult {
+        denom: denom.clone(),
+    })?;
     let create_denom_msg = TokenMsg::CreateDenom {
         subdenom,
         metadata: None,
@@ -70,7 +155,186 @@ pub fn create_denom(subdenom: String) -> Result<Response<TokenFactoryMsg>, Token
 
     let res = Response::new()
         .add_attribute("method", "create_denom")
-        .add_message(create_denom_msg);
+        .add_attributes(denom_attributes(&denom))
+        .add_message(create_denom_msg)
+        .add_messages(refund)
+        .set_data(data);
+
+    Ok(res)
+}
+
+pub fn create_denoms(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    subdenoms: Vec<String>,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    if subdenoms.is_empty() {
+        return Err(TokenFactoryError::EmptyBatch {});
+    }
+    if subdenoms.len() > MAX_CREATE_DENOMS {
+        return Err(TokenFactoryError::BatchTooLarge {
+            max: MAX_CREATE_DENOMS,
+        });
+    }
+    let mut denoms = Vec::with_capacity(subdenoms.len());
+    for (i, subdenom) in subdenoms.iter().enumerate() {
+        if subdenom.is_empty() {
+            return Err(TokenFactoryError::InvalidSubdenom {
+                subdenom: subdenom.clone(),
+            });
+        }
+        if subdenoms[..i].contains(subdenom) {
+            return Err(TokenFactoryError::DuplicateSubdenom {
+                subdenom: subdenom.clone(),
+            });
+        }
+        let denom = new_denom(deps.as_ref(), &env, subdenom.clone())?;
+        validate_full_denom(&denom).map_err(|err| TokenFactoryError::InvalidDenom {
+            denom: denom.clone(),
+            message: err.to_string(),
+        })?;
+        denoms.push(denom);
+    }
+
+    let refund = pay_creation_fee(deps.as_ref(), &info, subdenoms.len() as u32)?;
+    let first_reply_id = CREATE_DENOMS_REPLIES.allocate_batch(deps.storage, &subdenoms)?;
+    let mut res = Response::new().add_attribute("method", "create_denoms");
+    for denom in &denoms {
+        res = res.add_attributes(denom_attributes(denom));
+    }
+    let data = to_binary(&CreateDenomsResult { denoms })?;
+    Ok(res
+        .add_submessages(create_denoms_submsgs(&subdenoms, first_reply_id)?)
+        .add_messages(refund)
+        .set_data(data))
+}
+
+/// The contract is the creator, so the chain charges the creation fee to it.
+/// The sender must attach the fee for each of the `count` denoms created, in one of the
+/// fee options of the chain, and gets back anything else attached.
+fn pay_creation_fee(
+    deps: Deps<TokenFactoryQuery>,
+    info: &MessageInfo,
+    count: u32,
+) -> Result<Option<BankMsg>, TokenFactoryError> {
+    let options = TokenQuerier::new(&deps.querier).creation_fee_options()?;
+    let refund = check_creation_fee_options(options, count, &info.funds)?;
+    if refund.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: refund,
+    }))
+}
+
+/// Matches the fee against the funds by denom, returning the funds left after paying it,
+/// or which fee coin is not covered
+pub fn check_creation_fee(fee: &[Coin], funds: &[Coin]) -> Result<Vec<Coin>, TokenFactoryError> {
+    let mut left = funds.to_vec();
+    for required in fee {
+        match left.iter_mut().find(|c| c.denom == required.denom) {
+            Some(sent) if sent.amount >= required.amount => sent.amount -= required.amount,
+            sent => {
+                return Err(TokenFactoryError::InsufficientCreationFee {
+                    required: required.clone(),
+                    sent: sent
+                        .map(|c| c.clone())
+                        .unwrap_or_else(|| coin(0, &required.denom)),
+                })
+            }
+        }
+    }
+    left.retain(|c| !c.amount.is_zero());
+    Ok(left)
+}
+
+/// Pays the first fee option `funds` cover for `count` denoms, returning the funds left,
+/// or the error of the first option if none is covered
+pub fn check_creation_fee_options(
+    options: Vec<Vec<Coin>>,
+    count: u32,
+    funds: &[Coin],
+) -> Result<Vec<Coin>, TokenFactoryError> {
+    let mut first_err = None;
+    for option in options {
+        let params = Params {
+            denom_creation_fee: option,
+            ..Params::default()
+        };
+        let fee = params.total_fee_for(count).map_err(StdError::from)?;
+        match check_creation_fee(&fee, funds) {
+            Ok(left) => return Ok(left),
+            Err(err) => {
+                first_err.get_or_insert(err);
+            }
+        }
+    }
+    match first_err {
+        Some(err) => Err(err),
+        // no option, no fee
+        None => check_creation_fee(&[], funds),
+    }
+}
+
+/// The denom is known before it is created, so callers get it right away
+fn new_denom(deps: Deps<TokenFactoryQuery>, env: &Env, subdenom: String) -> StdResult<String> {
+    let res = TokenQuerier::new(&deps.querier).full_denom(&env.contract.address, subdenom)?;
+    Ok(res.denom)
+}
+
+/// Every response about a denom carries the full denom for machine consumers,
+/// and `short_denom` of it for display, as explorers truncate long values unpredictably
+fn denom_attributes(denom: &str) -> Vec<Attribute> {
+    vec![
+        Attribute::new("denom", denom),
+        Attribute::new("denom_short", short_denom(denom)),
+    ]
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_token(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    subdenom: String,
+    metadata: Option<Metadata>,
+    initial_mint: Option<InitialMint>,
+    metadata_in_create: bool,
+    allow_reserved_symbol: bool,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    if subdenom.is_empty() {
+        return Err(TokenFactoryError::InvalidSubdenom { subdenom });
+    }
+    if let Some(metadata) = &metadata {
+        check_symbol(deps.as_ref(), metadata, allow_reserved_symbol)?;
+    }
+
+    let refund = pay_creation_fee(deps.as_ref(), &info, 1)?;
+    let denom = new_denom(deps.as_ref(), &env, subdenom.clone())?;
+    let data = to_binary(&CreateDenomResult {
+        denom: denom.clone(),
+    })?;
+    let mut plan = CreateDenomPlan::new(subdenom).with_metadata_in_create(metadata_in_create);
+    if let Some(metadata) = metadata {
+        plan = plan.with_metadata(metadata);
+    }
+    if let Some(InitialMint { to, amount }) = initial_mint {
+        deps.api.addr_validate(&to)?;
+        if amount.is_zero() {
+            return Err(TokenFactoryError::ZeroAmount {});
+        }
+        plan = plan.with_mint(to, amount);
+    }
+    PENDING_PLAN.save(deps.storage, &plan)?;
+
+    let res = Response::new()
+        .add_attribute("method", "create_token")
+        .add_attributes(denom_attributes(&denom))
+        .add_submessage(plan.create_msg(CREATE_TOKEN_REPLY_ID))
+        .add_messages(refund)
+        .set_data(data);
 
     Ok(res)
 }
@@ -79,25 +343,37 @@ pub fn change_admin(
     deps: DepsMut<TokenFactoryQuery>,
     denom: String,
     new_admin_address: String,
+    allow_module_account: bool,
 ) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
     deps.api.addr_validate(&new_admin_address)?;
 
     validate_denom(deps, denom.clone())?;
-
-    let change_admin_msg = TokenMsg::ChangeAdmin {
-        denom,
-        new_admin_address,
+    let check = ChangeAdminCheck {
+        allow_module_account,
+        address_validated: true,
+        ..ChangeAdminCheck::default()
     };
+    let change_admin_msg =
+        TokenMsg::change_admin_safe(denom.clone(), new_admin_address.clone(), &check)?;
+
+    let data = to_binary(&ChangeAdminResult {
+        denom: denom.clone(),
+        new_admin: new_admin_address.clone(),
+    })?;
+    let attributes = denom_attributes(&denom);
 
     let res = Response::new()
         .add_attribute("method", "change_admin")
-        .add_message(change_admin_msg);
+        .add_attributes(attributes)
+        .add_message(change_admin_msg)
+        .set_data(data);
 
     Ok(res)
 }
 
 pub fn mint_tokens(
-    deps: DepsMut<TokenFactoryQuery>,
+    mut deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
     denom: String,
     amount: Uint128,
     mint_to_address: String,
@@ -108,17 +384,132 @@ pub fn mint_tokens(
         return Result::Err(TokenFactoryError::ZeroAmount {});
     }
 
-    validate_denom(deps, denom.clone())?;
+    validate_denom(deps.branch(), denom.clone())?;
+    consume_mint_rate_limit(deps, &env, &denom, amount)?;
 
+    let data = to_binary(&MintResult {
+        denom: denom.clone(),
+        amount,
+        mint_to_address: mint_to_address.clone(),
+    })?;
+    let attributes = denom_attributes(&denom);
     let mint_tokens_msg = TokenMsg::mint_contract_tokens(denom, amount, mint_to_address);
 
     let res = Response::new()
         .add_attribute("method", "mint_tokens")
-        .add_message(mint_tokens_msg);
+        .add_attributes(attributes)
+        .add_message(mint_tokens_msg)
+        .set_data(data);
 
     Ok(res)
 }
 
+pub fn mint_batch(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    denom: String,
+    recipients: Vec<(String, Uint128)>,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    if recipients.is_empty() {
+        return Err(TokenFactoryError::EmptyBatch {});
+    }
+    let mut total = Uint128::zero();
+    for (recipient, amount) in &recipients {
+        validate_address(deps.as_ref(), "recipient", recipient)?;
+        total = total.checked_add(*amount).map_err(StdError::from)?;
+    }
+
+    validate_denom(deps.branch(), denom.clone())?;
+    consume_mint_rate_limit(deps, &env, &denom, total)?;
+
+    let count = recipients.len();
+    let mints = recipients
+        .into_iter()
+        .map(|(recipient, amount)| (denom.clone(), amount, recipient))
+        .collect();
+    // rejects zero amounts, and merges repeated recipients
+    let mint_msgs = TokenMsg::mint_batch(mints)?;
+
+    let data = to_binary(&MintBatchResult {
+        denom: denom.clone(),
+        amount: total,
+    })?;
+
+    let res = Response::new()
+        .add_attribute("method", "mint_batch")
+        .add_attributes(denom_attributes(&denom))
+        .add_attribute("recipients", count.to_string())
+        .add_messages(mint_msgs)
+        .set_data(data);
+
+    Ok(res)
+}
+
+/// Counts `amount` against the rate limit of the denom, if any.
+/// Errors if that exceeds what is left in the current window.
+fn consume_mint_rate_limit(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: &Env,
+    denom: &str,
+    amount: Uint128,
+) -> Result<(), TokenFactoryError> {
+    let limit = match MINT_RATE_LIMITS.may_load(deps.storage, denom)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let mut window = current_mint_window(deps.as_ref(), env, denom, &limit)?;
+    window.minted = window.minted.checked_add(amount).map_err(StdError::from)?;
+    if window.minted > limit.max_per_window {
+        return Err(TokenFactoryError::RateLimited {
+            retry_after: window.start.plus_seconds(limit.window_secs),
+        });
+    }
+    MINT_WINDOWS.save(deps.storage, denom, &window)?;
+    Ok(())
+}
+
+/// The window containing the current block time, with what was minted in it so far
+fn current_mint_window(
+    deps: Deps<TokenFactoryQuery>,
+    env: &Env,
+    denom: &str,
+    limit: &MintRateLimit,
+) -> StdResult<MintWindow> {
+    let now = env.block.time.seconds();
+    let start = Timestamp::from_seconds(now - now % limit.window_secs);
+    Ok(match MINT_WINDOWS.may_load(deps.storage, denom)? {
+        Some(window) if window.start == start => window,
+        _ => MintWindow {
+            start,
+            minted: Uint128::zero(),
+        },
+    })
+}
+
+pub fn set_mint_rate_limit(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    denom: String,
+    limit: Option<MintRateLimit>,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    validate_denom(deps.branch(), denom.clone())?;
+    match limit {
+        Some(limit) => {
+            if limit.window_secs == 0 {
+                return Err(TokenFactoryError::InvalidRateLimit {});
+            }
+            MINT_RATE_LIMITS.save(deps.storage, &denom, &limit)?;
+        }
+        None => {
+            MINT_RATE_LIMITS.remove(deps.storage, &denom);
+            MINT_WINDOWS.remove(deps.storage, &denom);
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_mint_rate_limit")
+        .add_attributes(denom_attributes(&denom)))
+}
+
 pub fn burn_tokens(
     deps: DepsMut<TokenFactoryQuery>,
     denom: String,
@@ -137,35 +528,435 @@ pub fn burn_tokens(
 
     validate_denom(deps, denom.clone())?;
 
+    let data = to_binary(&BurnResult {
+        denom: denom.clone(),
+        amount,
+    })?;
+    let attributes = denom_attributes(&denom);
     let burn_token_msg = TokenMsg::burn_contract_tokens(denom, amount, burn_from_address);
 
     let res = Response::new()
         .add_attribute("method", "burn_tokens")
-        .add_message(burn_token_msg);
+        .add_attributes(attributes)
+        .add_message(burn_token_msg)
+        .set_data(data);
+
+    Ok(res)
+}
+
+pub fn force_transfer(
+    deps: DepsMut<TokenFactoryQuery>,
+    denom: String,
+    amount: Uint128,
+    from_address: String,
+    to_address: String,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    if !STATE.load(deps.storage)?.enable_force_transfer {
+        return Err(TokenFactoryError::FeatureDisabled {
+            feature: String::from("force_transfer"),
+        });
+    }
+    validate_address(deps.as_ref(), "from_address", &from_address)?;
+    validate_address(deps.as_ref(), "to_address", &to_address)?;
+
+    if amount.is_zero() {
+        return Err(TokenFactoryError::ZeroAmount {});
+    }
+
+    // not every chain has force transfer, fail here rather than in the chain's dispatch
+    if !TokenQuerier::new(&deps.querier).features().force_transfer {
+        return Err(TokenFactoryError::FeatureUnsupported {
+            feature: String::from("force_transfer"),
+        });
+    }
+
+    validate_denom(deps, denom.clone())?;
+
+    let data = to_binary(&ForceTransferResult {
+        denom: denom.clone(),
+        amount,
+        from_address: from_address.clone(),
+        to_address: to_address.clone(),
+    })?;
+    let attributes = denom_attributes(&denom);
+    let force_transfer_msg = TokenMsg::ForceTransfer {
+        denom,
+        amount,
+        from_address,
+        to_address,
+    };
+
+    let res = Response::new()
+        .add_attribute("method", "force_transfer")
+        .add_attributes(attributes)
+        .add_message(force_transfer_msg)
+        .set_data(data);
+
+    Ok(res)
+}
+
+/// Like `addr_validate`, but names the field, as messages with several addresses
+/// would otherwise fail without saying which one is wrong
+fn validate_address(
+    deps: Deps<TokenFactoryQuery>,
+    field: &str,
+    address: &str,
+) -> Result<(), TokenFactoryError> {
+    deps.api
+        .addr_validate(address)
+        .map_err(|err| TokenFactoryError::InvalidAddress {
+            field: field.to_string(),
+            address: address.to_string(),
+            message: err.to_string(),
+        })?;
+    Ok(())
+}
+
+pub fn set_metadata(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    denom: String,
+    metadata: Metadata,
+    allow_reserved_symbol: bool,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    validate_denom(deps.branch(), denom.clone())?;
+    check_symbol(deps.as_ref(), &metadata, allow_reserved_symbol)?;
+    LAST_SET_METADATA.save(deps.storage, &denom, &metadata)?;
+
+    let data = to_binary(&SetMetadataResult {
+        denom: denom.clone(),
+    })?;
+    let attributes = denom_attributes(&denom);
+    let set_metadata_msg = TokenMsg::SetMetadata { denom, metadata };
+
+    let res = Response::new()
+        .add_attribute("method", "set_metadata")
+        .add_attributes(attributes)
+        .add_message(set_metadata_msg)
+        .set_data(data);
+
+    Ok(res)
+}
+
+/// Refuses a metadata symbol on the reserved list, ignoring case, unless overridden
+fn check_symbol(
+    deps: Deps<TokenFactoryQuery>,
+    metadata: &Metadata,
+    allow_reserved_symbol: bool,
+) -> Result<(), TokenFactoryError> {
+    if allow_reserved_symbol {
+        return Ok(());
+    }
+    if let Some(symbol) = &metadata.symbol {
+        if RESERVED_SYMBOLS.has(deps.storage, &symbol.to_uppercase()) {
+            return Err(TokenFactoryError::SymbolReserved {
+                symbol: symbol.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn reserve_symbols(
+    deps: DepsMut<TokenFactoryQuery>,
+    symbols: Vec<String>,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    for symbol in &symbols {
+        RESERVED_SYMBOLS.save(deps.storage, &symbol.to_uppercase(), &Empty {})?;
+    }
+    Ok(Response::new()
+        .add_attribute("method", "reserve_symbols")
+        .add_attribute("symbols", symbols.join(",")))
+}
+
+pub fn unreserve_symbols(
+    deps: DepsMut<TokenFactoryQuery>,
+    symbols: Vec<String>,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    for symbol in &symbols {
+        RESERVED_SYMBOLS.remove(deps.storage, &symbol.to_uppercase());
+    }
+    Ok(Response::new()
+        .add_attribute("method", "unreserve_symbols")
+        .add_attribute("symbols", symbols.join(",")))
+}
+
+pub fn set_metadata_uri(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    denom: String,
+    uri: String,
+    uri_hash: Option<String>,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    if !uri.starts_with("https://") && !uri.starts_with("ipfs://") {
+        return Err(TokenFactoryError::InvalidUri { uri });
+    }
+    validate_denom(deps.branch(), denom.clone())?;
+
+    let mut metadata = match TokenQuerier::new(&deps.querier).metadata(&denom)?.metadata {
+        Some(metadata) => metadata,
+        None => return Err(TokenFactoryError::NoMetadata { denom }),
+    };
+    metadata.uri = Some(uri);
+    metadata.uri_hash = uri_hash;
+    LAST_SET_METADATA.save(deps.storage, &denom, &metadata)?;
+
+    let data = to_binary(&SetMetadataResult {
+        denom: denom.clone(),
+    })?;
+    let attributes = denom_attributes(&denom);
+    let set_metadata_msg = TokenMsg::SetMetadata { denom, metadata };
+
+    let res = Response::new()
+        .add_attribute("method", "set_metadata_uri")
+        .add_attributes(attributes)
+        .add_message(set_metadata_msg)
+        .set_data(data);
 
     Ok(res)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<TokenFactoryQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    msg: Reply,
+) -> Result<Response<TokenFactoryMsg>, TokenFactoryError> {
+    match msg.id {
+        CREATE_TOKEN_REPLY_ID => {
+            let plan = PENDING_PLAN.load(deps.storage)?;
+            PENDING_PLAN.remove(deps.storage);
+            // the initial mint counts against a rate limit set before the denom was created
+            if let Some(mint) = &plan.mint {
+                let denom = created_denom(msg.result.clone())?;
+                consume_mint_rate_limit(deps, &env, &denom, mint.amount)?;
+            }
+            let msgs = plan.handle_reply(msg)?;
+            Ok(Response::new()
+                .add_attribute("method", "create_token_reply")
+                .add_messages(msgs))
+        }
+        id => {
+            let subdenom: String = CREATE_DENOMS_REPLIES
+                .may_take_context(deps.storage, id)?
+                .ok_or(TokenFactoryError::UnknownReplyId { id })?;
+            let denom = created_denom(msg.result)?;
+            CREATED_DENOMS.save(deps.storage, &denom, &Empty {})?;
+            Ok(Response::new()
+                .add_attribute("method", "create_denoms_reply")
+                .add_attribute("subdenom", subdenom)
+                .add_attributes(denom_attributes(&denom)))
+        }
+    }
+}
+
+/// The new denom, from the result of a CreateDenom submessage
+fn created_denom(result: SubMsgResult) -> StdResult<String> {
+    let data = result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .ok_or_else(|| StdError::generic_err("CreateDenom reply has no data"))?;
+    Ok(CreateDenomResponse::from_reply_data(data)?.new_token_denom)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<TokenFactoryQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetDenom {
             creator_address,
             subdenom,
-        } => to_binary(&get_denom(deps, creator_address, subdenom)),
+        } => to_binary(&get_denom(deps, creator_address, subdenom)?),
+        QueryMsg::DenomStatus {
+            creator_address,
+            subdenom,
+        } => to_binary(&denom_status(deps, creator_address, subdenom)?),
+        QueryMsg::GetMetadata { denom } => to_binary(&get_metadata(deps, denom)?),
+        QueryMsg::GetAdmin { denom } => to_binary(&get_admin(deps, denom)?),
+        QueryMsg::Owner {} => to_binary(&owner(deps)?),
+        QueryMsg::Config {} => to_binary(&config(deps)?),
+        QueryMsg::MetadataDrift { denom } => to_binary(&metadata_drift(deps, denom)?),
+        QueryMsg::MintWindow { denom } => to_binary(&mint_window(deps, env, denom)?),
+        QueryMsg::CreatedDenoms(page) => to_binary(&created_denoms(deps, page)?),
+        QueryMsg::ReservedSymbols(page) => to_binary(&reserved_symbols(deps, page)?),
+        QueryMsg::SimulateExecute { sender, funds, msg } => {
+            let info = MessageInfo {
+                sender: deps.api.addr_validate(&sender)?,
+                funds,
+            };
+            to_binary(&simulate_execute(deps, env, info, *msg)?)
+        }
     }
 }
 
+fn created_denoms(
+    deps: Deps<TokenFactoryQuery>,
+    page: PageRequest,
+) -> StdResult<CreatedDenomsResponse> {
+    let limit = page.limit();
+    let start = page
+        .start_after
+        .map(|denom| Bound::ExclusiveRaw(denom.into()));
+    let denoms = CREATED_DENOMS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<_>>()?;
+    Ok(PageResponse::new(denoms, limit, String::clone))
+}
+
+fn reserved_symbols(
+    deps: Deps<TokenFactoryQuery>,
+    page: PageRequest,
+) -> StdResult<ReservedSymbolsResponse> {
+    let limit = page.limit();
+    let start = page
+        .start_after
+        .map(|symbol| Bound::ExclusiveRaw(symbol.to_uppercase().into()));
+    let symbols = RESERVED_SYMBOLS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<_>>()?;
+    Ok(PageResponse::new(symbols, limit, String::clone))
+}
+
+fn mint_window(
+    deps: Deps<TokenFactoryQuery>,
+    env: Env,
+    denom: String,
+) -> StdResult<MintWindowResponse> {
+    let limit = MINT_RATE_LIMITS.may_load(deps.storage, &denom)?;
+    let window = match &limit {
+        Some(limit) => Some(current_mint_window(deps, &env, &denom, limit)?),
+        None => None,
+    };
+    Ok(MintWindowResponse {
+        denom,
+        limit,
+        window,
+    })
+}
+
+fn metadata_drift(
+    deps: Deps<TokenFactoryQuery>,
+    denom: String,
+) -> StdResult<MetadataDriftResponse> {
+    let expected = LAST_SET_METADATA.load(deps.storage, &denom)?;
+    let current = TokenQuerier::new(&deps.querier)
+        .metadata(&denom)?
+        .metadata
+        // metadata removed on chain shows as all fields changed to unset
+        .unwrap_or(Metadata {
+            description: None,
+            denom_units: vec![],
+            base: None,
+            display: None,
+            name: None,
+            symbol: None,
+            uri: None,
+            uri_hash: None,
+        });
+    Ok(MetadataDriftResponse {
+        denom,
+        changes: expected.diff(&current),
+    })
+}
+
+fn owner(deps: Deps<TokenFactoryQuery>) -> StdResult<OwnerResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(OwnerResponse {
+        owner: state.owner.into_string(),
+    })
+}
+
+fn config(deps: Deps<TokenFactoryQuery>) -> StdResult<ConfigResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: state.owner.into_string(),
+        enable_force_transfer: state.enable_force_transfer,
+    })
+}
+
 fn get_denom(
     deps: Deps<TokenFactoryQuery>,
     creator_addr: String,
     subdenom: String,
-) -> GetDenomResponse {
+) -> StdResult<GetDenomResponse> {
+    // any creator can be resolved, not just this contract
     let querier = TokenQuerier::new(&deps.querier);
-    let response = querier.full_denom(creator_addr, subdenom).unwrap();
+    let response = querier.full_denom(creator_addr, subdenom)?;
 
-    GetDenomResponse {
+    Ok(GetDenomResponse {
         denom: response.denom,
+    })
+}
+
+fn denom_status(
+    deps: Deps<TokenFactoryQuery>,
+    creator_addr: String,
+    subdenom: String,
+) -> StdResult<DenomStatusResponse> {
+    let querier = TokenQuerier::new(&deps.querier);
+    let denom = querier.full_denom(creator_addr, subdenom)?.denom;
+    let admin = find_admin(deps, &denom)?;
+    let has_metadata = find_metadata(deps, &denom)?.is_some();
+
+    Ok(DenomStatusResponse {
+        denom,
+        exists: admin.is_some(),
+        admin,
+        has_metadata,
+    })
+}
+
+fn get_metadata(deps: Deps<TokenFactoryQuery>, denom: String) -> StdResult<GetMetadataResponse> {
+    // also finds the metadata of native denoms, which have no admin
+    let metadata = find_metadata(deps, &denom)?;
+    Ok(GetMetadataResponse {
+        found: metadata.is_some(),
+        metadata,
+    })
+}
+
+fn get_admin(deps: Deps<TokenFactoryQuery>, denom: String) -> StdResult<GetAdminResponse> {
+    let admin = find_admin(deps, &denom)?;
+    Ok(GetAdminResponse {
+        found: admin.is_some(),
+        admin,
+    })
+}
+
+/// The admin of the denom, None if it doesn't exist.
+/// The chain errors on admin queries for denoms it doesn't know about, with messages
+/// differing between chains, so any error is taken as a denom which doesn't exist (yet).
+fn find_admin(deps: Deps<TokenFactoryQuery>, denom: &str) -> StdResult<Option<String>> {
+    let query = TokenQuery::Admin {
+        denom: denom.to_string(),
+    };
+    let res: Option<AdminResponse> = query_unless_not_found(deps, query)?;
+    Ok(res.map(|res| res.admin))
+}
+
+/// The metadata of the denom, None if it has none or the chain errors for unknown denoms
+fn find_metadata(deps: Deps<TokenFactoryQuery>, denom: &str) -> StdResult<Option<Metadata>> {
+    let query = TokenQuery::Metadata {
+        denom: denom.to_string(),
+    };
+    let res: Option<MetadataResponse> = query_unless_not_found(deps, query)?;
+    Ok(res.and_then(|res| res.metadata))
+}
+
+/// None if the chain rejects the query, as it does for denoms which don't exist.
+/// Like `TokenQuerier::admin_is`, system errors and unparsable responses still fail.
+fn query_unless_not_found<T: DeserializeOwned>(
+    deps: Deps<TokenFactoryQuery>,
+    query: TokenQuery,
+) -> StdResult<Option<T>> {
+    let request: QueryRequest<TokenFactoryQuery> = query.into();
+    match deps.querier.raw_query(&to_vec(&request)?) {
+        SystemResult::Ok(ContractResult::Ok(value)) => from_binary(&value).map(Some),
+        SystemResult::Ok(ContractResult::Err(_)) => Ok(None),
+        SystemResult::Err(err) => Err(StdError::generic_err(format!(
+            "Querier system error: {}",
+            err
+        ))),
     }
 }
 
@@ -173,33 +964,24 @@ fn validate_denom(
     deps: DepsMut<TokenFactoryQuery>,
     denom: String,
 ) -> Result<(), TokenFactoryError> {
-    let denom_to_split = denom.clone();
-    let tokenfactory_denom_parts: Vec<&str> = denom_to_split.split('/').collect();
-
-    if tokenfactory_denom_parts.len() != 3 {
-        return Result::Err(TokenFactoryError::InvalidDenom {
-            denom,
-            message: std::format!(
-                "denom must have 3 parts separated by /, had {}",
-                tokenfactory_denom_parts.len()
-            ),
-        });
+    // native, IBC and other module denoms get a clearer error than the structural one
+    if !is_factory_denom(&denom) {
+        return Err(TokenFactoryError::NotAFactoryDenom { denom });
     }
 
-    let prefix = tokenfactory_denom_parts[0];
-    let creator_address = tokenfactory_denom_parts[1];
-    let subdenom = tokenfactory_denom_parts[2];
-
-    if !prefix.eq_ignore_ascii_case("factory") {
-        return Result::Err(TokenFactoryError::InvalidDenom {
-            denom,
-            message: std::format!("prefix must be 'factory', was {}", prefix),
-        });
-    }
+    // the creator may have any bech32 prefix, the chain validates it below
+    let parts = match validate_full_denom(&denom) {
+        Ok(parts) => parts,
+        Err(err) => {
+            return Result::Err(TokenFactoryError::InvalidDenom {
+                denom,
+                message: err.to_string(),
+            })
+        }
+    };
 
     // Validate denom by attempting to query for full denom
-    let response = TokenQuerier::new(&deps.querier)
-        .full_denom(String::from(creator_address), String::from(subdenom));
+    let response = TokenQuerier::new(&deps.querier).full_denom(parts.creator, parts.subdenom);
     if response.is_err() {
         return Result::Err(TokenFactoryError::InvalidDenom {
             denom,
@@ -217,11 +999,14 @@ mod tests {
         mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
     };
     use cosmwasm_std::{
-        coins, from_binary, Attribute, ContractResult, CosmosMsg, OwnedDeps, Querier, StdError,
-        SystemError, SystemResult,
+        coins, from_binary, Attribute, ContractResult, CosmosMsg, OwnedDeps, Querier, ReplyOn,
+        StdError, SubMsgResponse, SubMsgResult, SystemError, SystemResult,
     };
     use std::marker::PhantomData;
-    use token_bindings::TokenQuery;
+    use token_bindings::{
+        AdminResponse, FullDenomResponse, MetadataResponse, TokenBindingsError,
+        TokenFactoryFeatures, TokenQuery,
+    };
     use token_bindings_test::TokenFactoryApp;
 
     const DENOM_NAME: &str = "mydenom";
@@ -238,50 +1023,234 @@ mod tests {
         }
     }
 
-    fn mock_dependencies_with_query_error(
-    ) -> OwnedDeps<MockStorage, MockApi, MockQuerier<TokenFactoryQuery>, TokenFactoryQuery> {
-        let custom_querier: MockQuerier<TokenFactoryQuery> =
-            MockQuerier::new(&[(MOCK_CONTRACT_ADDR, &[])]).with_custom_handler(|a| match a {
-                TokenFactoryQuery::Token(TokenQuery::FullDenom {
-                    creator_addr,
-                    subdenom,
-                }) => {
-                    let binary_request = to_binary(a).unwrap();
+    fn mock_dependencies_with_query_error(
+    ) -> OwnedDeps<MockStorage, MockApi, MockQuerier<TokenFactoryQuery>, TokenFactoryQuery> {
+        let custom_querier: MockQuerier<TokenFactoryQuery> =
+            MockQuerier::new(&[(MOCK_CONTRACT_ADDR, &[])]).with_custom_handler(|a| match a {
+                TokenFactoryQuery::Token(TokenQuery::FullDenom {
+                    creator_addr,
+                    subdenom,
+                }) => {
+                    let binary_request = to_binary(a).unwrap();
+
+                    if creator_addr.is_empty() {
+                        return SystemResult::Err(SystemError::InvalidRequest {
+                            error: String::from("invalid creator address"),
+                            request: binary_request,
+                        });
+                    }
+                    if subdenom.is_empty() {
+                        return SystemResult::Err(SystemError::InvalidRequest {
+                            error: String::from("invalid subdenom"),
+                            request: binary_request,
+                        });
+                    }
+                    SystemResult::Ok(ContractResult::Ok(binary_request))
+                }
+                _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: "token query".to_string(),
+                }),
+            });
+        mock_dependencies_with_custom_quierier(custom_querier)
+    }
+
+    /// The attributes of a method on DENOM_NAME: the full denom, and a short one for display
+    fn denom_attributes(method: &str) -> Vec<Attribute> {
+        let full_denom = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        vec![
+            Attribute::new("method", method),
+            Attribute::new("denom", full_denom),
+            Attribute::new("denom_short", format!("factory/…ract/{}", DENOM_NAME)),
+        ]
+    }
+
+    pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, TokenFactoryApp, TokenFactoryQuery>
+    {
+        let custom_querier = TokenFactoryApp::new();
+        mock_dependencies_with_custom_quierier(custom_querier)
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg::default();
+        let info = mock_info("creator", &coins(1000, "uosmo"));
+
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+    }
+
+    #[test]
+    fn msg_set_metadata_success() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let full_denom_name = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let metadata = Metadata::two_unit("umydenom", "mydenom", 6, "MYD", "My Denom");
+        let msg = ExecuteMsg::SetMetadata {
+            denom: full_denom_name.clone(),
+            metadata: metadata.clone(),
+            allow_reserved_symbol: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let expected_message = CosmosMsg::from(TokenMsg::SetMetadata {
+            denom: full_denom_name.clone(),
+            metadata: metadata.clone(),
+        });
+        assert_eq!(expected_message, res.messages[0].msg);
+        let data: SetMetadataResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(full_denom_name, data.denom);
+        assert_eq!(
+            metadata,
+            LAST_SET_METADATA
+                .load(deps.as_ref().storage, &full_denom_name)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn msg_set_metadata_uri_invalid() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let full_denom_name = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let msg = ExecuteMsg::SetMetadataUri {
+            denom: full_denom_name.clone(),
+            uri: String::from("https://example.com/logo.svg"),
+            uri_hash: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            TokenFactoryError::NoMetadata {
+                denom: full_denom_name.clone()
+            },
+            err
+        );
+
+        let msg = ExecuteMsg::SetMetadataUri {
+            denom: full_denom_name,
+            uri: String::from("http://example.com/logo.svg"),
+            uri_hash: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            TokenFactoryError::InvalidUri {
+                uri: String::from("http://example.com/logo.svg")
+            },
+            err
+        );
+    }
+
+    #[test]
+    fn msg_mint_rate_limited() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let denom = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let set_limit = |window_secs: u64| ExecuteMsg::SetMintRateLimit {
+            denom: denom.clone(),
+            limit: Some(MintRateLimit {
+                max_per_window: Uint128::new(100),
+                window_secs,
+            }),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), set_limit(0)).unwrap_err();
+        assert_eq!(TokenFactoryError::InvalidRateLimit {}, err);
+        execute(deps.as_mut(), mock_env(), info.clone(), set_limit(3600)).unwrap();
+
+        let mint = |amount: u128| ExecuteMsg::MintTokens {
+            denom: denom.clone(),
+            amount: Uint128::new(amount),
+            mint_to_address: String::from("rcpt"),
+        };
+        let env = mock_env();
+        execute(deps.as_mut(), env.clone(), info.clone(), mint(60)).unwrap();
+        execute(deps.as_mut(), env.clone(), info.clone(), mint(40)).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), mint(1)).unwrap_err();
+        let now = env.block.time.seconds();
+        let window_end = Timestamp::from_seconds(now - now % 3600 + 3600);
+        assert_eq!(
+            TokenFactoryError::RateLimited {
+                retry_after: window_end
+            },
+            err
+        );
+
+        // the next window starts afresh
+        let mut later = env;
+        later.block.time = window_end;
+        execute(deps.as_mut(), later.clone(), info.clone(), mint(100)).unwrap();
+        execute(deps.as_mut(), later.clone(), info.clone(), mint(1)).unwrap_err();
 
-                    if creator_addr.eq("") {
-                        return SystemResult::Err(SystemError::InvalidRequest {
-                            error: String::from("invalid creator address"),
-                            request: binary_request,
-                        });
-                    }
-                    if subdenom.eq("") {
-                        return SystemResult::Err(SystemError::InvalidRequest {
-                            error: String::from("invalid subdenom"),
-                            request: binary_request,
-                        });
-                    }
-                    SystemResult::Ok(ContractResult::Ok(binary_request))
-                }
-                _ => todo!(),
-            });
-        mock_dependencies_with_custom_quierier(custom_querier)
+        // until the limit is removed
+        let remove = ExecuteMsg::SetMintRateLimit {
+            denom: denom.clone(),
+            limit: None,
+        };
+        execute(deps.as_mut(), later.clone(), info.clone(), remove).unwrap();
+        execute(deps.as_mut(), later, info, mint(1000)).unwrap();
     }
 
-    pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, TokenFactoryApp, TokenFactoryQuery>
-    {
-        let custom_querier = TokenFactoryApp::new();
-        mock_dependencies_with_custom_quierier(custom_querier)
+    #[test]
+    fn only_owner_can_execute() {
+        let mut deps = mock_dependencies();
+
+        let owner = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::CreateDenom {
+            subdenom: String::from(DENOM_NAME),
+        };
+        let other = mock_info("someone", &[]);
+        let err = execute(deps.as_mut(), mock_env(), other, msg.clone()).unwrap_err();
+        assert_eq!(TokenFactoryError::Unauthorized {}, err);
+
+        let res = execute(deps.as_mut(), mock_env(), owner, msg).unwrap();
+        assert_eq!(1, res.messages.len());
     }
 
     #[test]
-    fn proper_initialization() {
+    fn query_owner() {
         let mut deps = mock_dependencies();
 
-        let msg = InstantiateMsg {};
-        let info = mock_info("creator", &coins(1000, "uosmo"));
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg::default()).unwrap();
 
-        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
-        assert_eq!(0, res.messages.len());
+        let response = query(deps.as_ref(), mock_env(), QueryMsg::Owner {}).unwrap();
+        let owner_response: OwnerResponse = from_binary(&response).unwrap();
+        assert_eq!("creator", owner_response.owner);
     }
 
     #[test]
@@ -299,6 +1268,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn query_denom_status_not_created() {
+        let deps = mock_dependencies();
+        let status_query = QueryMsg::DenomStatus {
+            creator_address: String::from(MOCK_CONTRACT_ADDR),
+            subdenom: String::from(DENOM_NAME),
+        };
+        let response = query(deps.as_ref(), mock_env(), status_query).unwrap();
+        let status: DenomStatusResponse = from_binary(&response).unwrap();
+        assert_eq!(
+            DenomStatusResponse {
+                denom: format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME),
+                exists: false,
+                admin: None,
+                has_metadata: false,
+            },
+            status
+        );
+    }
+
+    #[test]
+    fn query_denom_status_created() {
+        let custom_querier: MockQuerier<TokenFactoryQuery> =
+            MockQuerier::new(&[(MOCK_CONTRACT_ADDR, &[])]).with_custom_handler(|a| {
+                let TokenFactoryQuery::Token(query) = a;
+                let res = match query {
+                    TokenQuery::FullDenom {
+                        creator_addr,
+                        subdenom,
+                    } => to_binary(&FullDenomResponse {
+                        denom: format!("{}/{}/{}", DENOM_PREFIX, creator_addr, subdenom),
+                    }),
+                    TokenQuery::Admin { .. } => to_binary(&AdminResponse {
+                        admin: String::from(MOCK_CONTRACT_ADDR),
+                    }),
+                    TokenQuery::Metadata { .. } => to_binary(&MetadataResponse { metadata: None }),
+                    _ => {
+                        return SystemResult::Err(SystemError::UnsupportedRequest {
+                            kind: "token query".to_string(),
+                        })
+                    }
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            });
+        let deps = mock_dependencies_with_custom_quierier(custom_querier);
+
+        let status_query = QueryMsg::DenomStatus {
+            creator_address: String::from(MOCK_CONTRACT_ADDR),
+            subdenom: String::from(DENOM_NAME),
+        };
+        let response = query(deps.as_ref(), mock_env(), status_query).unwrap();
+        let status: DenomStatusResponse = from_binary(&response).unwrap();
+        assert_eq!(
+            DenomStatusResponse {
+                denom: format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME),
+                exists: true,
+                admin: Some(String::from(MOCK_CONTRACT_ADDR)),
+                has_metadata: false,
+            },
+            status
+        );
+    }
+
+    #[test]
+    fn query_missing_denoms_propagates_system_errors() {
+        // a chain without token factory queries
+        let custom_querier: MockQuerier<TokenFactoryQuery> = MockQuerier::new(&[]);
+        let deps = mock_dependencies_with_custom_quierier(custom_querier);
+
+        let denom = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        for msg in [
+            QueryMsg::GetMetadata {
+                denom: denom.clone(),
+            },
+            QueryMsg::GetAdmin { denom },
+        ] {
+            let err = query(deps.as_ref(), mock_env(), msg).unwrap_err();
+            assert!(err.to_string().contains("Querier system error"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn query_denom_status_propagates_system_errors() {
+        // the admin query fails on the node rather than reporting a missing denom
+        let custom_querier: MockQuerier<TokenFactoryQuery> =
+            MockQuerier::new(&[(MOCK_CONTRACT_ADDR, &[])]).with_custom_handler(|a| {
+                let TokenFactoryQuery::Token(query) = a;
+                match query {
+                    TokenQuery::FullDenom {
+                        creator_addr,
+                        subdenom,
+                    } => {
+                        let res = FullDenomResponse {
+                            denom: format!("{}/{}/{}", DENOM_PREFIX, creator_addr, subdenom),
+                        };
+                        SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+                    }
+                    _ => SystemResult::Err(SystemError::Unknown {}),
+                }
+            });
+        let deps = mock_dependencies_with_custom_quierier(custom_querier);
+
+        let status_query = QueryMsg::DenomStatus {
+            creator_address: String::from(MOCK_CONTRACT_ADDR),
+            subdenom: String::from(DENOM_NAME),
+        };
+        let err = query(deps.as_ref(), mock_env(), status_query).unwrap_err();
+        assert!(err.to_string().contains("Querier system error"), "{}", err);
+    }
+
     #[test]
     fn msg_create_denom_success() {
         let mut deps = mock_dependencies();
@@ -307,24 +1386,114 @@ mod tests {
 
         let msg = ExecuteMsg::CreateDenom { subdenom };
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-        assert_eq!(1, res.messages.len());
+        // there is no creation fee, so the attached funds are refunded
+        assert_eq!(2, res.messages.len());
 
         let expected_message = CosmosMsg::from(TokenMsg::CreateDenom {
             subdenom: String::from(DENOM_NAME),
             metadata: None,
         });
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
+        let expected_refund = CosmosMsg::from(BankMsg::Send {
+            to_address: String::from("creator"),
+            amount: coins(2, "token"),
+        });
+        assert_eq!(expected_refund, res.messages[1].msg);
+
+        assert_eq!(denom_attributes("create_denom"), res.attributes);
 
-        assert_eq!(1, res.attributes.len());
+        let data: CreateDenomResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            CreateDenomResult {
+                denom: format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME),
+            },
+            data
+        );
+    }
 
-        let expected_attribute = Attribute::new("method", "create_denom");
-        let actual_attribute = res.attributes.get(0).unwrap();
-        assert_eq!(expected_attribute, actual_attribute);
+    #[test]
+    fn creation_fee_matching() {
+        let fee = vec![coin(10, "uosmo"), coin(5, "uion")];
+        let insufficient = |required: Coin, sent: Coin| {
+            Err(TokenFactoryError::InsufficientCreationFee { required, sent })
+        };
+        let cases = vec![
+            // no fee, everything is refunded
+            (vec![], vec![], Ok(vec![])),
+            (vec![], vec![coin(3, "usdc")], Ok(vec![coin(3, "usdc")])),
+            // exact
+            (
+                fee.clone(),
+                vec![coin(5, "uion"), coin(10, "uosmo")],
+                Ok(vec![]),
+            ),
+            // excess and unrelated funds are refunded
+            (
+                fee.clone(),
+                vec![coin(5, "uion"), coin(12, "uosmo"), coin(3, "usdc")],
+                Ok(vec![coin(2, "uosmo"), coin(3, "usdc")]),
+            ),
+            // short on one coin
+            (
+                fee.clone(),
+                vec![coin(5, "uion"), coin(9, "uosmo")],
+                insufficient(coin(10, "uosmo"), coin(9, "uosmo")),
+            ),
+            // one coin missing, others don't make up for it
+            (
+                fee.clone(),
+                vec![coin(100, "uosmo"), coin(100, "usdc")],
+                insufficient(coin(5, "uion"), coin(0, "uion")),
+            ),
+            (
+                fee.clone(),
+                vec![],
+                insufficient(coin(10, "uosmo"), coin(0, "uosmo")),
+            ),
+        ];
+        for (fee, funds, expected) in cases {
+            assert_eq!(
+                expected,
+                check_creation_fee(&fee, &funds),
+                "{:?} {:?}",
+                fee,
+                funds
+            );
+        }
+    }
 
-        assert_eq!(res.data.ok_or(0), Err(0));
+    #[test]
+    fn creation_fee_options_matching() {
+        let options = vec![vec![coin(10, "uosmo")], vec![coin(5, "uion")]];
+        let check = |funds: &[Coin]| check_creation_fee_options(options.clone(), 2, funds);
+        assert_eq!(check(&[coin(25, "uosmo")]), Ok(vec![coin(5, "uosmo")]));
+        // the alternative, while the first option is not covered
+        assert_eq!(
+            check(&[coin(15, "uosmo"), coin(10, "uion")]),
+            Ok(vec![coin(15, "uosmo")])
+        );
+        // the error is about the first option
+        assert_eq!(
+            check(&[coin(9, "uion")]),
+            Err(TokenFactoryError::InsufficientCreationFee {
+                required: coin(20, "uosmo"),
+                sent: coin(0, "uosmo"),
+            })
+        );
+        assert_eq!(
+            check_creation_fee_options(vec![], 1, &[coin(3, "usdc")]),
+            Ok(vec![coin(3, "usdc")])
+        );
     }
 
     #[test]
@@ -335,6 +1504,13 @@ mod tests {
 
         let msg = ExecuteMsg::CreateDenom { subdenom };
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
         assert_eq!(
             TokenFactoryError::InvalidSubdenom {
@@ -344,13 +1520,148 @@ mod tests {
         );
     }
 
+    #[test]
+    fn msg_create_denom_from_label() {
+        let mut deps = mock_dependencies();
+
+        let msg = ExecuteMsg::CreateDenomFromLabel {
+            label: String::from("My Cool Token!"),
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let expected_message = CosmosMsg::from(TokenMsg::CreateDenom {
+            subdenom: String::from("my.cool.token.a8723934"),
+            metadata: None,
+        });
+        assert_eq!(expected_message, res.messages[0].msg);
+
+        let data: CreateDenomResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            format!(
+                "{}/{}/my.cool.token.a8723934",
+                DENOM_PREFIX, MOCK_CONTRACT_ADDR
+            ),
+            data.denom
+        );
+    }
+
+    #[test]
+    fn msg_create_token_replies() {
+        let mut deps = mock_dependencies();
+
+        let msg = ExecuteMsg::CreateToken {
+            subdenom: String::from(DENOM_NAME),
+            metadata: None,
+            initial_mint: Some(InitialMint {
+                to: String::from("alice"),
+                amount: Uint128::zero(),
+            }),
+            metadata_in_create: false,
+            allow_reserved_symbol: false,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(TokenFactoryError::ZeroAmount {}, err);
+
+        let msg = ExecuteMsg::CreateToken {
+            subdenom: String::from(DENOM_NAME),
+            metadata: None,
+            initial_mint: None,
+            metadata_in_create: false,
+            allow_reserved_symbol: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(CREATE_TOKEN_REPLY_ID, res.messages[0].id);
+        assert_eq!(ReplyOn::Success, res.messages[0].reply_on);
+        let data: CreateDenomResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME),
+            data.denom
+        );
+        PENDING_PLAN.load(deps.as_ref().storage).unwrap();
+    }
+
+    #[test]
+    fn msg_create_denoms_reply_ids() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        // every batch gets fresh ids, above the fixed ones
+        let mut ids = vec![];
+        for subdenoms in [["fundz", "gems"], ["coins", "more"]] {
+            let msg = ExecuteMsg::CreateDenoms {
+                subdenoms: subdenoms.iter().map(|s| s.to_string()).collect(),
+            };
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+            ids.extend(res.messages.iter().map(|msg| msg.id));
+        }
+        assert_eq!(ids, vec![100, 101, 102, 103]);
+
+        let reply_for = |id: u64, subdenom: &str| Reply {
+            id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(
+                    CreateDenomResponse {
+                        new_token_denom: format!(
+                            "{}/{}/{}",
+                            DENOM_PREFIX, MOCK_CONTRACT_ADDR, subdenom
+                        ),
+                    }
+                    .encode()
+                    .unwrap(),
+                ),
+            }),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_for(102, "coins")).unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("subdenom", "coins")));
+
+        // the context of each id is used once
+        for id in [102, 7, 104] {
+            let err = reply(deps.as_mut(), mock_env(), reply_for(id, "coins")).unwrap_err();
+            assert_eq!(err, TokenFactoryError::UnknownReplyId { id });
+        }
+    }
+
     #[test]
     fn msg_change_admin_success() {
         let mut deps = mock_dependencies();
 
-        const NEW_ADMIN_ADDR: &str = "newadmin";
+        const NEW_ADMIN_ADDR: &str = "cosmos1cyyzpxplxdzkeea7kwsydadg87357qnalx9dqz";
 
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
 
         let full_denom_name: &str =
             &format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME)[..];
@@ -358,6 +1669,7 @@ mod tests {
         let msg = ExecuteMsg::ChangeAdmin {
             denom: String::from(full_denom_name),
             new_admin_address: String::from(NEW_ADMIN_ADDR),
+            allow_module_account: false,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -367,16 +1679,74 @@ mod tests {
             denom: String::from(full_denom_name),
             new_admin_address: String::from(NEW_ADMIN_ADDR),
         });
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
 
-        assert_eq!(1, res.attributes.len());
+        assert_eq!(denom_attributes("change_admin"), res.attributes);
 
-        let expected_attribute = Attribute::new("method", "change_admin");
-        let actual_attribute = res.attributes.get(0).unwrap();
-        assert_eq!(expected_attribute, actual_attribute);
+        let data: ChangeAdminResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            ChangeAdminResult {
+                denom: String::from(full_denom_name),
+                new_admin: String::from(NEW_ADMIN_ADDR),
+            },
+            data
+        );
+    }
+
+    #[test]
+    fn msg_change_admin_refuses_module_accounts() {
+        let mut deps = mock_dependencies();
+
+        // the distribution module account, ie. the community pool
+        const MODULE_ADDR: &str = "cosmos1jv65s3grqf6v6jl3dp4t6c9t9rk99cd88lyufl";
 
-        assert_eq!(res.data.ok_or(0), Err(0));
+        let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let full_denom_name = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let change_admin = |new_admin: &str, allow_module_account: bool| ExecuteMsg::ChangeAdmin {
+            denom: full_denom_name.clone(),
+            new_admin_address: new_admin.to_string(),
+            allow_module_account,
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            change_admin(MODULE_ADDR, false),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::Bindings(TokenBindingsError::ModuleAccountAdmin {
+                address: MODULE_ADDR.to_string(),
+                module: "distribution".to_string(),
+            })
+        );
+
+        // the format, eg. the checksum, is left to deps.api, which knows the chain's addresses
+
+        // explicitly handing the denom to the module
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            change_admin(MODULE_ADDR, true),
+        )
+        .unwrap();
+        let expected_message = CosmosMsg::from(TokenMsg::ChangeAdmin {
+            denom: full_denom_name.clone(),
+            new_admin_address: MODULE_ADDR.to_string(),
+        });
+        assert_eq!(expected_message, res.messages[0].msg);
     }
 
     #[test]
@@ -386,10 +1756,18 @@ mod tests {
         const EMPTY_ADDR: &str = "";
 
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
 
         let msg = ExecuteMsg::ChangeAdmin {
             denom: String::from(DENOM_NAME),
             new_admin_address: String::from(EMPTY_ADDR),
+            allow_module_account: false,
         };
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
         match err {
@@ -418,6 +1796,13 @@ mod tests {
         const NEW_ADMIN_ADDR: &str = "newadmin";
 
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
 
         // too many parts in denom
         let full_denom_name: &str = &format!(
@@ -428,6 +1813,7 @@ mod tests {
         let msg = ExecuteMsg::ChangeAdmin {
             denom: String::from(full_denom_name),
             new_admin_address: String::from(NEW_ADMIN_ADDR),
+            allow_module_account: false,
         };
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
 
@@ -451,6 +1837,13 @@ mod tests {
             &format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME)[..];
 
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
 
         let msg = ExecuteMsg::MintTokens {
             denom: String::from(full_denom_name),
@@ -465,17 +1858,22 @@ mod tests {
             denom: String::from(full_denom_name),
             amount: mint_amount,
             mint_to_address: String::from(NEW_ADMIN_ADDR),
+            dedup_key: None,
         });
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
 
-        assert_eq!(1, res.attributes.len());
-
-        let expected_attribute = Attribute::new("method", "mint_tokens");
-        let actual_attribute = res.attributes.get(0).unwrap();
-        assert_eq!(expected_attribute, actual_attribute);
+        assert_eq!(denom_attributes("mint_tokens"), res.attributes);
 
-        assert_eq!(res.data.ok_or(0), Err(0));
+        let data: MintResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            MintResult {
+                denom: String::from(full_denom_name),
+                amount: mint_amount,
+                mint_to_address: String::from(NEW_ADMIN_ADDR),
+            },
+            data
+        );
     }
 
     #[test]
@@ -487,6 +1885,13 @@ mod tests {
         let mint_amount = Uint128::new(100_u128);
 
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
 
         let full_denom_name: &str = &format!("{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR)[..];
         let msg = ExecuteMsg::MintTokens {
@@ -512,6 +1917,13 @@ mod tests {
             &format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME)[..];
 
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
 
         let msg = ExecuteMsg::BurnTokens {
             denom: String::from(full_denom_name),
@@ -526,16 +1938,19 @@ mod tests {
             amount: mint_amount,
             burn_from_address: String::from(""),
         });
-        let actual_message = res.messages.get(0).unwrap();
+        let actual_message = res.messages.first().unwrap();
         assert_eq!(expected_message, actual_message.msg);
 
-        assert_eq!(1, res.attributes.len());
-
-        let expected_attribute = Attribute::new("method", "burn_tokens");
-        let actual_attribute = res.attributes.get(0).unwrap();
-        assert_eq!(expected_attribute, actual_attribute);
+        assert_eq!(denom_attributes("burn_tokens"), res.attributes);
 
-        assert_eq!(res.data.ok_or(0), Err(0))
+        let data: BurnResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            BurnResult {
+                denom: String::from(full_denom_name),
+                amount: mint_amount,
+            },
+            data
+        );
     }
 
     #[test]
@@ -548,6 +1963,13 @@ mod tests {
             &format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME)[..];
 
         let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
 
         let msg = ExecuteMsg::BurnTokens {
             denom: String::from(full_denom_name),
@@ -610,14 +2032,46 @@ mod tests {
 
         let err = validate_denom(deps.as_mut(), String::from(full_denom_name)).unwrap_err();
 
-        let expected_error = TokenFactoryError::InvalidDenom {
+        let expected_error = TokenFactoryError::NotAFactoryDenom {
             denom: String::from(full_denom_name),
-            message: String::from("prefix must be 'factory', was invalid"),
         };
 
         assert_eq!(expected_error, err);
     }
 
+    #[test]
+    fn msg_mint_not_a_factory_denom() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        for denom in [
+            "uosmo",
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+            "gamm/pool/1",
+        ] {
+            let msg = ExecuteMsg::MintTokens {
+                denom: denom.to_string(),
+                amount: Uint128::new(100),
+                mint_to_address: String::from("rcpt"),
+            };
+            let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+            assert_eq!(
+                err,
+                TokenFactoryError::NotAFactoryDenom {
+                    denom: denom.to_string()
+                }
+            );
+        }
+    }
+
     #[test]
     fn msg_validate_denom_creator_address_invalid() {
         let mut deps = mock_dependencies_with_query_error();
@@ -634,4 +2088,294 @@ mod tests {
             err => panic!("Unexpected error: {:?}", err),
         }
     }
+
+    #[test]
+    fn msg_force_transfer_success() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                enable_force_transfer: true,
+            },
+        )
+        .unwrap();
+
+        let full_denom_name = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let msg = ExecuteMsg::ForceTransfer {
+            denom: full_denom_name.clone(),
+            amount: Uint128::new(100),
+            from_address: String::from("holder"),
+            to_address: String::from("rcpt"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(1, res.messages.len());
+        let expected_message = CosmosMsg::from(TokenMsg::ForceTransfer {
+            denom: full_denom_name.clone(),
+            amount: Uint128::new(100),
+            from_address: String::from("holder"),
+            to_address: String::from("rcpt"),
+        });
+        assert_eq!(expected_message, res.messages[0].msg);
+
+        let data: ForceTransferResult = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(
+            ForceTransferResult {
+                denom: full_denom_name,
+                amount: Uint128::new(100),
+                from_address: String::from("holder"),
+                to_address: String::from("rcpt"),
+            },
+            data
+        );
+    }
+
+    #[test]
+    fn msg_force_transfer_invalid_addresses() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &coins(2, "token"));
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                enable_force_transfer: true,
+            },
+        )
+        .unwrap();
+
+        let full_denom_name = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let force_transfer = |from: &str, to: &str| ExecuteMsg::ForceTransfer {
+            denom: full_denom_name.clone(),
+            amount: Uint128::new(100),
+            from_address: String::from(from),
+            to_address: String::from(to),
+        };
+
+        // the mock api only accepts normalized (lowercase) addresses
+        let msg = force_transfer("Holder", "rcpt");
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            TokenFactoryError::InvalidAddress { ref field, ref address, .. }
+                if field == "from_address" && address == "Holder"
+        ));
+
+        let msg = force_transfer("holder", "");
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(
+            err,
+            TokenFactoryError::InvalidAddress { ref field, ref address, .. }
+                if field == "to_address" && address.is_empty()
+        ));
+    }
+
+    #[test]
+    fn msg_set_metadata_reserved_symbol() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let msg = ExecuteMsg::ReserveSymbols {
+            symbols: vec![String::from("ATOM"), String::from("osmo")],
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let full_denom_name = format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME);
+        let set_metadata = |symbol: &str, allow_reserved_symbol: bool| ExecuteMsg::SetMetadata {
+            denom: full_denom_name.clone(),
+            metadata: Metadata::two_unit("umydenom", "mydenom", 6, symbol, "My Denom"),
+            allow_reserved_symbol,
+        };
+
+        // compared ignoring case
+        for symbol in ["ATOM", "Osmo"] {
+            let msg = set_metadata(symbol, false);
+            let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+            assert_eq!(
+                TokenFactoryError::SymbolReserved {
+                    symbol: String::from(symbol),
+                },
+                err
+            );
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            set_metadata("MYD", false),
+        )
+        .unwrap();
+
+        // the owner may override
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            set_metadata("ATOM", true),
+        )
+        .unwrap();
+
+        // also checked on creation
+        let msg = ExecuteMsg::CreateToken {
+            subdenom: String::from(DENOM_NAME),
+            metadata: Some(Metadata::two_unit("uatom", "atom", 6, "atom", "Atom")),
+            initial_mint: None,
+            metadata_in_create: false,
+            allow_reserved_symbol: false,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            TokenFactoryError::SymbolReserved {
+                symbol: String::from("atom"),
+            },
+            err
+        );
+
+        let msg = ExecuteMsg::UnreserveSymbols {
+            symbols: vec![String::from("Atom")],
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), info, set_metadata("ATOM", false)).unwrap();
+    }
+
+    #[test]
+    fn query_reserved_symbols() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let msg = ExecuteMsg::ReserveSymbols {
+            symbols: vec![
+                String::from("usdc"),
+                String::from("ATOM"),
+                String::from("OSMO"),
+                String::from("ATOM"),
+            ],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let reserved = |start_after: Option<&str>, limit: Option<u32>| {
+            let msg = QueryMsg::ReservedSymbols(PageRequest {
+                start_after: start_after.map(String::from),
+                limit,
+            });
+            let res: ReservedSymbolsResponse =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            (res.items, res.next_start_after)
+        };
+        let next = |symbol: &str| Some(symbol.to_string());
+        assert_eq!(
+            reserved(None, None),
+            (vec!["ATOM".into(), "OSMO".into(), "USDC".into()], None)
+        );
+        assert_eq!(
+            reserved(None, Some(2)),
+            (vec!["ATOM".into(), "OSMO".into()], next("OSMO"))
+        );
+        // the terminal page, exactly as long as the limit
+        assert_eq!(reserved(Some("osmo"), Some(1)), (vec!["USDC".into()], None));
+        assert_eq!(reserved(Some("USDC"), None), (vec![], None));
+    }
+
+    #[test]
+    fn msg_force_transfer_unsupported() {
+        let querier = TokenFactoryApp::builder()
+            .with_features(TokenFactoryFeatures::conservative())
+            .build();
+        let mut deps = mock_dependencies_with_custom_quierier(querier);
+
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                enable_force_transfer: true,
+            },
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::ForceTransfer {
+            denom: format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME),
+            amount: Uint128::new(100),
+            from_address: String::from("holder"),
+            to_address: String::from("rcpt"),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            TokenFactoryError::FeatureUnsupported {
+                feature: String::from("force_transfer"),
+            },
+            err
+        );
+    }
+
+    #[test]
+    fn force_transfer_switch() {
+        let msg = ExecuteMsg::ForceTransfer {
+            denom: format!("{}/{}/{}", DENOM_PREFIX, MOCK_CONTRACT_ADDR, DENOM_NAME),
+            amount: Uint128::new(100),
+            from_address: String::from("holder"),
+            to_address: String::from("rcpt"),
+        };
+        for enable_force_transfer in [false, true] {
+            let mut deps = mock_dependencies();
+            let info = mock_info("creator", &[]);
+            let instantiate_msg = InstantiateMsg {
+                enable_force_transfer,
+            };
+            instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg).unwrap();
+
+            let response = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+            let config: ConfigResponse = from_binary(&response).unwrap();
+            assert_eq!(
+                config,
+                ConfigResponse {
+                    owner: String::from("creator"),
+                    enable_force_transfer,
+                }
+            );
+
+            let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+            if enable_force_transfer {
+                assert_eq!(res.unwrap().messages.len(), 1);
+            } else {
+                assert_eq!(
+                    res.unwrap_err(),
+                    TokenFactoryError::FeatureDisabled {
+                        feature: String::from("force_transfer"),
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn force_transfer_switch_in_instantiate_msg() {
+        // off unless set, and part of the schema
+        let msg: InstantiateMsg = cosmwasm_std::from_slice(b"{}").unwrap();
+        assert!(!msg.enable_force_transfer);
+        let schema = cosmwasm_schema::schema_for!(InstantiateMsg);
+        let object = schema.schema.object.unwrap();
+        assert!(object.properties.contains_key("enable_force_transfer"));
+        assert!(object.required.is_empty());
+    }
 }
@@ -1,11 +1,15 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, Timestamp};
 use thiserror::Error;
+use token_bindings::TokenBindingsError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum TokenFactoryError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Bindings(#[from] TokenBindingsError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -15,12 +19,58 @@ pub enum TokenFactoryError {
     #[error("Invalid denom: {denom:?} {message:?}")]
     InvalidDenom { denom: String, message: String },
 
+    #[error("Not a token factory denom: {denom:?}")]
+    NotAFactoryDenom { denom: String },
+
     #[error("denom does not exist: {denom:?}")]
     DenomDoesNotExist { denom: String },
 
+    #[error("invalid {field}: {address:?} {message}")]
+    InvalidAddress {
+        field: String,
+        address: String,
+        message: String,
+    },
+
+    #[error("not supported by this chain: {feature}")]
+    FeatureUnsupported { feature: String },
+
+    #[error("disabled at instantiation: {feature}")]
+    FeatureDisabled { feature: String },
+
     #[error("address is not supported yet, was: {address:?}")]
     BurnFromAddressNotSupported { address: String },
 
+    #[error("batch has no entries")]
+    EmptyBatch {},
+
+    #[error("batch has more than {max} entries")]
+    BatchTooLarge { max: usize },
+
+    #[error("subdenom {subdenom:?} is in the batch more than once")]
+    DuplicateSubdenom { subdenom: String },
+
     #[error("amount was zero, must be positive")]
     ZeroAmount {},
+
+    #[error("insufficient creation fee: {required} required, {sent} sent")]
+    InsufficientCreationFee { required: Coin, sent: Coin },
+
+    #[error("denom has no metadata to update: {denom:?}")]
+    NoMetadata { denom: String },
+
+    #[error("symbol is reserved: {symbol:?}")]
+    SymbolReserved { symbol: String },
+
+    #[error("uri must use the https or ipfs scheme, was: {uri:?}")]
+    InvalidUri { uri: String },
+
+    #[error("rate limit window must not be empty")]
+    InvalidRateLimit {},
+
+    #[error("mint rate limit reached, retry after {retry_after}")]
+    RateLimited { retry_after: Timestamp },
+
+    #[error("unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
 }
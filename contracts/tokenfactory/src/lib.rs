@@ -1,6 +1,9 @@
 pub mod contract;
 mod error;
 pub mod msg;
+#[cfg(test)]
+mod multitest;
+mod simulate;
 pub mod state;
 
 pub use crate::error::TokenFactoryError;
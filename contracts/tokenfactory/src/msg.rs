@@ -1,28 +1,124 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, CosmosMsg, Timestamp, Uint128};
+use token_bindings::{
+    InitialMint, Metadata, MetadataFieldChange, PageRequest, PageResponse, TokenFactoryMsg,
+};
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+#[derive(Default)]
+pub struct InstantiateMsg {
+    /// Allows `ExecuteMsg::ForceTransfer`, which moves tokens without the holder's consent.
+    /// Fixed at instantiation, off if not set.
+    #[serde(default)]
+    pub enable_force_transfer: bool,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
     CreateDenom {
         subdenom: String,
     },
+    /// Creates up to `MAX_CREATE_DENOMS` denoms at once, with the creation fee of each
+    /// attached. The denoms are added to the `CreatedDenoms` registry as they are created.
+    CreateDenoms {
+        subdenoms: Vec<String>,
+    },
+    /// Opt-in alternative to CreateDenom, using a subdenom derived from an arbitrary label
+    /// (see `token_bindings::derive_subdenom`)
+    CreateDenomFromLabel {
+        label: String,
+    },
+    /// Creates a denom, then sets its metadata and mints an initial supply
+    /// once the denom is known
+    CreateToken {
+        subdenom: String,
+        metadata: Option<Metadata>,
+        initial_mint: Option<InitialMint>,
+        /// Set if the chain accepts metadata in CreateDenom
+        #[serde(default)]
+        metadata_in_create: bool,
+        /// Accept a metadata symbol on the reserved list anyway
+        #[serde(default)]
+        allow_reserved_symbol: bool,
+    },
+    /// Refuses module accounts (which would brick the denom) unless `allow_module_account`
+    /// is set, and addresses with an invalid bech32 checksum
     ChangeAdmin {
         denom: String,
         new_admin_address: String,
+        #[serde(default)]
+        allow_module_account: bool,
     },
     MintTokens {
         denom: String,
         amount: Uint128,
         mint_to_address: String,
     },
+    /// Mints to many recipients at once, eg. for an airdrop. The denom is validated
+    /// once, and the total counts against the rate limit. Amounts for the same
+    /// recipient are merged, so each recipient gets a single MintTokens.
+    MintBatch {
+        denom: String,
+        recipients: Vec<(String, Uint128)>,
+    },
     BurnTokens {
         denom: String,
         amount: Uint128,
         burn_from_address: String,
     },
+    /// Moves tokens of the denom between any two addresses, which must be valid
+    ForceTransfer {
+        denom: String,
+        amount: Uint128,
+        from_address: String,
+        to_address: String,
+    },
+    /// Sets the metadata and remembers it, see `QueryMsg::MetadataDrift`.
+    /// Refuses reserved symbols unless `allow_reserved_symbol` is set.
+    SetMetadata {
+        denom: String,
+        metadata: Metadata,
+        #[serde(default)]
+        allow_reserved_symbol: bool,
+    },
+    /// Only changes the uri (usually the logo) of the existing metadata, which must
+    /// use the https or ipfs scheme. Remembered like `SetMetadata`.
+    SetMetadataUri {
+        denom: String,
+        uri: String,
+        uri_hash: Option<String>,
+    },
+    /// Adds symbols to the reserved list, which CreateToken and SetMetadata refuse
+    /// as metadata symbol, ignoring case. Meant for symbols of well-known tokens
+    /// (ATOM, OSMO, USDC, ...), to prevent impersonation.
+    ReserveSymbols {
+        symbols: Vec<String>,
+    },
+    /// Removes symbols from the reserved list
+    UnreserveSymbols {
+        symbols: Vec<String>,
+    },
+    /// Caps how much of the denom MintTokens, MintBatch and the initial mint of CreateToken
+    /// may mint per window, None removes the cap. It may be set before the denom is created.
+    SetMintRateLimit {
+        denom: String,
+        limit: Option<MintRateLimit>,
+    },
+}
+
+/// At most `max_per_window` tokens may be minted per fixed window of `window_secs`.
+/// Windows are aligned to multiples of `window_secs` since the epoch.
+#[cw_serde]
+pub struct MintRateLimit {
+    pub max_per_window: Uint128,
+    pub window_secs: u64,
+}
+
+/// How much was minted since the start of a rate limit window
+#[cw_serde]
+pub struct MintWindow {
+    pub start: Timestamp,
+    pub minted: Uint128,
 }
 
 #[cw_serde]
@@ -33,6 +129,101 @@ pub enum QueryMsg {
         creator_address: String,
         subdenom: String,
     },
+    /// Resolves the full denom and reports whether it was created yet,
+    /// saving clients a second round trip
+    #[returns(DenomStatusResponse)]
+    DenomStatus {
+        creator_address: String,
+        subdenom: String,
+    },
+    /// Returns the metadata of any denom, native ones included. A denom without metadata,
+    /// or which doesn't exist, is reported with `found: false` instead of an error,
+    /// which differs between chains.
+    #[returns(GetMetadataResponse)]
+    GetMetadata { denom: String },
+    /// Returns the admin of any denom, reporting a missing denom like `GetMetadata`
+    #[returns(GetAdminResponse)]
+    GetAdmin { denom: String },
+    /// Returns the address which instantiated the contract
+    #[returns(OwnerResponse)]
+    Owner {},
+    /// Returns the configuration set at instantiation
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Compares the metadata last set through this contract with the current one on chain,
+    /// to detect changes made out of band
+    #[returns(MetadataDriftResponse)]
+    MetadataDrift { denom: String },
+    /// Returns the mint rate limit of the denom and its usage in the current window
+    #[returns(MintWindowResponse)]
+    MintWindow { denom: String },
+    /// Lists the denoms created through CreateDenoms, sorted
+    #[returns(CreatedDenomsResponse)]
+    CreatedDenoms(PageRequest),
+    /// Lists the reserved symbols, upper-cased and sorted
+    #[returns(ReservedSymbolsResponse)]
+    ReservedSymbols(PageRequest),
+    /// Runs `msg` as if `sender` executed it with `funds` attached, without changing any
+    /// state, and returns the messages it would emit. Errors where the execute would.
+    /// Lets front-ends and wallets preview the token factory actions of a call.
+    #[returns(SimulateExecuteResponse)]
+    SimulateExecute {
+        sender: String,
+        funds: Vec<Coin>,
+        msg: Box<ExecuteMsg>,
+    },
+}
+
+// Each execute sets one of these as response data, so calling contracts
+// can read the result from their reply
+#[cw_serde]
+pub struct CreateDenomResult {
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct CreateDenomsResult {
+    /// In the order of the subdenoms
+    pub denoms: Vec<String>,
+}
+
+#[cw_serde]
+pub struct ChangeAdminResult {
+    pub denom: String,
+    pub new_admin: String,
+}
+
+#[cw_serde]
+pub struct MintResult {
+    pub denom: String,
+    pub amount: Uint128,
+    pub mint_to_address: String,
+}
+
+#[cw_serde]
+pub struct MintBatchResult {
+    pub denom: String,
+    /// Sum over all recipients
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct BurnResult {
+    pub denom: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct ForceTransferResult {
+    pub denom: String,
+    pub amount: Uint128,
+    pub from_address: String,
+    pub to_address: String,
+}
+
+#[cw_serde]
+pub struct SetMetadataResult {
+    pub denom: String,
 }
 
 // We define a custom struct for each query response
@@ -40,3 +231,118 @@ pub enum QueryMsg {
 pub struct GetDenomResponse {
     pub denom: String,
 }
+
+#[cw_serde]
+pub struct GetMetadataResponse {
+    /// Whether the denom has metadata
+    pub found: bool,
+    /// None if the denom doesn't exist or has no metadata
+    pub metadata: Option<Metadata>,
+}
+
+#[cw_serde]
+pub struct GetAdminResponse {
+    /// Whether the denom exists
+    pub found: bool,
+    /// None if the denom doesn't exist, empty if the admin was renounced
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub struct MetadataDriftResponse {
+    pub denom: String,
+    /// Changes from the metadata set by this contract to the one on chain,
+    /// empty if there is no drift
+    pub changes: Vec<MetadataFieldChange>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub enable_force_transfer: bool,
+}
+
+#[cw_serde]
+pub struct OwnerResponse {
+    pub owner: String,
+}
+
+#[cw_serde]
+pub struct DenomStatusResponse {
+    pub denom: String,
+    pub exists: bool,
+    /// Current admin of the denom, None if it doesn't exist
+    pub admin: Option<String>,
+    pub has_metadata: bool,
+}
+
+#[cw_serde]
+pub struct MintWindowResponse {
+    pub denom: String,
+    /// None if minting the denom is not rate limited
+    pub limit: Option<MintRateLimit>,
+    /// The current window, None if not rate limited
+    pub window: Option<MintWindow>,
+}
+
+pub type ReservedSymbolsResponse = PageResponse<String>;
+
+pub type CreatedDenomsResponse = PageResponse<String>;
+
+#[cw_serde]
+pub struct SimulateExecuteResponse {
+    /// The messages of the response, in order. Replies to them are not simulated.
+    pub messages: Vec<CosmosMsg<TokenFactoryMsg>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::from_slice;
+
+    // Uint128 is a string on the wire, as JSON numbers can't hold all u128 values.
+    // Clients in JavaScript or TypeScript must send amounts like "1000", not 1000.
+
+    #[test]
+    fn mint_amount_is_a_string() {
+        let msg: ExecuteMsg = from_slice(
+            br#"{"mint_tokens":{"denom":"factory/creator/fundz","amount":"1000","mint_to_address":"alice"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            ExecuteMsg::MintTokens {
+                denom: "factory/creator/fundz".to_string(),
+                amount: Uint128::new(1000),
+                mint_to_address: "alice".to_string(),
+            }
+        );
+
+        from_slice::<ExecuteMsg>(
+            br#"{"mint_tokens":{"denom":"factory/creator/fundz","amount":1000,"mint_to_address":"alice"}}"#,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn burn_amount_is_a_string() {
+        // larger than a JavaScript number can represent exactly
+        let msg: ExecuteMsg = from_slice(
+            br#"{"burn_tokens":{"denom":"factory/creator/fundz","amount":"340282366920938463463374607431768211455","burn_from_address":""}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            ExecuteMsg::BurnTokens {
+                denom: "factory/creator/fundz".to_string(),
+                amount: Uint128::MAX,
+                burn_from_address: "".to_string(),
+            }
+        );
+
+        from_slice::<ExecuteMsg>(
+            br#"{"burn_tokens":{"denom":"factory/creator/fundz","amount":10,"burn_from_address":""}}"#,
+        )
+        .unwrap_err();
+    }
+}
@@ -0,0 +1,1028 @@
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use cw_utils::parse_reply_execute_data;
+use token_bindings::{
+    InitialMint, Metadata, MetadataFieldChange, PageRequest, TokenFactoryMsg, TokenFactoryQuery,
+    TokenMsg, TokenQuerier,
+};
+use token_bindings_test::TokenFactoryApp;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::TokenFactoryError;
+use crate::msg::{
+    CreateDenomResult, CreateDenomsResult, CreatedDenomsResponse, DenomStatusResponse, ExecuteMsg,
+    GetAdminResponse, GetDenomResponse, GetMetadataResponse, InstantiateMsg, MetadataDriftResponse,
+    MintBatchResult, MintRateLimit, MintResult, MintWindow, MintWindowResponse, QueryMsg,
+    SimulateExecuteResponse,
+};
+
+const OWNER: &str = "owner";
+const DENOM_NAME: &str = "mydenom";
+
+fn contract_tokenfactory() -> Box<dyn Contract<TokenFactoryMsg, TokenFactoryQuery>> {
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    Box::new(contract)
+}
+
+fn setup(app: &mut TokenFactoryApp) -> Addr {
+    let code_id = app.store_code(contract_tokenfactory());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &InstantiateMsg::default(),
+        &[],
+        "tokenfactory",
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn denom_status_before_and_after_creation() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+
+    let status_query = QueryMsg::DenomStatus {
+        creator_address: contract.to_string(),
+        subdenom: DENOM_NAME.to_string(),
+    };
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    let status: DenomStatusResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &status_query)
+        .unwrap();
+    assert_eq!(
+        status,
+        DenomStatusResponse {
+            denom: denom.clone(),
+            exists: false,
+            admin: None,
+            has_metadata: false,
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        contract.clone(),
+        &ExecuteMsg::CreateDenom {
+            subdenom: DENOM_NAME.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let status: DenomStatusResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &status_query)
+        .unwrap();
+    assert_eq!(
+        status,
+        DenomStatusResponse {
+            denom,
+            exists: true,
+            admin: Some(contract.to_string()),
+            has_metadata: false,
+        }
+    );
+}
+
+#[test]
+fn change_admin_to_another_contract() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+    let successor = setup(&mut app);
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    let owner = Addr::unchecked(OWNER);
+    let create = ExecuteMsg::CreateDenom {
+        subdenom: DENOM_NAME.to_string(),
+    };
+    app.execute_contract(owner.clone(), contract.clone(), &create, &[])
+        .unwrap();
+    // the address of the contract is not bech32, the api of the chain decides
+    let change_admin = ExecuteMsg::ChangeAdmin {
+        denom: denom.clone(),
+        new_admin_address: successor.to_string(),
+        allow_module_account: false,
+    };
+    app.execute_contract(owner, contract.clone(), &change_admin, &[])
+        .unwrap();
+
+    let admin: GetAdminResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &QueryMsg::GetAdmin { denom })
+        .unwrap();
+    assert_eq!(
+        admin,
+        GetAdminResponse {
+            found: true,
+            admin: Some(successor.to_string()),
+        }
+    );
+}
+
+#[test]
+fn get_denom_of_another_creator() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+
+    // the denom doesn't need to exist, nor the creator to be a contract
+    let get_denom = |creator: &str| QueryMsg::GetDenom {
+        creator_address: creator.to_string(),
+        subdenom: "sub".to_string(),
+    };
+    let res: GetDenomResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &get_denom("other"))
+        .unwrap();
+    assert_eq!(res.denom, "factory/other/sub");
+
+    // the creator must be a valid address, here it is not normalized
+    let err = app
+        .wrap()
+        .query_wasm_smart::<GetDenomResponse>(&contract, &get_denom("Other"))
+        .unwrap_err();
+    assert!(err.to_string().contains("not normalized"), "{}", err);
+}
+
+#[test]
+fn mint_emits_expected_messages() {
+    // the module only records what the contract emits, no bank mints happen
+    let mut app = TokenFactoryApp::builder().with_interception().build();
+    let contract = setup(&mut app);
+
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+    let recipients = ["alice", "bob"];
+    for (i, rcpt) in recipients.iter().enumerate() {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            contract.clone(),
+            &ExecuteMsg::MintTokens {
+                denom: denom.clone(),
+                amount: Uint128::new(100 * (i as u128 + 1)),
+                mint_to_address: rcpt.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let expected: Vec<_> = recipients
+        .iter()
+        .enumerate()
+        .map(|(i, rcpt)| {
+            let msg = TokenMsg::MintTokens {
+                denom: denom.clone(),
+                amount: Uint128::new(100 * (i as u128 + 1)),
+                mint_to_address: rcpt.to_string(),
+                dedup_key: None,
+            };
+            (contract.clone(), msg)
+        })
+        .collect();
+    assert_eq!(app.intercepted_msgs(), expected);
+
+    let balance = app.wrap().query_balance("alice", &denom).unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
+}
+
+#[test]
+fn create_token_sets_metadata_and_mints() {
+    // the mock supports both ways of setting metadata
+    for metadata_in_create in [false, true] {
+        let mut app = TokenFactoryApp::new();
+        let contract = setup(&mut app);
+
+        let metadata = Metadata::two_unit("umydenom", "mydenom", 6, "MYD", "My Denom");
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            contract.clone(),
+            &ExecuteMsg::CreateToken {
+                subdenom: DENOM_NAME.to_string(),
+                metadata: Some(metadata.clone()),
+                initial_mint: Some(InitialMint {
+                    to: "alice".to_string(),
+                    amount: Uint128::new(1000),
+                }),
+                metadata_in_create,
+                allow_reserved_symbol: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+        let balance = app.wrap().query_balance("alice", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(1000));
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        let res = querier.metadata(denom).unwrap();
+        assert_eq!(res.metadata, Some(metadata));
+    }
+}
+
+#[test]
+fn missing_denoms_are_not_found() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    let query = |app: &TokenFactoryApp, denom: &str| {
+        let metadata: GetMetadataResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &contract,
+                &QueryMsg::GetMetadata {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap();
+        let admin: GetAdminResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &contract,
+                &QueryMsg::GetAdmin {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap();
+        (metadata, admin)
+    };
+
+    // not created yet, and a native denom without metadata
+    for missing in [denom.as_str(), "uosmo"] {
+        assert_eq!(
+            query(&app, missing),
+            (
+                GetMetadataResponse {
+                    found: false,
+                    metadata: None,
+                },
+                GetAdminResponse {
+                    found: false,
+                    admin: None,
+                }
+            )
+        );
+    }
+
+    let metadata = Metadata::two_unit("umydenom", "mydenom", 6, "MYD", "My Denom");
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        contract.clone(),
+        &ExecuteMsg::CreateToken {
+            subdenom: DENOM_NAME.to_string(),
+            metadata: Some(metadata.clone()),
+            initial_mint: None,
+            metadata_in_create: false,
+            allow_reserved_symbol: false,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query(&app, &denom),
+        (
+            GetMetadataResponse {
+                found: true,
+                metadata: Some(metadata),
+            },
+            GetAdminResponse {
+                found: true,
+                admin: Some(contract.to_string()),
+            }
+        )
+    );
+
+    // native denoms have metadata but no admin
+    let uosmo = Metadata::two_unit("uosmo", "osmo", 6, "OSMO", "Osmosis");
+    app.set_staking_token_metadata(uosmo.clone()).unwrap();
+    assert_eq!(
+        query(&app, "uosmo"),
+        (
+            GetMetadataResponse {
+                found: true,
+                metadata: Some(uosmo),
+            },
+            GetAdminResponse {
+                found: false,
+                admin: None,
+            }
+        )
+    );
+}
+
+// A contract using the demo as a library: it forwards messages to it
+// and keeps the result data from the reply
+const LAST_RESULT: Item<Binary> = Item::new("last_result");
+
+fn caller_instantiate(
+    _deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    Ok(Response::new())
+}
+
+/// Forwards the message to the demo contract, given as (address, message)
+fn caller_execute(
+    _deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: (String, ExecuteMsg),
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let (contract_addr, msg) = msg;
+    let wasm = WasmMsg::Execute {
+        contract_addr,
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    };
+    Ok(Response::new().add_submessage(SubMsg::reply_on_success(wasm, 1)))
+}
+
+fn caller_reply(
+    deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    reply: Reply,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let data = parse_reply_execute_data(reply)
+        .map_err(|err| StdError::generic_err(err.to_string()))?
+        .data
+        .ok_or_else(|| StdError::generic_err("no result data"))?;
+    LAST_RESULT.save(deps.storage, &data)?;
+    Ok(Response::new())
+}
+
+fn caller_query(deps: Deps<TokenFactoryQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&LAST_RESULT.load(deps.storage)?)
+}
+
+#[test]
+fn results_are_readable_by_calling_contracts() {
+    let mut app = TokenFactoryApp::new();
+    let caller_code = app.store_code(Box::new(
+        ContractWrapper::new(caller_execute, caller_instantiate, caller_query)
+            .with_reply(caller_reply),
+    ));
+    let caller = app
+        .instantiate_contract(
+            caller_code,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "caller",
+            None,
+        )
+        .unwrap();
+
+    // the caller owns the demo contract
+    let code_id = app.store_code(contract_tokenfactory());
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            caller.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "tf",
+            None,
+        )
+        .unwrap();
+
+    let mut forward = |msg: ExecuteMsg| -> Binary {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            caller.clone(),
+            &(contract.to_string(), msg),
+            &[],
+        )
+        .unwrap();
+        app.wrap().query_wasm_smart(&caller, &Empty {}).unwrap()
+    };
+
+    let created: CreateDenomResult = from_binary(&forward(ExecuteMsg::CreateDenom {
+        subdenom: DENOM_NAME.to_string(),
+    }))
+    .unwrap();
+    assert_eq!(
+        created.denom,
+        format!("factory/{}/{}", contract, DENOM_NAME)
+    );
+
+    let minted: MintResult = from_binary(&forward(ExecuteMsg::MintTokens {
+        denom: created.denom.clone(),
+        amount: Uint128::new(42),
+        mint_to_address: "alice".to_string(),
+    }))
+    .unwrap();
+    assert_eq!(
+        minted,
+        MintResult {
+            denom: created.denom.clone(),
+            amount: Uint128::new(42),
+            mint_to_address: "alice".to_string(),
+        }
+    );
+
+    let balance = app.wrap().query_balance("alice", &created.denom).unwrap();
+    assert_eq!(balance.amount, Uint128::new(42));
+}
+
+#[test]
+fn metadata_drift() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    let owner = Addr::unchecked(OWNER);
+    app.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &ExecuteMsg::CreateDenom {
+            subdenom: DENOM_NAME.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let metadata = Metadata::two_unit("umydenom", "mydenom", 6, "MYD", "My Denom");
+    app.execute_contract(
+        owner,
+        contract.clone(),
+        &ExecuteMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: metadata.clone(),
+            allow_reserved_symbol: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let drift_query = QueryMsg::MetadataDrift {
+        denom: denom.clone(),
+    };
+    let drift: MetadataDriftResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &drift_query)
+        .unwrap();
+    assert_eq!(drift.changes, vec![]);
+
+    // the module is called as the contract, like an admin bypassing it would
+    let mut changed = metadata;
+    changed.symbol = Some("SCAM".to_string());
+    let out_of_band = TokenMsg::SetMetadata {
+        denom,
+        metadata: changed,
+    };
+    app.execute(contract.clone(), out_of_band.into()).unwrap();
+
+    let drift: MetadataDriftResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &drift_query)
+        .unwrap();
+    assert_eq!(
+        drift.changes,
+        vec![MetadataFieldChange {
+            field: "symbol".to_string(),
+            from: Some("MYD".to_string()),
+            to: Some("SCAM".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn set_metadata_uri_keeps_the_rest() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    let owner = Addr::unchecked(OWNER);
+    app.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &ExecuteMsg::CreateDenom {
+            subdenom: DENOM_NAME.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let metadata = Metadata::two_unit("umydenom", "mydenom", 6, "MYD", "My Denom");
+    app.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &ExecuteMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: metadata.clone(),
+            allow_reserved_symbol: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let uri = "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string();
+    let uri_hash = Some("8a4f9b9f8f8b4c6e".to_string());
+    app.execute_contract(
+        owner,
+        contract.clone(),
+        &ExecuteMsg::SetMetadataUri {
+            denom: denom.clone(),
+            uri: uri.clone(),
+            uri_hash: uri_hash.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let expected = Metadata {
+        uri: Some(uri),
+        uri_hash,
+        ..metadata
+    };
+    let querier = app.wrap();
+    let current = TokenQuerier::new(&querier).metadata(&denom).unwrap();
+    assert_eq!(current.metadata, Some(expected));
+
+    // the update is remembered, so there is no drift
+    let drift: MetadataDriftResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &QueryMsg::MetadataDrift { denom })
+        .unwrap();
+    assert_eq!(drift.changes, vec![]);
+}
+
+#[test]
+fn mint_rate_limit_window_resets() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    let owner = Addr::unchecked(OWNER);
+    app.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &ExecuteMsg::CreateDenom {
+            subdenom: DENOM_NAME.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let limit = MintRateLimit {
+        max_per_window: Uint128::new(1000),
+        window_secs: 86400,
+    };
+    app.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &ExecuteMsg::SetMintRateLimit {
+            denom: denom.clone(),
+            limit: Some(limit.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let mint = |amount: u128| ExecuteMsg::MintTokens {
+        denom: denom.clone(),
+        amount: Uint128::new(amount),
+        mint_to_address: "alice".to_string(),
+    };
+    app.execute_contract(owner.clone(), contract.clone(), &mint(700), &[])
+        .unwrap();
+    let err = app
+        .execute_contract(owner.clone(), contract.clone(), &mint(301), &[])
+        .unwrap_err();
+    let retry_after = match err.downcast::<TokenFactoryError>().unwrap() {
+        TokenFactoryError::RateLimited { retry_after } => retry_after,
+        err => panic!("Unexpected error: {:?}", err),
+    };
+
+    let window_query = QueryMsg::MintWindow {
+        denom: denom.clone(),
+    };
+    let usage: MintWindowResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &window_query)
+        .unwrap();
+    assert_eq!(usage.limit, Some(limit));
+    let window = usage.window.unwrap();
+    assert_eq!(window.minted, Uint128::new(700));
+    assert_eq!(window.start.plus_seconds(86400), retry_after);
+
+    // still the same window right before it ends
+    app.advance_to_time(retry_after.minus_seconds(1)).unwrap();
+    app.execute_contract(owner.clone(), contract.clone(), &mint(301), &[])
+        .unwrap_err();
+
+    app.advance_to_time(retry_after).unwrap();
+    let usage: MintWindowResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &window_query)
+        .unwrap();
+    assert_eq!(
+        usage.window,
+        Some(MintWindow {
+            start: retry_after,
+            minted: Uint128::zero(),
+        })
+    );
+    app.execute_contract(owner, contract, &mint(1000), &[])
+        .unwrap();
+
+    let balance = app.wrap().query_balance("alice", &denom).unwrap();
+    assert_eq!(balance.amount, Uint128::new(1700));
+}
+
+#[test]
+fn create_token_initial_mint_is_rate_limited() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    // the limit is set before the denom exists
+    let owner = Addr::unchecked(OWNER);
+    app.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &ExecuteMsg::SetMintRateLimit {
+            denom: denom.clone(),
+            limit: Some(MintRateLimit {
+                max_per_window: Uint128::new(1000),
+                window_secs: 86400,
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let create = |amount: u128| ExecuteMsg::CreateToken {
+        subdenom: DENOM_NAME.to_string(),
+        metadata: None,
+        initial_mint: Some(InitialMint {
+            to: "alice".to_string(),
+            amount: Uint128::new(amount),
+        }),
+        metadata_in_create: false,
+        allow_reserved_symbol: false,
+    };
+    let err = app
+        .execute_contract(owner.clone(), contract.clone(), &create(1001), &[])
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<TokenFactoryError>().unwrap(),
+        TokenFactoryError::RateLimited { .. }
+    ));
+    // the whole creation was reverted
+    let status: DenomStatusResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contract,
+            &QueryMsg::DenomStatus {
+                creator_address: contract.to_string(),
+                subdenom: DENOM_NAME.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!status.exists);
+
+    app.execute_contract(owner.clone(), contract.clone(), &create(700), &[])
+        .unwrap();
+    let usage: MintWindowResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contract,
+            &QueryMsg::MintWindow {
+                denom: denom.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(usage.window.unwrap().minted, Uint128::new(700));
+
+    let mint = ExecuteMsg::MintTokens {
+        denom,
+        amount: Uint128::new(301),
+        mint_to_address: "alice".to_string(),
+    };
+    app.execute_contract(owner, contract, &mint, &[])
+        .unwrap_err();
+}
+
+#[test]
+fn creation_fee_is_paid_by_sender() {
+    let fee = vec![coin(5, "uion"), coin(10, "uosmo")];
+    let mut app = TokenFactoryApp::builder().with_creation_fee(fee).build();
+    let owner = Addr::unchecked(OWNER);
+    app.init_modules(|router, _, storage| {
+        let funds = vec![coin(100, "uion"), coin(100, "uosmo"), coin(100, "usdc")];
+        router.bank.init_balance(storage, &owner, funds)
+    })
+    .unwrap();
+    let contract = setup(&mut app);
+
+    let create = ExecuteMsg::CreateDenom {
+        subdenom: DENOM_NAME.to_string(),
+    };
+
+    // the uion part of the fee is missing
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            contract.clone(),
+            &create,
+            &[coin(20, "uosmo")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<TokenFactoryError>().unwrap(),
+        TokenFactoryError::InsufficientCreationFee {
+            required: coin(5, "uion"),
+            sent: coin(0, "uion"),
+        }
+    );
+
+    // the excess uosmo and the usdc come back
+    let funds = [coin(5, "uion"), coin(15, "uosmo"), coin(3, "usdc")];
+    app.execute_contract(owner.clone(), contract.clone(), &create, &funds)
+        .unwrap();
+
+    let balances = app.wrap().query_all_balances(&owner).unwrap();
+    assert_eq!(
+        balances,
+        vec![coin(95, "uion"), coin(90, "uosmo"), coin(100, "usdc")]
+    );
+    let balances = app.wrap().query_all_balances(&contract).unwrap();
+    assert_eq!(balances, vec![]);
+}
+
+#[test]
+fn creation_fee_in_an_alternative() {
+    let options = vec![
+        vec![coin(5, "uion"), coin(10, "uosmo")],
+        vec![coin(7, "usdc")],
+    ];
+    let mut app = TokenFactoryApp::builder()
+        .with_creation_fee(options)
+        .build();
+    let owner = Addr::unchecked(OWNER);
+    app.init_modules(|router, _, storage| {
+        let funds = vec![coin(100, "uion"), coin(100, "usdc")];
+        router.bank.init_balance(storage, &owner, funds)
+    })
+    .unwrap();
+    let contract = setup(&mut app);
+
+    // only usdc is sent, so the second option is paid
+    let create = ExecuteMsg::CreateDenom {
+        subdenom: DENOM_NAME.to_string(),
+    };
+    app.execute_contract(owner.clone(), contract.clone(), &create, &[coin(9, "usdc")])
+        .unwrap();
+
+    let balances = app.wrap().query_all_balances(&owner).unwrap();
+    assert_eq!(balances, vec![coin(100, "uion"), coin(93, "usdc")]);
+    let balances = app.wrap().query_all_balances(&contract).unwrap();
+    assert_eq!(balances, vec![]);
+}
+
+#[test]
+fn mint_batch_airdrop() {
+    let mut app = TokenFactoryApp::new();
+    let contract = setup(&mut app);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        contract.clone(),
+        &ExecuteMsg::CreateDenom {
+            subdenom: DENOM_NAME.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            contract.clone(),
+            &ExecuteMsg::MintBatch {
+                denom: denom.clone(),
+                recipients: vec![
+                    ("alice".to_string(), Uint128::new(100)),
+                    ("bob".to_string(), Uint128::new(200)),
+                    ("alice".to_string(), Uint128::new(50)),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+
+    // one mint per recipient
+    let mints = res.events.iter().filter(|e| e.ty == "tf_mint").count();
+    assert_eq!(mints, 2);
+    let result: MintBatchResult = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(
+        result,
+        MintBatchResult {
+            denom: denom.clone(),
+            amount: Uint128::new(350),
+        }
+    );
+
+    for (rcpt, expected) in [("alice", 150), ("bob", 200)] {
+        let balance = app.wrap().query_balance(rcpt, &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(expected));
+    }
+
+    // one bad entry rejects the whole batch
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            contract,
+            &ExecuteMsg::MintBatch {
+                denom: denom.clone(),
+                recipients: vec![
+                    ("alice".to_string(), Uint128::new(100)),
+                    ("Bob".to_string(), Uint128::new(200)),
+                ],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<TokenFactoryError>().unwrap(),
+        TokenFactoryError::InvalidAddress { .. }
+    ));
+    let balance = app.wrap().query_balance("alice", &denom).unwrap();
+    assert_eq!(balance.amount, Uint128::new(150));
+}
+
+#[test]
+fn create_denoms_in_one_call() {
+    let mut app = TokenFactoryApp::builder()
+        .with_creation_fee(vec![coin(10, "uosmo")])
+        .build();
+    let owner = Addr::unchecked(OWNER);
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, vec![coin(100, "uosmo")])
+    })
+    .unwrap();
+    let contract = setup(&mut app);
+    let create = |subdenoms: &[&str]| ExecuteMsg::CreateDenoms {
+        subdenoms: subdenoms.iter().map(|s| s.to_string()).collect(),
+    };
+
+    // rejected up front
+    let cases = [
+        (create(&[]), TokenFactoryError::EmptyBatch {}),
+        (
+            create(&["fundz", "gems", "fundz"]),
+            TokenFactoryError::DuplicateSubdenom {
+                subdenom: "fundz".to_string(),
+            },
+        ),
+        (
+            create(&["a"; 11]),
+            TokenFactoryError::BatchTooLarge { max: 10 },
+        ),
+        (
+            create(&["fundz", "gems", "coins"]),
+            TokenFactoryError::InsufficientCreationFee {
+                required: coin(30, "uosmo"),
+                sent: coin(25, "uosmo"),
+            },
+        ),
+    ];
+    for (msg, expected) in cases {
+        let err = app
+            .execute_contract(owner.clone(), contract.clone(), &msg, &[coin(25, "uosmo")])
+            .unwrap_err();
+        assert_eq!(err.downcast::<TokenFactoryError>().unwrap(), expected);
+    }
+
+    let res = app
+        .execute_contract(
+            owner.clone(),
+            contract.clone(),
+            &create(&["fundz", "gems", "coins"]),
+            &[coin(35, "uosmo")],
+        )
+        .unwrap();
+    let denoms: Vec<String> = ["fundz", "gems", "coins"]
+        .iter()
+        .map(|subdenom| format!("factory/{}/{}", contract, subdenom))
+        .collect();
+    let result: CreateDenomsResult = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(result.denoms, denoms);
+    // the excess is refunded
+    let balance = app.wrap().query_balance(&owner, "uosmo").unwrap();
+    assert_eq!(balance.amount, Uint128::new(70));
+
+    // all registered, in sorted order
+    let registry: CreatedDenomsResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &QueryMsg::CreatedDenoms(PageRequest::default()))
+        .unwrap();
+    let mut sorted = denoms.clone();
+    sorted.sort();
+    assert_eq!(registry.items, sorted);
+    assert_eq!(registry.next_start_after, None);
+    let page: CreatedDenomsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contract,
+            &QueryMsg::CreatedDenoms(PageRequest {
+                start_after: Some(sorted[0].clone()),
+                limit: Some(1),
+            }),
+        )
+        .unwrap();
+    assert_eq!(page.items, vec![sorted[1].clone()]);
+    assert_eq!(page.next_start_after, Some(sorted[1].clone()));
+
+    for denom in &denoms {
+        let admin: GetAdminResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &contract,
+                &QueryMsg::GetAdmin {
+                    denom: denom.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(admin.admin, Some(contract.to_string()));
+    }
+}
+
+#[test]
+fn simulate_execute_matches_execute() {
+    let mut app = TokenFactoryApp::builder().with_interception().build();
+    let contract = setup(&mut app);
+    let denom = format!("factory/{}/{}", contract, DENOM_NAME);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        contract.clone(),
+        &ExecuteMsg::SetMintRateLimit {
+            denom: denom.clone(),
+            limit: Some(MintRateLimit {
+                max_per_window: Uint128::new(1000),
+                window_secs: 3600,
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::MintBatch {
+        denom: denom.clone(),
+        recipients: vec![
+            ("alice".to_string(), Uint128::new(100)),
+            ("bob".to_string(), Uint128::new(200)),
+        ],
+    };
+    let simulate = |app: &TokenFactoryApp, sender: &str| {
+        app.wrap().query_wasm_smart::<SimulateExecuteResponse>(
+            &contract,
+            &QueryMsg::SimulateExecute {
+                sender: sender.to_string(),
+                funds: vec![],
+                msg: Box::new(msg.clone()),
+            },
+        )
+    };
+    let simulated = simulate(&app, OWNER).unwrap();
+
+    // nothing was sent, and the mints didn't count against the rate limit
+    assert!(app.intercepted_msgs().is_empty());
+    let window: MintWindowResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contract,
+            &QueryMsg::MintWindow {
+                denom: denom.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(window.window.unwrap().minted, Uint128::zero());
+
+    app.execute_contract(Addr::unchecked(OWNER), contract.clone(), &msg, &[])
+        .unwrap();
+    let executed: Vec<CosmosMsg<TokenFactoryMsg>> = app
+        .intercepted_msgs()
+        .into_iter()
+        .map(|(sender, msg)| {
+            assert_eq!(sender, contract);
+            msg.into()
+        })
+        .collect();
+    assert_eq!(simulated.messages.len(), 2);
+    assert_eq!(simulated.messages, executed);
+
+    // errors where the execute would
+    let err = simulate(&app, "alice").unwrap_err();
+    assert!(err.to_string().contains("Unauthorized"), "{}", err);
+}
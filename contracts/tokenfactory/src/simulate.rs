@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Order, Record, StdError, StdResult, Storage};
+use token_bindings::TokenFactoryQuery;
+
+use crate::contract::execute;
+use crate::msg::{ExecuteMsg, SimulateExecuteResponse};
+
+/// Runs `execute` on top of the contract's storage without changing it, returning
+/// the messages it would emit. Replies to those messages are not simulated.
+pub fn simulate_execute(
+    deps: Deps<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<SimulateExecuteResponse> {
+    let mut storage = DryRunStorage::new(deps.storage);
+    let deps_mut = DepsMut {
+        storage: &mut storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+    let res = execute(deps_mut, env, info, msg)
+        .map_err(|err| StdError::generic_err(format!("execute would fail: {}", err)))?;
+    Ok(SimulateExecuteResponse {
+        messages: res.messages.into_iter().map(|sub| sub.msg).collect(),
+    })
+}
+
+/// Reads through to `base` and keeps all writes in memory, where they are dropped with it
+struct DryRunStorage<'a> {
+    base: &'a dyn Storage,
+    /// None for removed keys
+    writes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<'a> DryRunStorage<'a> {
+    fn new(base: &'a dyn Storage) -> Self {
+        DryRunStorage {
+            base,
+            writes: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'a> Storage for DryRunStorage<'a> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.writes.get(key) {
+            Some(value) => value.clone(),
+            None => self.base.get(key),
+        }
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        let mut merged: BTreeMap<Vec<u8>, Vec<u8>> =
+            self.base.range(start, end, Order::Ascending).collect();
+        let bounds = (
+            start.map_or(Bound::Unbounded, |start| Bound::Included(start.to_vec())),
+            end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.to_vec())),
+        );
+        for (key, value) in self.writes.range(bounds) {
+            match value {
+                Some(value) => merged.insert(key.clone(), value.clone()),
+                None => merged.remove(key),
+            };
+        }
+        match order {
+            Order::Ascending => Box::new(merged.into_iter()),
+            Order::Descending => Box::new(merged.into_iter().rev()),
+        }
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.writes.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.writes.insert(key.to_vec(), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn writes_stay_in_the_overlay() {
+        let mut base = MockStorage::new();
+        base.set(b"a", b"1");
+        base.set(b"b", b"2");
+        base.set(b"d", b"4");
+
+        let mut storage = DryRunStorage::new(&base);
+        storage.set(b"b", b"20");
+        storage.set(b"c", b"3");
+        storage.remove(b"d");
+        assert_eq!(storage.get(b"b"), Some(b"20".to_vec()));
+        assert_eq!(storage.get(b"d"), None);
+
+        let keys = |order| -> Vec<Vec<u8>> {
+            storage
+                .range(Some(b"b"), None, order)
+                .map(|(key, _)| key)
+                .collect()
+        };
+        assert_eq!(keys(Order::Ascending), vec![b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!(keys(Order::Descending), vec![b"c".to_vec(), b"b".to_vec()]);
+
+        drop(storage);
+        assert_eq!(base.get(b"b"), Some(b"2".to_vec()));
+        assert_eq!(base.get(b"c"), None);
+        assert_eq!(base.get(b"d"), Some(b"4".to_vec()));
+    }
+}
@@ -1,12 +1,42 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
+use token_bindings::{CreateDenomPlan, Metadata, ReplyDispatcher};
+
+use crate::msg::{MintRateLimit, MintWindow};
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
 pub struct State {
     pub owner: Addr,
+    /// Never changes after instantiation. Missing in the state of older versions,
+    /// which had no force transfer switch, and read as disabled there.
+    #[serde(default)]
+    pub enable_force_transfer: bool,
 }
 
 pub const STATE: Item<State> = Item::new("state");
+
+/// The CreateToken being executed, until the CreateDenom reply comes back
+pub const PENDING_PLAN: Item<CreateDenomPlan> = Item::new("pending_plan");
+
+/// The metadata this contract last set for each denom
+pub const LAST_SET_METADATA: Map<&str, Metadata> = Map::new("last_set_metadata");
+
+/// The mint rate limit of each limited denom
+pub const MINT_RATE_LIMITS: Map<&str, MintRateLimit> = Map::new("mint_rate_limits");
+
+/// The latest window anything was minted in, for each limited denom
+pub const MINT_WINDOWS: Map<&str, MintWindow> = Map::new("mint_windows");
+
+/// Reply ids of the CreateDenom submessages of CreateDenoms, with the subdenom as context.
+/// Starts above the fixed reply ids of the contract.
+pub const CREATE_DENOMS_REPLIES: ReplyDispatcher =
+    ReplyDispatcher::new("create_denoms_reply_id", "create_denoms_replies", 100);
+
+/// Denoms created through CreateDenoms, added when their reply comes back
+pub const CREATED_DENOMS: Map<&str, Empty> = Map::new("created_denoms");
+
+/// Symbols metadata may not use without an override, upper-cased
+pub const RESERVED_SYMBOLS: Map<&str, Empty> = Map::new("reserved_symbols");
@@ -0,0 +1,341 @@
+//! Smoke test of the interface the wasm build exports. On native targets `#[entry_point]`
+//! generates nothing, so a changed entry point signature or a message the VM can't
+//! (de)serialize only showed up once the contract was compiled to wasm and uploaded.
+//! This checks what `cosmwasm-check` and the VM rely on, with `cargo test`:
+//! the entry points have the signatures the export glue accepts, and raw JSON goes
+//! through them as the VM passes it.
+//!
+//! When the wasm32 target is installed, the contract is also built for it and the exports
+//! and imports of the binary are checked like `cosmwasm-check` does, with and without the
+//! `library` feature. Without the target these tests are skipped.
+
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::process::Command;
+
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    from_slice, to_vec, ContractResult, CustomMsg, CustomQuery, Deps, DepsMut, Env, MessageInfo,
+    OwnedDeps, QueryResponse, Reply, Response,
+};
+use serde::de::DeserializeOwned;
+use token_bindings::{TokenFactoryMsg, TokenFactoryQuery};
+use token_bindings_test::TokenFactoryApp;
+
+use tokenfactory::contract::{execute, instantiate, query, reply};
+use tokenfactory::msg::{ConfigResponse, QueryMsg};
+
+// instantiate and execute take the same arguments
+type ExecuteFn<Q, M, C, E> = fn(DepsMut<Q>, Env, MessageInfo, M) -> Result<Response<C>, E>;
+type QueryFn<Q, M, E> = fn(Deps<Q>, Env, M) -> Result<QueryResponse, E>;
+type ReplyFn<Q, C, E> = fn(DepsMut<Q>, Env, Reply) -> Result<Response<C>, E>;
+
+// The bounds cosmwasm-std's `do_instantiate`, `do_execute`, `do_query` and `do_reply` put on
+// the functions `#[entry_point]` exports, which they only check on wasm32
+fn instantiate_entry_point<Q, M, C, E>(_: ExecuteFn<Q, M, C, E>)
+where
+    Q: CustomQuery,
+    M: DeserializeOwned,
+    C: CustomMsg,
+    E: ToString,
+{
+}
+
+fn execute_entry_point<Q, M, C, E>(_: ExecuteFn<Q, M, C, E>)
+where
+    Q: CustomQuery,
+    M: DeserializeOwned,
+    C: CustomMsg,
+    E: ToString,
+{
+}
+
+fn query_entry_point<Q, M, E>(_: QueryFn<Q, M, E>)
+where
+    Q: CustomQuery,
+    M: DeserializeOwned,
+    E: ToString,
+{
+}
+
+fn reply_entry_point<Q, C, E>(_: ReplyFn<Q, C, E>)
+where
+    Q: CustomQuery,
+    C: CustomMsg,
+    E: ToString,
+{
+}
+
+#[test]
+fn entry_points_can_be_exported() {
+    instantiate_entry_point(instantiate);
+    execute_entry_point(execute);
+    query_entry_point(query);
+    reply_entry_point(reply);
+}
+
+#[test]
+fn every_query_has_a_response_schema() {
+    QueryMsg::response_schemas().unwrap();
+}
+
+fn deps() -> OwnedDeps<MockStorage, MockApi, TokenFactoryApp, TokenFactoryQuery> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: TokenFactoryApp::new(),
+        custom_query_type: PhantomData,
+    }
+}
+
+// The VM deserializes the message, then serializes the result as a ContractResult
+fn raw_instantiate(deps: DepsMut<TokenFactoryQuery>, sender: &str, msg: &[u8]) -> Vec<u8> {
+    let res = instantiate(
+        deps,
+        mock_env(),
+        mock_info(sender, &[]),
+        from_slice(msg).unwrap(),
+    );
+    to_vec(&ContractResult::from(res)).unwrap()
+}
+
+fn raw_execute(deps: DepsMut<TokenFactoryQuery>, sender: &str, msg: &[u8]) -> Vec<u8> {
+    let res = execute(
+        deps,
+        mock_env(),
+        mock_info(sender, &[]),
+        from_slice(msg).unwrap(),
+    );
+    to_vec(&ContractResult::from(res)).unwrap()
+}
+
+fn raw_query(deps: Deps<TokenFactoryQuery>, msg: &[u8]) -> Vec<u8> {
+    let res = query(deps, mock_env(), from_slice(msg).unwrap());
+    to_vec(&ContractResult::from(res)).unwrap()
+}
+
+#[test]
+fn raw_json_round_trip() {
+    let mut deps = deps();
+
+    let res = raw_instantiate(deps.as_mut(), "owner", br#"{}"#);
+    let res: ContractResult<Response<TokenFactoryMsg>> = from_slice(&res).unwrap();
+    res.unwrap();
+
+    let res = raw_execute(
+        deps.as_mut(),
+        "owner",
+        br#"{"create_denom":{"subdenom":"fundz"}}"#,
+    );
+    let res: ContractResult<Response<TokenFactoryMsg>> = from_slice(&res).unwrap();
+    assert_eq!(res.unwrap().messages.len(), 1);
+
+    // errors are returned to the VM as strings, not as panics
+    let res = raw_execute(
+        deps.as_mut(),
+        "intruder",
+        br#"{"create_denom":{"subdenom":"fundz"}}"#,
+    );
+    let res: ContractResult<Response<TokenFactoryMsg>> = from_slice(&res).unwrap();
+    assert_eq!(res.unwrap_err(), "Unauthorized");
+
+    let res = raw_query(deps.as_ref(), br#"{"config":{}}"#);
+    let res: ContractResult<QueryResponse> = from_slice(&res).unwrap();
+    let config: ConfigResponse = from_slice(&res.unwrap()).unwrap();
+    assert_eq!(config.owner, "owner");
+    assert!(!config.enable_force_transfer);
+}
+
+// What the VM offers, as passed to cosmwasm-check in CI
+const AVAILABLE_CAPABILITIES: &[&str] = &[
+    "token_factory",
+    "iterator",
+    "staking",
+    "stargate",
+    "cosmwasm_1_1",
+];
+
+// The functions cosmwasm-vm 1.1 provides to contracts, in the "env" module
+const SUPPORTED_IMPORTS: &[&str] = &[
+    "abort",
+    "db_read",
+    "db_write",
+    "db_remove",
+    "db_scan",
+    "db_next",
+    "addr_validate",
+    "addr_canonicalize",
+    "addr_humanize",
+    "secp256k1_verify",
+    "secp256k1_recover_pubkey",
+    "ed25519_verify",
+    "ed25519_batch_verify",
+    "debug",
+    "query_chain",
+];
+
+const ENTRY_POINTS: &[&str] = &["instantiate", "execute", "query", "reply"];
+
+/// Builds the contract for wasm32 with `features`, None if the target isn't installed
+fn build_wasm(features: &str) -> Option<Vec<u8>> {
+    let sysroot = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+        .args(["--print", "sysroot"])
+        .output()
+        .unwrap();
+    let sysroot = String::from_utf8(sysroot.stdout).unwrap();
+    if !Path::new(sysroot.trim())
+        .join("lib/rustlib/wasm32-unknown-unknown")
+        .exists()
+    {
+        eprintln!("wasm32-unknown-unknown is not installed, skipping");
+        return None;
+    }
+
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("wasm-{}", features));
+    let status = Command::new(env!("CARGO"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--lib", "--target", "wasm32-unknown-unknown"])
+        .args(["--features", features])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "wasm build failed");
+    let wasm = target_dir.join("wasm32-unknown-unknown/debug/tokenfactory.wasm");
+    Some(std::fs::read(wasm).unwrap())
+}
+
+/// The exported names and the (module, name) of the imports of a wasm binary
+#[derive(Default)]
+struct Interface {
+    exports: BTreeSet<String>,
+    imports: BTreeSet<(String, String)>,
+}
+
+fn read_leb128(wasm: &[u8], pos: &mut usize) -> u64 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = wasm[*pos];
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+fn read_name(wasm: &[u8], pos: &mut usize) -> String {
+    let len = read_leb128(wasm, pos) as usize;
+    let name = String::from_utf8(wasm[*pos..*pos + len].to_vec()).unwrap();
+    *pos += len;
+    name
+}
+
+/// Reads the import and export sections, skipping all others
+fn parse_interface(wasm: &[u8]) -> Interface {
+    assert_eq!(&wasm[..8], b"\0asm\x01\0\0\0", "not a wasm 1 binary");
+    let mut interface = Interface::default();
+    let mut pos = 8;
+    while pos < wasm.len() {
+        let id = wasm[pos];
+        pos += 1;
+        let size = read_leb128(wasm, &mut pos) as usize;
+        let end = pos + size;
+        match id {
+            // import section, only functions are expected
+            2 => {
+                let count = read_leb128(wasm, &mut pos);
+                for _ in 0..count {
+                    let module = read_name(wasm, &mut pos);
+                    let name = read_name(wasm, &mut pos);
+                    assert_eq!(wasm[pos], 0, "import {}.{} is not a function", module, name);
+                    pos += 1;
+                    read_leb128(wasm, &mut pos);
+                    interface.imports.insert((module, name));
+                }
+            }
+            // export section
+            7 => {
+                let count = read_leb128(wasm, &mut pos);
+                for _ in 0..count {
+                    interface.exports.insert(read_name(wasm, &mut pos));
+                    pos += 1;
+                    read_leb128(wasm, &mut pos);
+                }
+            }
+            _ => {}
+        }
+        pos = end;
+    }
+    interface
+}
+
+/// The static checks of cosmwasm-check on the interface
+fn check_interface(interface: &Interface) {
+    let exports = &interface.exports;
+    let versions: Vec<_> = exports
+        .iter()
+        .filter(|name| name.starts_with("interface_version_"))
+        .collect();
+    assert_eq!(versions, vec!["interface_version_8"]);
+    for name in ["allocate", "deallocate"] {
+        assert!(exports.contains(name), "missing export {}", name);
+    }
+
+    let required: Vec<_> = exports
+        .iter()
+        .filter_map(|name| name.strip_prefix("requires_"))
+        .collect();
+    assert!(required.contains(&"token_factory"));
+    for capability in required {
+        assert!(
+            AVAILABLE_CAPABILITIES.contains(&capability),
+            "unavailable capability {}",
+            capability
+        );
+    }
+
+    for (module, name) in &interface.imports {
+        assert!(
+            module == "env" && SUPPORTED_IMPORTS.contains(&name.as_str()),
+            "unsupported import {}.{}",
+            module,
+            name
+        );
+    }
+}
+
+#[test]
+fn wasm_passes_static_checks() {
+    let wasm = match build_wasm("") {
+        Some(wasm) => wasm,
+        None => return,
+    };
+    let interface = parse_interface(&wasm);
+    check_interface(&interface);
+    for name in ENTRY_POINTS {
+        assert!(interface.exports.contains(*name), "missing export {}", name);
+    }
+}
+
+#[test]
+fn library_feature_exports_no_entry_points() {
+    let wasm = match build_wasm("library") {
+        Some(wasm) => wasm,
+        None => return,
+    };
+    // still a valid contract interface, without the entry points
+    let interface = parse_interface(&wasm);
+    check_interface(&interface);
+    for name in ENTRY_POINTS {
+        assert!(
+            !interface.exports.contains(*name),
+            "unexpected export {}",
+            name
+        );
+    }
+}
@@ -1,20 +1,217 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, OverflowOperation, StdError, Uint128};
 use thiserror::Error;
+use token_bindings::MetadataError;
 
+/// Errors returned by `TokenFactoryModule` when executing or querying
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
+    /// Storage, serialization or address validation error
     #[error("{0}")]
     Std(#[from] StdError),
 
+    /// Supply arithmetic overflowed
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    /// Metadata rejected by the bank's stateless checks, see `validate_metadata`
+    #[error("{0}")]
+    InvalidMetadata(#[from] MetadataError),
+
+    /// The denom derived from creator and subdenom is not a valid denom
     #[error("Invalid full denom '{full_denom}'")]
-    InvalidFullDenom { full_denom: String },
+    InvalidFullDenom {
+        /// The rejected denom
+        full_denom: String,
+    },
 
+    /// The creator doesn't have the address format configured with `with_creator_format`
+    #[error("Invalid creator address '{creator}': {reason}")]
+    InvalidCreatorAddress {
+        /// The rejected creator
+        creator: String,
+        /// What is wrong with it
+        reason: String,
+    },
+
+    /// Sender is not the admin of the denom
     #[error("Not admin of token, cannot perfrom action")]
     NotTokenAdmin,
 
+    /// The (creator, subdenom) pair was already used
     #[error("Token denom already exists, cannot create again")]
     TokenExists,
 
+    /// The denom was never created by the token factory
     #[error("Token denom was never created")]
     TokenDoesntExist,
+
+    /// The denom was created by another module, eg. a native or ibc denom
+    #[error("Denom {denom} was not created by the token factory")]
+    NotFactoryDenom {
+        /// The queried denom
+        denom: String,
+    },
+
+    /// Denom creation while the module is disabled by its params
+    #[error("Token factory is disabled")]
+    TokenFactoryDisabled,
+
+    /// Mint with a dedup key already used for the denom
+    #[error("Mint with key {key} was already executed")]
+    DuplicateMint {
+        /// The repeated key
+        key: String,
+    },
+
+    /// A message needing a feature the module was configured without
+    #[error("Not supported by this chain ({features})")]
+    FeatureUnsupported {
+        /// The features of the module, as capability string
+        features: String,
+    },
+
+    /// Mint of more than the configured `max_mint_per_tx`
+    #[error("Cannot mint {requested} in one transaction, the limit is {limit}")]
+    MintLimitExceeded {
+        /// The configured limit
+        limit: Uint128,
+        /// The amount of the rejected mint
+        requested: Uint128,
+    },
+
+    /// Mint of a denom whose minting was switched off with `set_mint_enabled`
+    #[error("Minting of {denom} is disabled")]
+    MintingDisabled {
+        /// The denom
+        denom: String,
+    },
+
+    /// Mint which would take the supply beyond the cap set with `set_supply_cap`
+    #[error("Cannot mint {requested}, the supply of {supply} is capped at {cap}")]
+    SupplyCapExceeded {
+        /// The configured cap
+        cap: Uint128,
+        /// The supply before the mint
+        supply: Uint128,
+        /// The amount of the rejected mint
+        requested: Uint128,
+    },
+
+    /// `CreateDenom` by a payer whose `choose_creation_fee` is none of the accepted fees
+    #[error("Creation fee {fee} is not accepted")]
+    CreationFeeNotAccepted {
+        /// The chosen fee
+        fee: String,
+    },
+
+    /// `GarbageCollect` without `with_gc_empty_denoms`
+    #[error("Garbage collection of denoms is disabled")]
+    GarbageCollectionDisabled,
+
+    /// `GarbageCollect` of a denom which still has a supply
+    #[error("Denom still has a supply of {supply}")]
+    DenomNotEmpty {
+        /// The remaining supply
+        supply: Uint128,
+    },
+
+    /// Burn or force transfer of zero tokens
+    #[error("Amount must be positive")]
+    ZeroAmount,
+
+    /// SetMetadata on a denom whose metadata was locked by LockMetadata
+    #[error("Metadata of {denom} is locked")]
+    MetadataLocked {
+        /// The locked denom
+        denom: String,
+    },
+
+    /// `MetadataHistory` query without `with_metadata_history`
+    #[error("Metadata history is not recorded")]
+    MetadataHistoryDisabled,
+
+    /// Burn with a `burn_from_address` other than the sender, without the `burn_from` feature
+    #[error("Burning from other addresses than the sender is not supported")]
+    BurnFromNotSupported,
+}
+
+/// The failure of a message executed on a `TokenFactoryApp`, whichever part of the app
+/// it came from, so tests can `assert_eq!` a single enum. See `unwrap_token_factory_err`.
+#[derive(Debug, PartialEq)]
+pub enum TokenFactoryModuleError {
+    /// Rejected by the token factory module
+    Module(ContractError),
+    /// An address failed validation
+    InvalidAddress {
+        /// The message of the api
+        message: String,
+    },
+    /// The bank could not take the funds from a balance, eg. to pay the creation fee
+    InsufficientFunds {
+        /// The balance of the denom
+        balance: String,
+        /// The amount taken from it
+        required: String,
+    },
+    /// Any other `StdError`
+    Std(StdError),
+    /// Anything else, eg. an error of a contract, by its message
+    Other(String),
+}
+
+/// Classifies the error of a failed `TokenFactoryApp` execution:
+///
+/// ```
+/// use cosmwasm_std::{Addr, Uint128};
+/// use cw_multi_test::Executor;
+/// use token_bindings::TokenMsg;
+/// use token_bindings_test::{
+///     unwrap_token_factory_err, ContractError, TokenFactoryApp, TokenFactoryModuleError,
+/// };
+///
+/// let mut app = TokenFactoryApp::new();
+/// let mint = TokenMsg::mint_contract_tokens(
+///     "factory/creator/fundz".to_string(),
+///     Uint128::new(1),
+///     "rcpt".to_string(),
+/// );
+/// let err = app.execute(Addr::unchecked("creator"), mint.into()).unwrap_err();
+/// assert_eq!(
+///     unwrap_token_factory_err(err),
+///     TokenFactoryModuleError::Module(ContractError::TokenDoesntExist)
+/// );
+/// ```
+pub fn unwrap_token_factory_err(err: anyhow::Error) -> TokenFactoryModuleError {
+    let err = match err.downcast::<ContractError>() {
+        Ok(ContractError::Std(err)) => return classify_std_error(err),
+        Ok(err) => return TokenFactoryModuleError::Module(err),
+        Err(err) => err,
+    };
+    match err.downcast::<StdError>() {
+        Ok(err) => classify_std_error(err),
+        Err(err) => TokenFactoryModuleError::Other(err.to_string()),
+    }
+}
+
+fn classify_std_error(err: StdError) -> TokenFactoryModuleError {
+    match err {
+        // the bank subtracts from the balance, so only that can underflow
+        StdError::Overflow {
+            source:
+                OverflowError {
+                    operation: OverflowOperation::Sub,
+                    operand1,
+                    operand2,
+                },
+            ..
+        } => TokenFactoryModuleError::InsufficientFunds {
+            balance: operand1,
+            required: operand2,
+        },
+        // the messages of MockApi::addr_validate
+        StdError::GenericErr { msg, .. } if msg.starts_with("Invalid input") => {
+            TokenFactoryModuleError::InvalidAddress { message: msg }
+        }
+        err => TokenFactoryModuleError::Std(err),
+    }
 }
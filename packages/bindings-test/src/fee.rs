@@ -0,0 +1,355 @@
+use anyhow::Result as AnyResult;
+
+use cosmwasm_std::{Addr, Attribute, BankMsg, BlockInfo, Coin, Storage};
+
+/// How `TokenFactoryModule` charges the denom creation fee. Chains differ here, so
+/// implement this to model a chain instead of forking the module.
+///
+/// The policy cannot execute messages itself, as the router is generic over the app's
+/// message types. Instead it returns bank messages, which the module executes as the
+/// creator before creating the denom. If any of them fails, the denom is not created.
+pub trait CreationFeePolicy {
+    /// Charges `fee`, the creation fee option `sender` pays, to `sender`.
+    /// `fee` is empty when the params have no creation fee.
+    fn charge(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        sender: &Addr,
+        fee: &[Coin],
+    ) -> AnyResult<FeeCharge>;
+}
+
+/// The denom creation fee: the coins charged for every denom, or several alternatives
+/// of which the creator pays one, for chains taking the fee in one of several denoms.
+///
+/// The module params hold a single fee, the first option. `TokenQuery::CreationFeeOptions`
+/// reports all options and `TokenQuery::CreationFeeIn` searches all of them. `CreateDenom`
+/// charges the option the creator chose with `choose_creation_fee`, else the first one its
+/// balance covers, else the first one, failing for lack of funds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreationFee {
+    /// The accepted fees, no option means no fee
+    pub options: Vec<Vec<Coin>>,
+}
+
+impl CreationFee {
+    /// The fee of the params and the alternatives
+    pub(crate) fn split(self) -> (Vec<Coin>, Vec<Vec<Coin>>) {
+        let mut options = self.options.into_iter();
+        (options.next().unwrap_or_default(), options.collect())
+    }
+}
+
+impl From<Vec<Coin>> for CreationFee {
+    fn from(fee: Vec<Coin>) -> Self {
+        CreationFee { options: vec![fee] }
+    }
+}
+
+impl From<Vec<Vec<Coin>>> for CreationFee {
+    fn from(options: Vec<Vec<Coin>>) -> Self {
+        CreationFee { options }
+    }
+}
+
+/// What a `CreationFeePolicy` does for one denom creation
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeeCharge {
+    /// Executed as the creator, in order
+    pub msgs: Vec<BankMsg>,
+    /// Appended to the `create_denom` event
+    pub attributes: Vec<Attribute>,
+}
+
+/// Burns the creation fee. This is the default, as the mock has no community pool.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BurnFee;
+
+impl CreationFeePolicy for BurnFee {
+    fn charge(
+        &self,
+        _: &dyn Storage,
+        _: &BlockInfo,
+        _: &Addr,
+        fee: &[Coin],
+    ) -> AnyResult<FeeCharge> {
+        let msgs = if fee.is_empty() {
+            vec![]
+        } else {
+            vec![BankMsg::Burn {
+                amount: fee.to_vec(),
+            }]
+        };
+        Ok(FeeCharge {
+            msgs,
+            attributes: vec![],
+        })
+    }
+}
+
+/// Sends the creation fee to a collector address, eg. the community pool
+/// or a fee collector module account
+#[derive(Clone, Debug, PartialEq)]
+pub struct SendToCollector {
+    /// The receiver of all creation fees
+    pub collector: Addr,
+}
+
+impl SendToCollector {
+    /// Sends all fees to `collector`
+    pub fn new(collector: impl Into<String>) -> Self {
+        SendToCollector {
+            collector: Addr::unchecked(collector),
+        }
+    }
+}
+
+impl CreationFeePolicy for SendToCollector {
+    fn charge(
+        &self,
+        _: &dyn Storage,
+        _: &BlockInfo,
+        _: &Addr,
+        fee: &[Coin],
+    ) -> AnyResult<FeeCharge> {
+        if fee.is_empty() {
+            return Ok(FeeCharge::default());
+        }
+        Ok(FeeCharge {
+            msgs: vec![BankMsg::Send {
+                to_address: self.collector.to_string(),
+                amount: fee.to_vec(),
+            }],
+            attributes: vec![Attribute::new("fee_collector", &self.collector)],
+        })
+    }
+}
+
+/// Never charges anything, whatever the params say. The `CreationFeeIn` query
+/// still reports the fee of the params.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NoFee;
+
+impl CreationFeePolicy for NoFee {
+    fn charge(&self, _: &dyn Storage, _: &BlockInfo, _: &Addr, _: &[Coin]) -> AnyResult<FeeCharge> {
+        Ok(FeeCharge::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        unwrap_token_factory_err, ContractError, TokenFactoryApp, TokenFactoryModuleError,
+    };
+    use cosmwasm_std::{coin, coins, Uint128};
+    use cw_multi_test::{AppResponse, Executor};
+    use token_bindings::{Params, TokenMsg, TokenQuerier};
+
+    const FEE: u128 = 100;
+
+    fn app_with_policy(policy: impl CreationFeePolicy + 'static) -> TokenFactoryApp {
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(coins(FEE, "uosmo"))
+            .with_fee_policy(Box::new(policy))
+            .build();
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("creator"), coins(150, "uosmo"))
+        })
+        .unwrap();
+        app
+    }
+
+    fn create(app: &mut TokenFactoryApp, subdenom: &str) -> AnyResult<AppResponse> {
+        let create = TokenMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+            metadata: None,
+        };
+        app.execute(Addr::unchecked("creator"), create.into())
+    }
+
+    fn balance(app: &TokenFactoryApp, address: &str) -> Uint128 {
+        app.wrap().query_balance(address, "uosmo").unwrap().amount
+    }
+
+    #[test]
+    fn burn_fee() {
+        let mut app = app_with_policy(BurnFee);
+        create(&mut app, "fundz").unwrap();
+        assert_eq!(balance(&app, "creator"), Uint128::new(150 - FEE));
+
+        // not enough left, nothing is created
+        let err = create(&mut app, "gems").unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::InsufficientFunds {
+                balance: "50".to_string(),
+                required: "100".to_string(),
+            }
+        );
+        assert_eq!(app.denom_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn send_to_collector() {
+        let mut app = app_with_policy(SendToCollector::new("community_pool"));
+        let res = create(&mut app, "fundz").unwrap();
+        let event = res.events.iter().find(|e| e.ty == "create_denom").unwrap();
+        assert!(event
+            .attributes
+            .contains(&Attribute::new("fee_collector", "community_pool")));
+
+        assert_eq!(balance(&app, "creator"), Uint128::new(150 - FEE));
+        assert_eq!(balance(&app, "community_pool"), Uint128::new(FEE));
+
+        let err = create(&mut app, "gems").unwrap_err();
+        assert!(matches!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::InsufficientFunds { .. }
+        ));
+        assert_eq!(balance(&app, "community_pool"), Uint128::new(FEE));
+    }
+
+    #[test]
+    fn no_fee() {
+        let mut app = app_with_policy(NoFee);
+        create(&mut app, "fundz").unwrap();
+        create(&mut app, "gems").unwrap();
+        assert_eq!(balance(&app, "creator"), Uint128::new(150));
+        assert_eq!(app.denom_count().unwrap(), 2);
+
+        // the params are unchanged
+        let querier = app.wrap();
+        let params = TokenQuerier::new(&querier).params().unwrap().params;
+        assert_eq!(params.denom_creation_fee, vec![coin(FEE, "uosmo")]);
+    }
+
+    #[test]
+    fn fee_options() {
+        let options = vec![coins(FEE, "uosmo"), vec![coin(5, "uion"), coin(20, "usdc")]];
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(options)
+            .build();
+        let funds = vec![
+            coin(150, "uosmo"),
+            coin(5, "uion"),
+            coin(30, "usdc"),
+            coin(10, "uatom"),
+        ];
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("creator"), funds)
+        })
+        .unwrap();
+        let balances = |app: &TokenFactoryApp| app.wrap().query_all_balances("creator").unwrap();
+
+        // the first option while it can be paid, then the second
+        create(&mut app, "fundz").unwrap();
+        assert_eq!(
+            balances(&app),
+            vec![
+                coin(10, "uatom"),
+                coin(5, "uion"),
+                coin(50, "uosmo"),
+                coin(30, "usdc")
+            ]
+        );
+        create(&mut app, "gems").unwrap();
+        assert_eq!(
+            balances(&app),
+            vec![coin(10, "uatom"), coin(50, "uosmo"), coin(10, "usdc")]
+        );
+
+        // neither option is covered, uatom is not accepted
+        let err = create(&mut app, "coins").unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::InsufficientFunds {
+                balance: "50".to_string(),
+                required: "100".to_string(),
+            }
+        );
+        assert_eq!(app.denom_count().unwrap(), 2);
+
+        // queries report the first option
+        let querier = app.wrap();
+        let params = TokenQuerier::new(&querier).params().unwrap().params;
+        assert_eq!(params.denom_creation_fee, coins(FEE, "uosmo"));
+    }
+
+    #[test]
+    fn chosen_fee_option() {
+        let options = vec![coins(FEE, "uosmo"), coins(5, "uion")];
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(options)
+            .build();
+        let funds = vec![coin(10, "uion"), coin(1000, "uosmo")];
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("creator"), funds)
+        })
+        .unwrap();
+        let balances = |app: &TokenFactoryApp| app.wrap().query_all_balances("creator").unwrap();
+
+        // pays the second option, although the first one is covered
+        app.choose_creation_fee("creator", coins(5, "uion"))
+            .unwrap();
+        create(&mut app, "fundz").unwrap();
+        assert_eq!(balances(&app), vec![coin(5, "uion"), coin(1000, "uosmo")]);
+
+        app.choose_creation_fee("creator", coins(5, "uatom"))
+            .unwrap();
+        let err = create(&mut app, "gems").unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::CreationFeeNotAccepted {
+                fee: "5uatom".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn params_set_the_first_fee_option() {
+        let options = vec![coins(FEE, "uosmo"), coins(5, "uion")];
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(options)
+            .build();
+        app.init_modules(|router, _, storage| {
+            router.bank.init_balance(
+                storage,
+                &Addr::unchecked("creator"),
+                vec![coin(1000, "uosmo")],
+            )
+        })
+        .unwrap();
+
+        // the params fee replaces the first option, the alternative stays accepted
+        let params = Params {
+            denom_creation_fee: coins(300, "uosmo"),
+            enabled: true,
+        };
+        app.init_modules(|router, _, storage| router.custom.set_params(storage, &params))
+            .unwrap();
+        assert_eq!(
+            app.creation_fee().unwrap(),
+            CreationFee {
+                options: vec![coins(300, "uosmo"), coins(5, "uion")],
+            }
+        );
+        create(&mut app, "fundz").unwrap();
+        let balance = app.wrap().query_balance("creator", "uosmo").unwrap();
+        assert_eq!(balance.amount, Uint128::new(700));
+
+        // a single fee drops the alternatives
+        app.set_creation_fee(coins(50, "uosmo")).unwrap();
+        assert_eq!(
+            app.creation_fee().unwrap(),
+            CreationFee::from(coins(50, "uosmo"))
+        );
+    }
+}
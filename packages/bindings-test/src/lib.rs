@@ -1,6 +1,70 @@
-pub mod error;
+//! Multitest support for contracts using the token factory bindings.
+//!
+//! [`TokenFactoryApp`] is a `cw_multi_test::App` with [`TokenFactoryModule`] handling
+//! all `TokenFactoryMsg` and `TokenFactoryQuery`, so contracts can be tested against
+//! a token factory without a chain. Use [`TokenFactoryAppBuilder`] to configure it:
+//!
+//! ```
+//! use cosmwasm_std::coin;
+//! use token_bindings_test::TokenFactoryApp;
+//!
+//! let app = TokenFactoryApp::builder()
+//!     .with_creation_fee(vec![coin(10_000_000, "uosmo")])
+//!     .build();
+//! assert_eq!(app.query_count(), 0);
+//! ```
+//!
+//! A full scenario, creating a denom and minting it:
+//!
+//! ```
+//! use cosmwasm_std::{coin, Addr, Uint128};
+//! use cw_multi_test::Executor;
+//! use token_bindings::TokenMsg;
+//! use token_bindings_test::{
+//!     unwrap_token_factory_err, ContractError, TokenFactoryApp, TokenFactoryModuleError,
+//! };
+//!
+//! let creator = Addr::unchecked("creator");
+//! let mut app = TokenFactoryApp::builder()
+//!     .with_creation_fee(vec![coin(100, "uosmo")])
+//!     .build();
+//! app.init_modules(|router, _, storage| {
+//!     router.bank.init_balance(storage, &creator, vec![coin(100, "uosmo")])
+//! })
+//! .unwrap();
+//!
+//! let create = TokenMsg::CreateDenom {
+//!     subdenom: "fundz".to_string(),
+//!     metadata: None,
+//! };
+//! app.execute(creator.clone(), create.into()).unwrap();
+//!
+//! let denom = "factory/creator/fundz".to_string();
+//! let mint = TokenMsg::mint_contract_tokens(denom.clone(), Uint128::new(500), "rcpt".to_string());
+//! app.execute(creator, mint.clone().into()).unwrap();
+//! let balance = app.wrap().query_balance("rcpt", &denom).unwrap();
+//! assert_eq!(balance.amount, Uint128::new(500));
+//!
+//! // only the admin may mint. Failures may come from the module, the bank or the api,
+//! // `unwrap_token_factory_err` sorts them into one enum to assert on
+//! let err = app.execute(Addr::unchecked("rcpt"), mint.into()).unwrap_err();
+//! assert_eq!(
+//!     unwrap_token_factory_err(err),
+//!     TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+//! );
+//! ```
+#![deny(missing_docs)]
+
+mod error;
+mod fee;
 mod multitest;
+mod scenario;
 
+pub use error::{unwrap_token_factory_err, ContractError, TokenFactoryModuleError};
+pub use fee::{BurnFee, CreationFee, CreationFeePolicy, FeeCharge, NoFee, SendToCollector};
 pub use multitest::{
-    TokenFactoryApp, TokenFactoryAppWrapped, TokenFactoryError, TokenFactoryModule,
+    AdminChange, AdminChangeCallback, DenomState, Snapshot, TokenFactoryApp,
+    TokenFactoryAppBuilder, TokenFactoryAppWrapped, TokenFactoryError, TokenFactoryModule,
+    TokenFactoryRouter, TokenFactoryState, TokenFactorySudo, BLOCK_TIME, DEFAULT_SUBDENOM_CHARS,
 };
+pub use scenario::{Scenario, ScenarioStep, SCENARIO_FUNDING_DENOM, SCENARIO_USERS};
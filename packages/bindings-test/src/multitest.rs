@@ -1,6 +1,8 @@
-use anyhow::{bail, Result as AnyResult};
+use anyhow::Result as AnyResult;
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
 use std::cmp::max;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
@@ -8,37 +10,624 @@ use thiserror::Error;
 
 use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::{
-    coins, to_binary, Addr, Api, Binary, BlockInfo, CustomQuery, Empty, Querier, QuerierResult,
-    StdError, Storage,
+    coins, from_binary, to_binary, Addr, AllBalanceResponse, Api, BankMsg, BankQuery, Binary,
+    BlockInfo, Coin, CustomQuery, Empty, Event, Order, Querier, QuerierResult, Record, StdError,
+    StdResult, Storage, Timestamp, Uint128,
 };
 use cw_multi_test::{
-    App, AppResponse, BankKeeper, BankSudo, BasicAppBuilder, CosmosRouter, Module, WasmKeeper,
+    App, AppResponse, BankKeeper, BankSudo, BasicAppBuilder, CosmosRouter, FailingDistribution,
+    FailingStaking, Module, Router, WasmKeeper, WasmSudo,
 };
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
 
+use token_bindings::events::{
+    ATTR_AMOUNT, ATTR_BEFORE_SEND_HOOK_ADDRESS, ATTR_BURN_FROM_ADDRESS, ATTR_CREATOR, ATTR_DENOM,
+    ATTR_MINT_TO_ADDRESS, ATTR_NEW_ADMIN, ATTR_NEW_TOKEN_DENOM, ATTR_SENDER,
+    ATTR_TRANSFER_FROM_ADDRESS, ATTR_TRANSFER_TO_ADDRESS, EVENT_BURN, EVENT_CHANGE_ADMIN,
+    EVENT_CREATE_DENOM, EVENT_FORCE_TRANSFER, EVENT_LOCK_DENOM_METADATA, EVENT_MINT,
+    EVENT_SET_BEFORE_SEND_HOOK, EVENT_SET_DENOM_METADATA,
+};
 use token_bindings::{
-    AdminResponse, CreateDenomResponse, DenomsByCreatorResponse, FullDenomResponse, Metadata,
-    MetadataResponse, TokenFactoryMsg, TokenFactoryQuery, TokenMsg, TokenQuery,
+    has_sufficient_funds, is_factory_denom, validate_creator_prefix, validate_full_denom,
+    validate_metadata, AdminResponse, BeforeSendHookMsg, CanMintResponse, CreateDenomResponse,
+    CreationFeeInResponse, CreationFeeOptionsResponse, CreatorTotalSupplyResponse,
+    DenomCreatorResponse, DenomsByCreatorResponse, FullDenomResponse, HasMetadataResponse,
+    Metadata, MetadataHistoryResponse, MetadataLockedResponse, MetadataResponse, Params,
+    ParamsResponse, ParseDenomResponse, SubdenomRulesResponse, TokenFactoryFeatures,
+    TokenFactoryMsg, TokenFactoryQuery, TokenMsg, TokenQuery, DENOM_PREFIX, FEATURES_VERSION,
+    MAX_SUBDENOM_LENGTH, SUBDENOM_CHARS,
 };
 
-use crate::error::ContractError;
+use crate::fee::{BurnFee, CreationFee, CreationFeePolicy};
+use crate::ContractError;
+
+/// Mock implementation of the token factory, to be used as the custom module of an `App`
+#[derive(Default)]
+pub struct TokenFactoryModule {
+    /// If set, executed messages are only recorded, never applied
+    intercept: bool,
+    /// Messages recorded while intercepting, along with their sender
+    intercepted: RefCell<Vec<(Addr, TokenMsg)>>,
+    /// Number of custom queries handled so far. Purely observational,
+    /// used to catch contracts issuing redundant queries.
+    query_count: Cell<u64>,
+    /// If set, `ForceTransfer` calls the denom's before send hook like any other send.
+    /// Chains differ here, so tests can model both behaviors.
+    force_transfer_triggers_hook: bool,
+    /// Subdenom charset and length accepted by `CreateDenom`, the defaults if unset
+    subdenom_rules: Option<SubdenomRulesResponse>,
+    /// How the creation fee is charged, `BurnFee` if unset
+    fee_policy: Option<Box<dyn CreationFeePolicy>>,
+    /// If set, every metadata of a denom is kept for `TokenQuery::MetadataHistory`
+    metadata_history: bool,
+    /// Params written by `init`, none if unset
+    genesis_params: Option<Params>,
+    /// Most a single `MintTokens` may mint, unlimited if unset
+    max_mint_per_tx: Option<Uint128>,
+    /// Features reported by `TokenQuery::Features` and enforced, see `features`
+    features: Option<TokenFactoryFeatures>,
+    /// If set, `TokenFactorySudo::GarbageCollect` may drop fully burned denoms
+    gc_empty_denoms: bool,
+    /// Bech32 prefix and optionally the address length in bytes creators must have
+    creator_format: Option<(String, Option<usize>)>,
+    /// Creation fees accepted besides the params fee, written by `init`
+    genesis_fee_alternatives: Vec<Vec<Coin>>,
+    /// Called after every `ChangeAdmin`, see `on_admin_change`
+    admin_change_callbacks: RefCell<Vec<AdminChangeCallback>>,
+}
+
+/// Called with the denom, the old admin and the new one (None once renounced),
+/// see `TokenFactoryModule::on_admin_change`
+pub type AdminChangeCallback = Box<dyn FnMut(&str, &Addr, Option<&Addr>)>;
 
-pub struct TokenFactoryModule {}
+/// Characters accepted in subdenoms unless configured otherwise, as in `validate_full_denom`
+pub const DEFAULT_SUBDENOM_CHARS: &str = SUBDENOM_CHARS;
 
 /// How many seconds per block
 /// (when we increment block.height, use this multiplier for block.time)
 pub const BLOCK_TIME: u64 = 5;
 
+// creation fees accepted instead of the one of the params, see `with_creation_fee`
+const FEE_ALTERNATIVES: Item<Vec<Vec<Coin>>> = Item::new("fee_alternatives");
+
+// the creation fee option each payer chose to pay, see `choose_creation_fee`
+const FEE_CHOICES: Map<&Addr, Vec<Coin>> = Map::new("fee_choices");
+
+// token factory module params, no creation fee if unset
+const PARAMS: Item<Params> = Item::new("params");
+
 // map denom to metadata
 const METADATA: Map<&str, Metadata> = Map::new("metadata");
 
-// map denom to admin
+// map denom to admin, removed when the admin is renounced. Kept by
+// `TokenFactorySudo::GarbageCollect` as a tombstone, so the denom can never be created again.
 const ADMIN: Map<&str, Addr> = Map::new("admin");
 
+// denoms whose metadata can't be changed anymore, see `TokenMsg::LockMetadata`
+const METADATA_LOCKED: Map<&str, Empty> = Map::new("metadata_locked");
+
+// every metadata per denom with the height it was set at, only if enabled
+const METADATA_HISTORY: Map<&str, Vec<(u64, Metadata)>> = Map::new("metadata_history");
+
+// every admin change per denom, including the creation, by sequence number.
+// The chain keeps no such history, this only exists for assertions in tests.
+const ADMIN_HISTORY: Map<(&str, u64), AdminChange> = Map::new("admin_history");
+
+// every denom ever created. The (creator, subdenom) pair stays unique forever,
+// even once the denom has no admin anymore
+const CREATED: Map<&str, Empty> = Map::new("created");
+
+// addresses besides the admin that may mint a denom
+const MINTERS: Map<(&str, &Addr), Empty> = Map::new("minters");
+
+// map denom to its before send hook contract
+const BEFORE_SEND_HOOKS: Map<&str, Addr> = Map::new("before_send_hooks");
+
+// dedup keys of all mints so far, per denom
+const MINT_KEYS: Map<(&str, &str), Empty> = Map::new("mint_keys");
+
+// map denom to total minted minus burned amount
+const SUPPLY: Map<&str, Uint128> = Map::new("supply");
+
+// denoms which may not be minted anymore
+const MINT_DISABLED: Map<&str, Empty> = Map::new("mint_disabled");
+
+// map denom to the most its supply may reach
+const SUPPLY_CAPS: Map<&str, Uint128> = Map::new("supply_caps");
+
+// map denom to the address which created it, kept when the admin changes
+const CREATOR: Map<&str, Addr> = Map::new("creator");
+
 // map creator to denoms
 const DENOMS_BY_CREATOR: Map<&Addr, Vec<String>> = Map::new("denom");
 
+// number of denoms ever created, in total and per creator, for cheap assertions
+const DENOM_COUNT: Item<u64> = Item::new("denom_count");
+const DENOM_COUNT_BY_CREATOR: Map<&Addr, u64> = Map::new("denom_count_by_creator");
+
 impl TokenFactoryModule {
+    /// Starts configuring a module with the defaults, for use in an `App` composed
+    /// without `TokenFactoryApp`. Chain the `with_*` options, pass the module to
+    /// `with_custom` and call `init` in the closure of `build` to write the genesis state:
+    ///
+    /// ```
+    /// use cosmwasm_std::coins;
+    /// use cw_multi_test::BasicAppBuilder;
+    /// use token_bindings::{TokenFactoryMsg, TokenFactoryQuery};
+    /// use token_bindings_test::TokenFactoryModule;
+    ///
+    /// let module = TokenFactoryModule::builder().with_creation_fee(coins(100, "uosmo"));
+    /// let app = BasicAppBuilder::<TokenFactoryMsg, TokenFactoryQuery>::new_custom()
+    ///     .with_custom(module)
+    ///     .build(|router, _, storage| router.custom.init(storage).unwrap());
+    /// ```
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    /// Sets the module params written by `init`
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.genesis_params = Some(params);
+        self
+    }
+
+    /// Charges the creation fee for every denom created, once `init` was called. Pass the coins
+    /// charged, or a `Vec` of alternatives of which the creator pays one, see `CreationFee`.
+    pub fn with_creation_fee(mut self, fee: impl Into<CreationFee>) -> Self {
+        let (first, alternatives) = fee.into().split();
+        self.genesis_fee_alternatives = alternatives;
+        let params = self.genesis_params.clone().unwrap_or_default();
+        self.with_params(Params {
+            denom_creation_fee: first,
+            ..params
+        })
+    }
+
+    /// Disables the token factory once `init` was called, so no denom can be created
+    pub fn disabled(self) -> Self {
+        let params = self.genesis_params.clone().unwrap_or_default();
+        self.with_params(Params {
+            enabled: false,
+            ..params
+        })
+    }
+
+    /// Writes the configured genesis state (the params, if set) to `storage`
+    pub fn init(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        if let Some(params) = &self.genesis_params {
+            self.set_params(storage, params)?;
+        }
+        if !self.genesis_fee_alternatives.is_empty() {
+            FEE_ALTERNATIVES.save(storage, &self.genesis_fee_alternatives)?;
+        }
+        Ok(())
+    }
+
+    /// Record all executed messages and acknowledge them with an empty response,
+    /// without touching any state. Use `intercepted_msgs` to inspect them.
+    pub fn with_interception(mut self) -> Self {
+        self.intercept = true;
+        self
+    }
+
+    /// Keeps every metadata set on a denom, to answer `TokenQuery::MetadataHistory`.
+    /// Off by default, as it grows with every `SetMetadata`.
+    pub fn with_metadata_history(mut self) -> Self {
+        self.metadata_history = true;
+        self
+    }
+
+    /// Rejects any single mint of more than `limit`, to model chains or wrappers
+    /// capping mints per transaction. Also reflected by `TokenQuery::CanMint`.
+    pub fn with_max_mint_per_tx(mut self, limit: Uint128) -> Self {
+        self.max_mint_per_tx = Some(limit);
+        self
+    }
+
+    /// Sets whether `ForceTransfer` calls the before send hook of the denom (if any),
+    /// or bypasses it. Bypassed by default.
+    pub fn with_force_transfer_triggers_hook(mut self, triggers_hook: bool) -> Self {
+        self.force_transfer_triggers_hook = triggers_hook;
+        self
+    }
+
+    /// Sets the characters and maximum length `CreateDenom` accepts in subdenoms, as
+    /// returned by `TokenQuery::SubdenomRules`. Defaults to `DEFAULT_SUBDENOM_CHARS`
+    /// and `MAX_SUBDENOM_LENGTH`.
+    pub fn with_subdenom_rules(mut self, allowed_chars: &str, max_len: u32) -> Self {
+        self.subdenom_rules = Some(SubdenomRulesResponse {
+            allowed_chars: allowed_chars.to_string(),
+            max_len,
+        });
+        self
+    }
+
+    /// The subdenom rules in effect
+    pub fn subdenom_rules(&self) -> SubdenomRulesResponse {
+        self.subdenom_rules
+            .clone()
+            .unwrap_or_else(|| SubdenomRulesResponse {
+                allowed_chars: DEFAULT_SUBDENOM_CHARS.to_string(),
+                max_len: MAX_SUBDENOM_LENGTH as u32,
+            })
+    }
+
+    /// Models a chain with only these optional features: they are reported by
+    /// `TokenQuery::Features`, and messages needing another one fail with
+    /// `ContractError::FeatureUnsupported`.
+    pub fn with_features(mut self, features: TokenFactoryFeatures) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    /// The features in effect, by default all but burning from other addresses
+    pub fn features(&self) -> TokenFactoryFeatures {
+        self.features.clone().unwrap_or(TokenFactoryFeatures {
+            version: FEATURES_VERSION,
+            set_metadata: true,
+            force_transfer: true,
+            before_send_hook: true,
+            burn_from: false,
+            lock_metadata: true,
+        })
+    }
+
+    /// Allows `TokenFactorySudo::GarbageCollect`, for tests creating and fully burning
+    /// many short-lived denoms, where the ever growing registry slows down state dumps.
+    /// Real chains never forget a denom, so only enable this where that doesn't matter.
+    pub fn with_gc_empty_denoms(mut self) -> Self {
+        self.gc_empty_denoms = true;
+        self
+    }
+
+    /// Only accepts creators which are bech32 addresses with the human readable part
+    /// `prefix` (eg. `osmo`) and, if set, `address_bytes` long. Without a length, account
+    /// (20 bytes) and contract (32 bytes) addresses are accepted.
+    ///
+    /// By default any creator the `Api` accepts is fine, which includes the plain
+    /// strings of `MockApi`. This catches addresses of the wrong chain in tests.
+    pub fn with_creator_format(mut self, prefix: &str, address_bytes: Option<usize>) -> Self {
+        self.creator_format = Some((prefix.to_string(), address_bytes));
+        self
+    }
+
+    /// Sets how the creation fee is charged. By default it is burned.
+    pub fn with_fee_policy(mut self, policy: Box<dyn CreationFeePolicy>) -> Self {
+        self.fee_policy = Some(policy);
+        self
+    }
+
+    /// Registers a callback invoked after each `ChangeAdmin` executed by the module,
+    /// eg. to mirror admin changes in test harness state. Callbacks are called in
+    /// registration order.
+    ///
+    /// They are called as the message executes, so they also see admin changes of
+    /// transactions which fail later on and are reverted.
+    pub fn on_admin_change(&self, callback: impl FnMut(&str, &Addr, Option<&Addr>) + 'static) {
+        self.admin_change_callbacks
+            .borrow_mut()
+            .push(Box::new(callback));
+    }
+
+    fn admin_changed(&self, denom: &str, old: &Addr, new: Option<&Addr>) {
+        for callback in self.admin_change_callbacks.borrow_mut().iter_mut() {
+            callback(denom, old, new);
+        }
+    }
+
+    /// Returns all messages recorded in interception mode, in execution order
+    pub fn intercepted_msgs(&self) -> Vec<(Addr, TokenMsg)> {
+        self.intercepted.borrow().clone()
+    }
+
+    /// Returns how many token factory queries this module has answered
+    pub fn query_count(&self) -> u64 {
+        self.query_count.get()
+    }
+
+    /// Ensures `minter` may mint `amount` of `denom`, exactly as `MintTokens` checks it
+    fn check_can_mint(
+        &self,
+        storage: &dyn Storage,
+        denom: &str,
+        minter: &Addr,
+        amount: Uint128,
+    ) -> Result<(), ContractError> {
+        if let Some(limit) = self.max_mint_per_tx {
+            if amount > limit {
+                return Err(ContractError::MintLimitExceeded {
+                    limit,
+                    requested: amount,
+                });
+            }
+        }
+        // ensure minter is admin of this denom (and it exists), or allowed to mint it
+        match self.ensure_admin(storage, denom, minter) {
+            Err(ContractError::NotTokenAdmin) if MINTERS.has(storage, (denom, minter)) => {}
+            res => res?,
+        }
+        if MINT_DISABLED.has(storage, denom) {
+            return Err(ContractError::MintingDisabled {
+                denom: denom.to_string(),
+            });
+        }
+        if let Some(cap) = SUPPLY_CAPS.may_load(storage, denom)? {
+            let supply = SUPPLY.may_load(storage, denom)?.unwrap_or_default();
+            if supply.checked_add(amount)? > cap {
+                return Err(ContractError::SupplyCapExceeded {
+                    cap,
+                    supply,
+                    requested: amount,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures the denom exists and `sender` is its admin
+    fn ensure_admin(
+        &self,
+        storage: &dyn Storage,
+        denom: &str,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        if !CREATED.has(storage, denom) {
+            return Err(ContractError::TokenDoesntExist);
+        }
+        match ADMIN.may_load(storage, denom)? {
+            Some(admin) if &admin == sender => Ok(()),
+            _ => Err(ContractError::NotTokenAdmin),
+        }
+    }
+
+    /// Sets the module params, usually at genesis. Their fee is the first creation fee
+    /// option, alternatives set with `set_creation_fee` stay accepted.
+    pub fn set_params(&self, storage: &mut dyn Storage, params: &Params) -> StdResult<()> {
+        PARAMS.save(storage, params)
+    }
+
+    /// Sets the creation fee, as `with_creation_fee` does at genesis. The first option
+    /// goes to the params, `TokenQuery::CreationFeeOptions` reports all of them.
+    pub fn set_creation_fee(
+        &self,
+        storage: &mut dyn Storage,
+        fee: impl Into<CreationFee>,
+    ) -> StdResult<()> {
+        let (first, alternatives) = fee.into().split();
+        let params = Params {
+            denom_creation_fee: first,
+            ..self.params(storage)?
+        };
+        self.set_params(storage, &params)?;
+        FEE_ALTERNATIVES.save(storage, &alternatives)
+    }
+
+    /// The creation fee options `CreateDenom` accepts: the fee of the params,
+    /// then the alternatives
+    pub fn creation_fee(&self, storage: &dyn Storage) -> StdResult<CreationFee> {
+        let mut options = vec![self.params(storage)?.denom_creation_fee];
+        options.extend(FEE_ALTERNATIVES.may_load(storage)?.unwrap_or_default());
+        Ok(CreationFee { options })
+    }
+
+    /// Makes `payer` pay `fee` for the denoms it creates, rather than the first option
+    /// it can afford. `CreateDenom` fails if `fee` is not one of the accepted options.
+    /// This stands in for choosing the fee with the funds sent along, which token
+    /// factory messages can't do.
+    pub fn choose_creation_fee(
+        &self,
+        storage: &mut dyn Storage,
+        payer: &Addr,
+        fee: Vec<Coin>,
+    ) -> StdResult<()> {
+        FEE_CHOICES.save(storage, payer, &fee)
+    }
+
+    /// Returns the module params, enabled with no creation fee if they were never set
+    pub fn params(&self, storage: &dyn Storage) -> StdResult<Params> {
+        Ok(PARAMS.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Lets `minter` mint `denom` in addition to its admin. This is not part of the
+    /// token factory, but allows testing designs with multiple authorized minters.
+    pub fn allow_minter(
+        &self,
+        storage: &mut dyn Storage,
+        denom: &str,
+        minter: &Addr,
+    ) -> StdResult<()> {
+        MINTERS.save(storage, (denom, minter), &Empty {})
+    }
+
+    /// Stops or resumes minting of `denom`, for testing designs where minting can be
+    /// switched off. Like `allow_minter`, this is not part of the token factory.
+    pub fn set_mint_enabled(
+        &self,
+        storage: &mut dyn Storage,
+        denom: &str,
+        enabled: bool,
+    ) -> StdResult<()> {
+        if enabled {
+            MINT_DISABLED.remove(storage, denom);
+            Ok(())
+        } else {
+            MINT_DISABLED.save(storage, denom, &Empty {})
+        }
+    }
+
+    /// Caps the supply of `denom`, mints beyond it fail. None removes the cap.
+    /// Like `allow_minter`, this is not part of the token factory.
+    pub fn set_supply_cap(
+        &self,
+        storage: &mut dyn Storage,
+        denom: &str,
+        cap: Option<Uint128>,
+    ) -> StdResult<()> {
+        match cap {
+            Some(cap) => SUPPLY_CAPS.save(storage, denom, &cap),
+            None => {
+                SUPPLY_CAPS.remove(storage, denom);
+                Ok(())
+            }
+        }
+    }
+
+    /// Sets the bank metadata of the staking token (or any other native token),
+    /// which the Metadata query returns for its base denom
+    pub fn set_staking_token_metadata(
+        &self,
+        storage: &mut dyn Storage,
+        metadata: &Metadata,
+    ) -> StdResult<()> {
+        let base = metadata
+            .base
+            .as_deref()
+            .ok_or_else(|| StdError::generic_err("staking token metadata must have a base"))?;
+        METADATA.save(storage, base, metadata)
+    }
+
+    /// Returns how many denoms were ever created
+    pub fn denom_count(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(DENOM_COUNT.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Returns how many denoms `creator` ever created
+    pub fn denom_count_for(&self, storage: &dyn Storage, creator: &Addr) -> StdResult<u64> {
+        Ok(DENOM_COUNT_BY_CREATOR
+            .may_load(storage, creator)?
+            .unwrap_or_default())
+    }
+
+    /// Returns every admin change of `denom`, oldest first, starting with its creation.
+    /// Test infrastructure only, the chain has no such query.
+    pub fn admin_history(&self, storage: &dyn Storage, denom: &str) -> StdResult<Vec<AdminChange>> {
+        ADMIN_HISTORY
+            .prefix(denom)
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, change)| change))
+            .collect()
+    }
+
+    /// Returns every denom ever created, sorted, with its admin, metadata and supply
+    pub fn dump_state(&self, storage: &dyn Storage) -> StdResult<TokenFactoryState> {
+        let denoms = CREATED
+            .keys(storage, None, None, Order::Ascending)
+            .map(|denom| {
+                let denom = denom?;
+                Ok(DenomState {
+                    admin: ADMIN.may_load(storage, &denom)?.map(String::from),
+                    metadata: METADATA.may_load(storage, &denom)?,
+                    locked: METADATA_LOCKED.has(storage, &denom),
+                    supply: SUPPLY.may_load(storage, &denom)?.unwrap_or_default(),
+                    denom,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(TokenFactoryState { denoms })
+    }
+
+    /// Removes everything stored about `denom`, except its admin, the tombstone keeping it unique
+    fn collect(&self, storage: &mut dyn Storage, denom: &str) -> StdResult<()> {
+        let creator = validate_full_denom(denom)
+            .map(|parts| Addr::unchecked(parts.creator))
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+        let mut denoms = DENOMS_BY_CREATOR
+            .may_load(storage, &creator)?
+            .unwrap_or_default();
+        denoms.retain(|d| d != denom);
+        DENOMS_BY_CREATOR.save(storage, &creator, &denoms)?;
+
+        CREATED.remove(storage, denom);
+        CREATOR.remove(storage, denom);
+        METADATA.remove(storage, denom);
+        METADATA_LOCKED.remove(storage, denom);
+        METADATA_HISTORY.remove(storage, denom);
+        SUPPLY.remove(storage, denom);
+        MINT_DISABLED.remove(storage, denom);
+        SUPPLY_CAPS.remove(storage, denom);
+        BEFORE_SEND_HOOKS.remove(storage, denom);
+
+        let minters = MINTERS
+            .prefix(denom)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for minter in minters {
+            MINTERS.remove(storage, (denom, &minter));
+        }
+        let keys = MINT_KEYS
+            .prefix(denom)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for key in keys {
+            MINT_KEYS.remove(storage, (denom, &key));
+        }
+        let changes = ADMIN_HISTORY
+            .prefix(denom)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for change in changes {
+            ADMIN_HISTORY.remove(storage, (denom, change));
+        }
+        Ok(())
+    }
+
+    /// The option `sender` chose with `choose_creation_fee`, else the first option it can pay,
+    /// else the first one, which then fails for lack of funds
+    fn select_creation_fee<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: &Addr,
+    ) -> AnyResult<Vec<Coin>>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let options = self.creation_fee(storage)?.options;
+        if let Some(choice) = FEE_CHOICES.may_load(storage, sender)? {
+            return match options.contains(&choice) {
+                true => Ok(choice),
+                false => {
+                    let fee: Vec<String> = choice.iter().map(Coin::to_string).collect();
+                    Err(ContractError::CreationFeeNotAccepted { fee: fee.join(",") }.into())
+                }
+            };
+        }
+        if options.len() == 1 {
+            return Ok(options[0].clone());
+        }
+        let query = BankQuery::AllBalances {
+            address: sender.to_string(),
+        };
+        let balances: AllBalanceResponse =
+            from_binary(&router.query(api, storage, block, query.into())?)?;
+        let option = options
+            .iter()
+            .find(|option| has_sufficient_funds(&balances.amount, option))
+            .unwrap_or(&options[0]);
+        Ok(option.clone())
+    }
+
+    fn save_metadata(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        denom: &str,
+        metadata: &Metadata,
+    ) -> StdResult<()> {
+        METADATA.save(storage, denom, metadata)?;
+        if self.metadata_history {
+            let mut history = METADATA_HISTORY
+                .may_load(storage, denom)?
+                .unwrap_or_default();
+            history.push((block.height, metadata.clone()));
+            METADATA_HISTORY.save(storage, denom, &history)?;
+        }
+        Ok(())
+    }
+
     fn build_denom(&self, creator: &Addr, subdenom: &str) -> Result<String, ContractError> {
         // Minimum validation checks on the full denom.
         // https://github.com/cosmos/cosmos-sdk/blob/2646b474c7beb0c93d4fafd395ef345f41afc251/types/coin.go#L706-L711
@@ -47,19 +636,43 @@ impl TokenFactoryModule {
         if full_denom.len() < 3
             || full_denom.len() > 128
             || creator.as_str().contains('/')
-            || subdenom.len() > 44
+            || !self.subdenom_rules().allows(subdenom)
             || creator.as_str().len() > 75
         {
             return Err(ContractError::InvalidFullDenom { full_denom });
         }
+        self.check_creator_format(creator)?;
         Ok(full_denom)
     }
+
+    fn check_creator_format(&self, creator: &Addr) -> Result<(), ContractError> {
+        let (prefix, address_bytes) = match &self.creator_format {
+            Some(format) => format,
+            None => return Ok(()),
+        };
+        let invalid = |reason: String| ContractError::InvalidCreatorAddress {
+            creator: creator.to_string(),
+            reason,
+        };
+        // checks the creator only, the subdenom was checked against the configured rules
+        let denom = format!("{}/{}/check", DENOM_PREFIX, creator);
+        validate_creator_prefix(&denom, prefix).map_err(|err| invalid(err.to_string()))?;
+        if let Some(bytes) = address_bytes {
+            // the data part has 5 bits per character, followed by a 6 character checksum,
+            // with less than a byte of padding
+            let data_chars = creator.as_str().len() - prefix.len() - 1 - 6;
+            if data_chars * 5 / 8 != *bytes {
+                return Err(invalid(format!("creator must be a {} byte address", bytes)));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Module for TokenFactoryModule {
     type ExecT = TokenFactoryMsg;
     type QueryT = TokenFactoryQuery;
-    type SudoT = Empty;
+    type SudoT = TokenFactorySudo;
 
     // Builds a mock rust implementation of the expected osmosis functionality for testing
     fn execute<ExecC, QueryC>(
@@ -76,86 +689,273 @@ impl Module for TokenFactoryModule {
         QueryC: CustomQuery + DeserializeOwned + 'static,
     {
         let TokenFactoryMsg::Token(msg) = msg;
+        if self.intercept {
+            self.intercepted.borrow_mut().push((sender, msg));
+            return Ok(AppResponse::default());
+        }
+        // burning from the sender's own address is always fine, the burn checks the rest
+        let features = self.features();
+        if !matches!(msg, TokenMsg::BurnTokens { .. }) && !features.supports(&msg) {
+            return Err(ContractError::FeatureUnsupported {
+                features: features.to_string(),
+            }
+            .into());
+        }
         match msg {
             TokenMsg::CreateDenom { subdenom, metadata } => {
+                if !self.params(storage)?.enabled {
+                    return Err(ContractError::TokenFactoryDisabled.into());
+                }
                 let new_token_denom = self.build_denom(&sender, &subdenom)?;
 
                 // errors if the denom was already created
-                if ADMIN.may_load(storage, &new_token_denom)?.is_some() {
+                if CREATED.has(storage, &new_token_denom) || ADMIN.has(storage, &new_token_denom) {
                     return Err(ContractError::TokenExists.into());
                 }
-                ADMIN.save(storage, &new_token_denom, &sender)?;
+                if let Some(md) = &metadata {
+                    validate_metadata(md).map_err(ContractError::from)?;
+                }
 
-                // TODO: charge the creation fee (once params is supported)
+                // charge the creation fee. The chain sends it to the community pool,
+                // which we don't have, so it is burned unless another policy is set.
+                // This must come before any write, so a failure leaves no trace
+                // even when not executed in a transaction.
+                let fee = self.select_creation_fee(api, storage, router, block, &sender)?;
+                let charge = match &self.fee_policy {
+                    Some(policy) => policy.charge(storage, block, &sender, &fee)?,
+                    None => BurnFee.charge(storage, block, &sender, &fee)?,
+                };
+                for msg in charge.msgs {
+                    router.execute(api, storage, block, sender.clone(), msg.into())?;
+                }
 
+                CREATED.save(storage, &new_token_denom, &Empty {})?;
+                CREATOR.save(storage, &new_token_denom, &sender)?;
+                ADMIN.save(storage, &new_token_denom, &sender)?;
+                record_admin_change(storage, block, &new_token_denom, &sender, Some(&sender))?;
                 let mut denoms = DENOMS_BY_CREATOR
                     .may_load(storage, &sender)?
                     .unwrap_or_default();
                 denoms.push(new_token_denom.clone());
                 DENOMS_BY_CREATOR.save(storage, &sender, &denoms)?;
+                let count = self.denom_count(storage)? + 1;
+                DENOM_COUNT.save(storage, &count)?;
+                let count = self.denom_count_for(storage, &sender)? + 1;
+                DENOM_COUNT_BY_CREATOR.save(storage, &sender, &count)?;
 
                 // set metadata if provided
                 if let Some(md) = metadata {
-                    METADATA.save(storage, &new_token_denom, &md)?;
+                    self.save_metadata(storage, block, &new_token_denom, &md)?;
                 }
 
+                // emit the same events as the chain does
+                let event = Event::new(EVENT_CREATE_DENOM)
+                    .add_attribute(ATTR_CREATOR, &sender)
+                    .add_attribute(ATTR_NEW_TOKEN_DENOM, &new_token_denom)
+                    .add_attributes(charge.attributes)
+                    .add_attribute(ATTR_SENDER, &sender)
+                    .add_attribute(ATTR_DENOM, &new_token_denom);
                 let data = Some(CreateDenomResponse { new_token_denom }.encode()?);
                 Ok(AppResponse {
                     data,
-                    events: vec![],
+                    events: vec![event],
                 })
             }
             TokenMsg::MintTokens {
                 denom,
                 amount,
                 mint_to_address,
+                dedup_key,
             } => {
-                // ensure we are admin of this denom (and it exists)
-                let admin = ADMIN
-                    .may_load(storage, &denom)?
-                    .ok_or(ContractError::TokenDoesntExist)?;
-                if admin != sender {
-                    return Err(ContractError::NotTokenAdmin.into());
+                self.check_can_mint(storage, &denom, &sender, amount)?;
+                if let Some(key) = &dedup_key {
+                    if MINT_KEYS.has(storage, (&denom, key)) {
+                        let key = key.clone();
+                        return Err(ContractError::DuplicateMint { key }.into());
+                    }
                 }
+                // no balance can exceed the supply, so this also protects the bank from overflowing
+                let supply = SUPPLY.may_load(storage, &denom)?.unwrap_or_default();
+                let supply = supply
+                    .checked_add(amount)
+                    .map_err(ContractError::Overflow)?;
+                let event = Event::new(EVENT_MINT)
+                    .add_attribute(ATTR_MINT_TO_ADDRESS, &mint_to_address)
+                    .add_attribute(ATTR_AMOUNT, format!("{}{}", amount, denom))
+                    .add_attribute(ATTR_SENDER, &sender)
+                    .add_attribute(ATTR_DENOM, &denom);
                 let mint = BankSudo::Mint {
                     to_address: mint_to_address,
                     amount: coins(amount.u128(), &denom),
                 };
                 router.sudo(api, storage, block, mint.into())?;
-                Ok(AppResponse::default())
+                // written once the bank minted, so a failed mint leaves no trace
+                // even when not executed in a transaction. Only a successful mint uses up its key.
+                SUPPLY.save(storage, &denom, &supply)?;
+                if let Some(key) = dedup_key {
+                    MINT_KEYS.save(storage, (&denom, &key), &Empty {})?;
+                }
+                Ok(AppResponse {
+                    data: None,
+                    events: vec![event],
+                })
             }
             TokenMsg::BurnTokens {
-                denom: _,
-                amount: _,
-                burn_from_address: _,
-            } => todo!(),
+                denom,
+                amount,
+                burn_from_address,
+            } => {
+                let burn_from = match burn_from_address.as_str() {
+                    "" => sender.clone(),
+                    address => api.addr_validate(address)?,
+                };
+                if burn_from != sender && !features.burn_from {
+                    return Err(ContractError::BurnFromNotSupported.into());
+                }
+                if amount.is_zero() {
+                    return Err(ContractError::ZeroAmount.into());
+                }
+                // ensure we are admin of this denom (and it exists)
+                self.ensure_admin(storage, &denom, &sender)?;
+                // the bank errors if the holder doesn't hold enough tokens
+                let burn = BankMsg::Burn {
+                    amount: coins(amount.u128(), &denom),
+                };
+                router.execute(api, storage, block, burn_from.clone(), burn.into())?;
+                let supply = SUPPLY.may_load(storage, &denom)?.unwrap_or_default();
+                let supply = supply
+                    .checked_sub(amount)
+                    .map_err(ContractError::Overflow)?;
+                SUPPLY.save(storage, &denom, &supply)?;
+                let event = Event::new(EVENT_BURN)
+                    .add_attribute(ATTR_BURN_FROM_ADDRESS, &burn_from)
+                    .add_attribute(ATTR_AMOUNT, format!("{}{}", amount, denom))
+                    .add_attribute(ATTR_SENDER, &sender)
+                    .add_attribute(ATTR_DENOM, &denom);
+                Ok(AppResponse {
+                    data: None,
+                    events: vec![event],
+                })
+            }
             TokenMsg::ChangeAdmin {
                 denom,
                 new_admin_address,
             } => {
                 // ensure we are admin of this denom (and it exists)
-                let admin = ADMIN
-                    .may_load(storage, &denom)?
-                    .ok_or(ContractError::TokenDoesntExist)?;
-                if admin != sender {
-                    return Err(ContractError::NotTokenAdmin.into());
+                self.ensure_admin(storage, &denom, &sender)?;
+                // an empty new admin renounces, otherwise it must be valid
+                if new_admin_address.is_empty() {
+                    ADMIN.remove(storage, &denom);
+                    record_admin_change(storage, block, &denom, &sender, None)?;
+                    self.admin_changed(&denom, &sender, None);
+                } else {
+                    let new_admin = api.addr_validate(&new_admin_address)?;
+                    ADMIN.save(storage, &denom, &new_admin)?;
+                    record_admin_change(storage, block, &denom, &sender, Some(&new_admin))?;
+                    self.admin_changed(&denom, &sender, Some(&new_admin));
                 }
-                // and new admin is valid
-                let new_admin = api.addr_validate(&new_admin_address)?;
-                ADMIN.save(storage, &denom, &new_admin)?;
-                Ok(AppResponse::default())
+                let event = Event::new(EVENT_CHANGE_ADMIN)
+                    .add_attribute(ATTR_DENOM, &denom)
+                    .add_attribute(ATTR_NEW_ADMIN, new_admin_address)
+                    .add_attribute(ATTR_SENDER, &sender);
+                Ok(AppResponse {
+                    data: None,
+                    events: vec![event],
+                })
             }
             TokenMsg::SetMetadata { denom, metadata } => {
                 // ensure we are admin of this denom (and it exists)
-                let admin = ADMIN
-                    .may_load(storage, &denom)?
-                    .ok_or(ContractError::TokenDoesntExist)?;
-                if admin != sender {
-                    return Err(ContractError::NotTokenAdmin.into());
+                self.ensure_admin(storage, &denom, &sender)?;
+                if METADATA_LOCKED.has(storage, &denom) {
+                    return Err(ContractError::MetadataLocked { denom }.into());
                 }
-                // FIXME: add validation of metadata
-                METADATA.save(storage, &denom, &metadata)?;
-                Ok(AppResponse::default())
+                validate_metadata(&metadata).map_err(ContractError::from)?;
+                // replaced in full like on chain, no field of the previous metadata is kept
+                self.save_metadata(storage, block, &denom, &metadata)?;
+                let event = Event::new(EVENT_SET_DENOM_METADATA)
+                    .add_attribute(ATTR_DENOM, &denom)
+                    .add_attribute(ATTR_SENDER, &sender);
+                Ok(AppResponse {
+                    data: None,
+                    events: vec![event],
+                })
+            }
+            TokenMsg::LockMetadata { denom } => {
+                // ensure we are admin of this denom (and it exists)
+                self.ensure_admin(storage, &denom, &sender)?;
+                METADATA_LOCKED.save(storage, &denom, &Empty {})?;
+                let event = Event::new(EVENT_LOCK_DENOM_METADATA)
+                    .add_attribute(ATTR_DENOM, &denom)
+                    .add_attribute(ATTR_SENDER, &sender);
+                Ok(AppResponse {
+                    data: None,
+                    events: vec![event],
+                })
+            }
+            TokenMsg::ForceTransfer {
+                denom,
+                amount,
+                from_address,
+                to_address,
+            } => {
+                // ensure we are admin of this denom (and it exists)
+                self.ensure_admin(storage, &denom, &sender)?;
+                if amount.is_zero() {
+                    return Err(ContractError::ZeroAmount.into());
+                }
+                // both must be valid addresses, as on chain
+                let from_address = api.addr_validate(&from_address)?;
+                let to_address = api.addr_validate(&to_address)?;
+                let amount = coins(amount.u128(), &denom);
+                if self.force_transfer_triggers_hook {
+                    if let Some(hook) = BEFORE_SEND_HOOKS.may_load(storage, &denom)? {
+                        // the hook rejects the transfer by returning an error
+                        let msg = BeforeSendHookMsg::BlockBeforeSend {
+                            from: from_address.to_string(),
+                            to: to_address.to_string(),
+                            amount: amount[0].clone(),
+                        };
+                        router.sudo(api, storage, block, WasmSudo::new(&hook, &msg)?.into())?;
+                    }
+                }
+                let event = Event::new(EVENT_FORCE_TRANSFER)
+                    .add_attribute(ATTR_TRANSFER_FROM_ADDRESS, &from_address)
+                    .add_attribute(ATTR_TRANSFER_TO_ADDRESS, &to_address)
+                    .add_attribute(ATTR_AMOUNT, format!("{}{}", amount[0].amount, denom))
+                    .add_attribute(ATTR_SENDER, &sender)
+                    .add_attribute(ATTR_DENOM, &denom);
+                // the bank errors if the owner doesn't hold enough tokens
+                let send = BankMsg::Send {
+                    to_address: to_address.into(),
+                    amount,
+                };
+                router.execute(api, storage, block, from_address, send.into())?;
+                Ok(AppResponse {
+                    data: None,
+                    events: vec![event],
+                })
+            }
+            TokenMsg::SetBeforeSendHook {
+                denom,
+                cosmwasm_address,
+            } => {
+                // ensure we are admin of this denom (and it exists)
+                self.ensure_admin(storage, &denom, &sender)?;
+                // an empty address removes the hook, otherwise it must be valid
+                if cosmwasm_address.is_empty() {
+                    BEFORE_SEND_HOOKS.remove(storage, &denom);
+                } else {
+                    let hook = api.addr_validate(&cosmwasm_address)?;
+                    BEFORE_SEND_HOOKS.save(storage, &denom, &hook)?;
+                }
+                let event = Event::new(EVENT_SET_BEFORE_SEND_HOOK)
+                    .add_attribute(ATTR_DENOM, &denom)
+                    .add_attribute(ATTR_BEFORE_SEND_HOOK_ADDRESS, cosmwasm_address)
+                    .add_attribute(ATTR_SENDER, &sender);
+                Ok(AppResponse {
+                    data: None,
+                    events: vec![event],
+                })
             }
         }
     }
@@ -163,16 +963,39 @@ impl Module for TokenFactoryModule {
     fn sudo<ExecC, QueryC>(
         &self,
         _api: &dyn Api,
-        _storage: &mut dyn Storage,
+        storage: &mut dyn Storage,
         _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
         _block: &BlockInfo,
-        _msg: Self::SudoT,
+        msg: TokenFactorySudo,
     ) -> AnyResult<AppResponse>
     where
         ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
         QueryC: CustomQuery + DeserializeOwned + 'static,
     {
-        bail!("sudo not implemented for OsmosisModule")
+        match msg {
+            TokenFactorySudo::GarbageCollect { denom, admin } => {
+                if !self.gc_empty_denoms {
+                    return Err(ContractError::GarbageCollectionDisabled.into());
+                }
+                self.ensure_admin(storage, &denom, &admin)?;
+                let supply = SUPPLY.may_load(storage, &denom)?.unwrap_or_default();
+                if !supply.is_zero() {
+                    return Err(ContractError::DenomNotEmpty { supply }.into());
+                }
+                self.collect(storage, &denom)?;
+                Ok(AppResponse::default())
+            }
+            TokenFactorySudo::ClearMetadata { denom } => {
+                if !CREATED.has(storage, &denom) {
+                    return Err(ContractError::TokenDoesntExist.into());
+                }
+                if METADATA_LOCKED.has(storage, &denom) {
+                    return Err(ContractError::MetadataLocked { denom }.into());
+                }
+                METADATA.remove(storage, &denom);
+                Ok(AppResponse::default())
+            }
+        }
     }
 
     fn query(
@@ -183,6 +1006,7 @@ impl Module for TokenFactoryModule {
         _block: &BlockInfo,
         request: TokenFactoryQuery,
     ) -> anyhow::Result<Binary> {
+        self.query_count.set(self.query_count.get() + 1);
         let TokenFactoryQuery::Token(query) = request;
         match query {
             TokenQuery::FullDenom {
@@ -198,8 +1022,27 @@ impl Module for TokenFactoryModule {
                 let metadata = METADATA.may_load(storage, &denom)?;
                 Ok(to_binary(&MetadataResponse { metadata })?)
             }
+            TokenQuery::HasMetadata { denom } => {
+                let has_metadata = METADATA.has(storage, &denom);
+                Ok(to_binary(&HasMetadataResponse { has_metadata })?)
+            }
+            TokenQuery::MetadataLocked { denom } => {
+                let locked = METADATA_LOCKED.has(storage, &denom);
+                Ok(to_binary(&MetadataLockedResponse { locked })?)
+            }
             TokenQuery::Admin { denom } => {
-                let admin = ADMIN.load(storage, &denom)?.to_string();
+                if !is_factory_denom(&denom) {
+                    return Err(ContractError::NotFactoryDenom { denom }.into());
+                }
+                // the admin of a garbage collected denom is still known
+                if !CREATED.has(storage, &denom) && !ADMIN.has(storage, &denom) {
+                    return Err(ContractError::TokenDoesntExist.into());
+                }
+                // empty once the admin was renounced
+                let admin = ADMIN
+                    .may_load(storage, &denom)?
+                    .map(String::from)
+                    .unwrap_or_default();
                 Ok(to_binary(&AdminResponse { admin })?)
             }
             TokenQuery::DenomsByCreator { creator } => {
@@ -209,21 +1052,192 @@ impl Module for TokenFactoryModule {
                     .unwrap_or_default();
                 Ok(to_binary(&DenomsByCreatorResponse { denoms })?)
             }
-            TokenQuery::Params {} => todo!(),
+            TokenQuery::Params {} => {
+                let params = self.params(storage)?;
+                Ok(to_binary(&ParamsResponse { params })?)
+            }
+            TokenQuery::CreationFeeIn { denom } => {
+                let amount = self
+                    .creation_fee(storage)?
+                    .options
+                    .into_iter()
+                    .flatten()
+                    .find(|coin| coin.denom == denom)
+                    .map(|coin| coin.amount);
+                Ok(to_binary(&CreationFeeInResponse { amount })?)
+            }
+            TokenQuery::CreationFeeOptions {} => {
+                let options = self.creation_fee(storage)?.options;
+                Ok(to_binary(&CreationFeeOptionsResponse { options })?)
+            }
+            TokenQuery::CanMint {
+                denom,
+                minter,
+                amount,
+            } => {
+                let minter = api.addr_validate(&minter)?;
+                let res = match self.check_can_mint(storage, &denom, &minter, amount) {
+                    Ok(()) => CanMintResponse {
+                        allowed: true,
+                        reason: None,
+                    },
+                    Err(ContractError::Std(err)) => return Err(err.into()),
+                    Err(err) => CanMintResponse {
+                        allowed: false,
+                        reason: Some(err.to_string()),
+                    },
+                };
+                Ok(to_binary(&res)?)
+            }
+            TokenQuery::CreatorTotalSupply { creator } => {
+                let creator = api.addr_validate(&creator)?;
+                let supply = DENOMS_BY_CREATOR
+                    .may_load(storage, &creator)?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|denom| {
+                        let amount = SUPPLY.may_load(storage, &denom)?.unwrap_or_default();
+                        Ok(Coin { denom, amount })
+                    })
+                    .collect::<StdResult<_>>()?;
+                Ok(to_binary(&CreatorTotalSupplyResponse { supply })?)
+            }
+            TokenQuery::ParseDenom { denom } => {
+                let parts = validate_full_denom(&denom)?;
+                let res = ParseDenomResponse {
+                    prefix: DENOM_PREFIX.to_string(),
+                    creator: parts.creator,
+                    subdenom: parts.subdenom,
+                };
+                Ok(to_binary(&res)?)
+            }
+            TokenQuery::DenomCreator { denom } => {
+                let creator = CREATOR.may_load(storage, &denom)?.map(String::from);
+                Ok(to_binary(&DenomCreatorResponse { creator })?)
+            }
+            TokenQuery::SubdenomRules {} => Ok(to_binary(&self.subdenom_rules())?),
+            TokenQuery::Features {} => Ok(to_binary(&self.features())?),
+            TokenQuery::MetadataHistory { denom } => {
+                if !self.metadata_history {
+                    return Err(ContractError::MetadataHistoryDisabled.into());
+                }
+                let history = METADATA_HISTORY
+                    .may_load(storage, &denom)?
+                    .unwrap_or_default();
+                Ok(to_binary(&MetadataHistoryResponse { history })?)
+            }
         }
     }
 }
 
-#[derive(Error, Debug, PartialEq)]
-pub enum TokenFactoryError {
-    #[error("{0}")]
+fn record_admin_change(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    denom: &str,
+    sender: &Addr,
+    new_admin: Option<&Addr>,
+) -> StdResult<()> {
+    let next = ADMIN_HISTORY
+        .prefix(denom)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    let change = AdminChange {
+        sender: sender.clone(),
+        new_admin: new_admin.cloned(),
+        height: block.height,
+    };
+    ADMIN_HISTORY.save(storage, (denom, next), &change)
+}
+
+/// One entry of `TokenFactoryModule::admin_history`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AdminChange {
+    /// Who created the denom or changed its admin
+    pub sender: Addr,
+    /// The admin from then on, `None` if renounced
+    pub new_admin: Option<Addr>,
+    /// The block height of the change
+    pub height: u64,
+}
+
+/// Privileged operations on the mock, outside of what the chain offers.
+/// cw-multi-test doesn't route custom sudo messages, use `TokenFactoryApp::sudo_token_factory`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenFactorySudo {
+    /// Forgets a denom whose supply is zero, see `TokenFactoryModule::with_gc_empty_denoms`.
+    /// Only its admin remains as a tombstone, so the denom can't be created again and
+    /// `TokenQuery::Admin` still answers, but it is gone from all other queries and `dump_state`.
+    /// Like a message, it must come from the admin of the denom, so denoms without an
+    /// admin are never collected.
+    GarbageCollect {
+        /// The full denom
+        denom: String,
+        /// The admin requesting the collection
+        admin: Addr,
+    },
+    /// Removes the metadata of a denom, eg. to reset it between scenarios. Chains can't
+    /// do this: `SetMetadata` replaces the metadata in full, but it is never deleted.
+    /// The history of `TokenQuery::MetadataHistory` is kept. Locked metadata can't be cleared.
+    ClearMetadata {
+        /// The full denom
+        denom: String,
+    },
+}
+
+/// The full state of the mock token factory, see `TokenFactoryModule::dump_state`.
+/// Serializable, so it can be compared against golden files.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TokenFactoryState {
+    /// All denoms ever created, sorted
+    pub denoms: Vec<DenomState>,
+}
+
+/// The state of a single denom in `TokenFactoryState`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DenomState {
+    /// The full denom
+    pub denom: String,
+    /// The current admin, `None` once renounced
+    pub admin: Option<String>,
+    /// The metadata, if any was set
+    pub metadata: Option<Metadata>,
+    /// Whether the metadata was locked with `TokenMsg::LockMetadata`
+    pub locked: bool,
+    /// Total minted minus burned amount
+    pub supply: Uint128,
+}
+
+/// The complete storage and block of a `TokenFactoryApp`, see `TokenFactoryApp::snapshot`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    records: Vec<Record>,
+    block: BlockInfo,
+}
+
+/// Generic errors of the token factory test helpers
+#[derive(Error, Debug, PartialEq)]
+pub enum TokenFactoryError {
+    /// Storage, serialization or address validation error
+    #[error("{0}")]
     Std(#[from] StdError),
 
+    /// Time travel helpers only move forward
+    #[error("Cannot go back from {current} to {target}")]
+    Backwards {
+        /// The current block height or time
+        current: String,
+        /// The requested, earlier, block height or time
+        target: String,
+    },
+
     /// Remove this to let the compiler find all TODOs
     #[error("Not yet implemented (TODO)")]
     Unimplemented,
 }
 
+/// The `App` wrapped by `TokenFactoryApp`, with the token factory as custom module
 pub type TokenFactoryAppWrapped = App<
     BankKeeper,
     MockApi,
@@ -232,6 +1246,17 @@ pub type TokenFactoryAppWrapped = App<
     WasmKeeper<TokenFactoryMsg, TokenFactoryQuery>,
 >;
 
+/// The router of a `TokenFactoryApp`, as passed to genesis init functions
+pub type TokenFactoryRouter = Router<
+    BankKeeper,
+    TokenFactoryModule,
+    WasmKeeper<TokenFactoryMsg, TokenFactoryQuery>,
+    FailingStaking,
+    FailingDistribution,
+>;
+
+/// A multitest `App` supporting the token factory bindings.
+/// Derefs to `TokenFactoryAppWrapped`, so all `App` and `Executor` methods are available.
 pub struct TokenFactoryApp(TokenFactoryAppWrapped);
 
 impl Deref for TokenFactoryApp {
@@ -260,17 +1285,255 @@ impl Default for TokenFactoryApp {
     }
 }
 
-impl TokenFactoryApp {
+/// Configures the token factory module and its genesis state before building a `TokenFactoryApp`
+#[derive(Default)]
+pub struct TokenFactoryAppBuilder {
+    module: TokenFactoryModule,
+}
+
+impl TokenFactoryAppBuilder {
+    /// Starts with the defaults: no creation fee, messages are executed
     pub fn new() -> Self {
-        Self(
+        Self::default()
+    }
+
+    /// See `TokenFactoryModule::with_interception`
+    pub fn with_interception(mut self) -> Self {
+        self.module = self.module.with_interception();
+        self
+    }
+
+    /// See `TokenFactoryModule::with_subdenom_rules`
+    pub fn with_subdenom_rules(mut self, allowed_chars: &str, max_len: u32) -> Self {
+        self.module = self.module.with_subdenom_rules(allowed_chars, max_len);
+        self
+    }
+
+    /// See `TokenFactoryModule::with_fee_policy`
+    pub fn with_fee_policy(mut self, policy: Box<dyn CreationFeePolicy>) -> Self {
+        self.module = self.module.with_fee_policy(policy);
+        self
+    }
+
+    /// See `TokenFactoryModule::with_metadata_history`
+    pub fn with_metadata_history(mut self) -> Self {
+        self.module = self.module.with_metadata_history();
+        self
+    }
+
+    /// See `TokenFactoryModule::with_force_transfer_triggers_hook`
+    pub fn with_force_transfer_triggers_hook(mut self, triggers_hook: bool) -> Self {
+        self.module = self.module.with_force_transfer_triggers_hook(triggers_hook);
+        self
+    }
+
+    /// See `TokenFactoryModule::with_gc_empty_denoms`
+    pub fn with_gc_empty_denoms(mut self) -> Self {
+        self.module = self.module.with_gc_empty_denoms();
+        self
+    }
+
+    /// See `TokenFactoryModule::with_creator_format`
+    pub fn with_creator_format(mut self, prefix: &str, address_bytes: Option<usize>) -> Self {
+        self.module = self.module.with_creator_format(prefix, address_bytes);
+        self
+    }
+
+    /// See `TokenFactoryModule::with_features`
+    pub fn with_features(mut self, features: TokenFactoryFeatures) -> Self {
+        self.module = self.module.with_features(features);
+        self
+    }
+
+    /// See `TokenFactoryModule::with_max_mint_per_tx`
+    pub fn with_max_mint_per_tx(mut self, limit: Uint128) -> Self {
+        self.module = self.module.with_max_mint_per_tx(limit);
+        self
+    }
+
+    /// Sets the module params at genesis
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.module = self.module.with_params(params);
+        self
+    }
+
+    /// See `TokenFactoryModule::with_creation_fee`
+    pub fn with_creation_fee(mut self, fee: impl Into<CreationFee>) -> Self {
+        self.module = self.module.with_creation_fee(fee);
+        self
+    }
+
+    /// Disables the token factory, so no denom can be created
+    pub fn disabled(mut self) -> Self {
+        self.module = self.module.disabled();
+        self
+    }
+
+    /// Builds the app, setting up the configured genesis state
+    pub fn build(self) -> TokenFactoryApp {
+        self.build_with(|_, _, _| {})
+    }
+
+    /// Builds the app, then calls `init_fn` to set up any further genesis state,
+    /// like balances or minters
+    pub fn build_with<F>(self, init_fn: F) -> TokenFactoryApp
+    where
+        F: FnOnce(&mut TokenFactoryRouter, &dyn Api, &mut dyn Storage),
+    {
+        TokenFactoryApp(
             BasicAppBuilder::<TokenFactoryMsg, TokenFactoryQuery>::new_custom()
-                .with_custom(TokenFactoryModule {})
-                .build(|_router, _, _storage| {
-                    // router.custom.set_owner(storage, &owner).unwrap();
+                .with_custom(self.module)
+                .build(|router, api, storage| {
+                    router.custom.init(storage).unwrap();
+                    init_fn(router, api, storage);
                 }),
         )
     }
+}
+
+impl TokenFactoryApp {
+    /// Builds an app with the default configuration
+    pub fn new() -> Self {
+        TokenFactoryAppBuilder::new().build()
+    }
+
+    /// Builds an app with the default configuration and custom genesis state.
+    /// See `TokenFactoryAppBuilder::build_with`
+    pub fn new_with<F>(init_fn: F) -> Self
+    where
+        F: FnOnce(&mut TokenFactoryRouter, &dyn Api, &mut dyn Storage),
+    {
+        TokenFactoryAppBuilder::new().build_with(init_fn)
+    }
+
+    /// Returns a builder to configure the app
+    pub fn builder() -> TokenFactoryAppBuilder {
+        TokenFactoryAppBuilder::new()
+    }
+
+    /// See `TokenFactoryModule::on_admin_change`
+    pub fn on_admin_change(&mut self, callback: impl FnMut(&str, &Addr, Option<&Addr>) + 'static) {
+        self.init_modules(|router, _, _| router.custom.on_admin_change(callback))
+    }
+
+    /// Returns all messages (and their senders) the module recorded instead of executing.
+    /// Only populated when built `with_interception`.
+    pub fn intercepted_msgs(&self) -> Vec<(Addr, TokenMsg)> {
+        self.read_module(|router, _, _| router.custom.intercepted_msgs())
+    }
+
+    /// Returns how many token factory queries were made against this app.
+    /// Useful to assert a code path performs at most N queries.
+    pub fn query_count(&self) -> u64 {
+        self.read_module(|router, _, _| router.custom.query_count())
+    }
+
+    /// See `TokenFactoryModule::allow_minter`
+    pub fn allow_minter(&mut self, denom: &str, minter: &str) -> StdResult<()> {
+        self.init_modules(|router, api, storage| {
+            let minter = api.addr_validate(minter)?;
+            router.custom.allow_minter(storage, denom, &minter)
+        })
+    }
+
+    /// See `TokenFactoryModule::set_creation_fee`
+    pub fn set_creation_fee(&mut self, fee: impl Into<CreationFee>) -> StdResult<()> {
+        self.init_modules(|router, _, storage| router.custom.set_creation_fee(storage, fee))
+    }
+
+    /// See `TokenFactoryModule::creation_fee`
+    pub fn creation_fee(&self) -> StdResult<CreationFee> {
+        self.read_module(|router, _, storage| router.custom.creation_fee(storage))
+    }
+
+    /// See `TokenFactoryModule::choose_creation_fee`
+    pub fn choose_creation_fee(&mut self, payer: &str, fee: Vec<Coin>) -> StdResult<()> {
+        self.init_modules(|router, api, storage| {
+            let payer = api.addr_validate(payer)?;
+            router.custom.choose_creation_fee(storage, &payer, fee)
+        })
+    }
+
+    /// See `TokenFactoryModule::set_mint_enabled`
+    pub fn set_mint_enabled(&mut self, denom: &str, enabled: bool) -> StdResult<()> {
+        self.init_modules(|router, _, storage| {
+            router.custom.set_mint_enabled(storage, denom, enabled)
+        })
+    }
+
+    /// See `TokenFactoryModule::set_supply_cap`
+    pub fn set_supply_cap(&mut self, denom: &str, cap: Option<Uint128>) -> StdResult<()> {
+        self.init_modules(|router, _, storage| router.custom.set_supply_cap(storage, denom, cap))
+    }
+
+    /// See `TokenFactoryModule::set_staking_token_metadata`
+    pub fn set_staking_token_metadata(&mut self, metadata: Metadata) -> StdResult<()> {
+        self.init_modules(|router, _, storage| {
+            router.custom.set_staking_token_metadata(storage, &metadata)
+        })
+    }
+
+    /// See `TokenFactoryModule::denom_count`
+    pub fn denom_count(&self) -> StdResult<u64> {
+        self.read_module(|router, _, storage| router.custom.denom_count(storage))
+    }
+
+    /// See `TokenFactoryModule::denom_count_for`
+    pub fn denom_count_for(&self, creator: &str) -> StdResult<u64> {
+        self.read_module(|router, api, storage| {
+            let creator = api.addr_validate(creator)?;
+            router.custom.denom_count_for(storage, &creator)
+        })
+    }
+
+    /// Executes a sudo message on the token factory module, committing on success
+    pub fn sudo_token_factory(&mut self, msg: TokenFactorySudo) -> AnyResult<AppResponse> {
+        let block = self.block_info();
+        self.init_modules(|router, api, storage| {
+            router.custom.sudo(api, storage, router, &block, msg)
+        })
+    }
+
+    /// See `TokenFactoryModule::admin_history`
+    pub fn admin_history(&self, denom: &str) -> StdResult<Vec<AdminChange>> {
+        self.read_module(|router, _, storage| router.custom.admin_history(storage, denom))
+    }
+
+    /// See `TokenFactoryModule::dump_state`
+    pub fn dump_state(&self) -> StdResult<TokenFactoryState> {
+        self.read_module(|router, _, storage| router.custom.dump_state(storage))
+    }
+
+    /// Captures the whole app storage (token factory, bank balances, contract states)
+    /// and the block, so a test can branch from the same point several times with `restore`.
+    /// Uploaded code and the module's interception and query counters are not captured.
+    pub fn snapshot(&self) -> Snapshot {
+        let records =
+            self.read_module(|_, _, storage| storage.range(None, None, Order::Ascending).collect());
+        Snapshot {
+            records,
+            block: self.block_info(),
+        }
+    }
+
+    /// Resets the storage and block to what they were when `snapshot` was taken
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.init_modules(|_, _, storage| {
+            let keys: Vec<Vec<u8>> = storage
+                .range(None, None, Order::Ascending)
+                .map(|(key, _)| key)
+                .collect();
+            for key in keys {
+                storage.remove(&key);
+            }
+            for (key, value) in &snapshot.records {
+                storage.set(key, value);
+            }
+        });
+        self.set_block(snapshot.block.clone());
+    }
 
+    /// Returns the current block info
     pub fn block_info(&self) -> BlockInfo {
         self.0.block_info()
     }
@@ -293,6 +1556,42 @@ impl TokenFactoryApp {
         });
     }
 
+    /// Advances to the given block time, moving the height by the elapsed blocks
+    /// (at least one) as `advance_seconds` does. Errors if `time` is in the past,
+    /// and does nothing if it is the current block time.
+    pub fn advance_to_time(&mut self, time: Timestamp) -> Result<(), TokenFactoryError> {
+        let current = self.block_info().time;
+        if time < current {
+            return Err(TokenFactoryError::Backwards {
+                current: current.to_string(),
+                target: time.to_string(),
+            });
+        }
+        if time > current {
+            let elapsed = time.nanos() - current.nanos();
+            self.update_block(|block| {
+                block.time = time;
+                block.height += max(1, elapsed / 1_000_000_000 / BLOCK_TIME);
+            });
+        }
+        Ok(())
+    }
+
+    /// Advances to the given block height, moving the time by `BLOCK_TIME` per block
+    /// as `advance_blocks` does. Errors if `height` is in the past,
+    /// and does nothing if it is the current height.
+    pub fn advance_to_height(&mut self, height: u64) -> Result<(), TokenFactoryError> {
+        let current = self.block_info().height;
+        if height < current {
+            return Err(TokenFactoryError::Backwards {
+                current: current.to_string(),
+                target: height.to_string(),
+            });
+        }
+        self.advance_blocks(height - current);
+        Ok(())
+    }
+
     /// Simple iterator when you don't care too much about the details and just want to
     /// simulate forward motion.
     pub fn next_block(&mut self) {
@@ -303,8 +1602,10 @@ impl TokenFactoryApp {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::{Coin, Uint128};
-    use cw_multi_test::Executor;
+    use crate::{unwrap_token_factory_err, SendToCollector, TokenFactoryModuleError};
+    use cosmwasm_std::{coin, Attribute};
+    use cw_multi_test::{ContractWrapper, Executor};
+    use token_bindings::{derive_subdenom, fixed_supply_token_msgs, MetadataError, TokenQuerier};
 
     #[test]
     fn mint_token() {
@@ -338,6 +1639,7 @@ mod tests {
             denom: denom.to_string(),
             amount,
             mint_to_address: rcpt.to_string(),
+            dedup_key: None,
         };
 
         // fails to mint token before creating it
@@ -345,8 +1647,8 @@ mod tests {
             .execute(contract.clone(), msg.clone().into())
             .unwrap_err();
         assert_eq!(
-            err.downcast::<ContractError>().unwrap(),
-            ContractError::TokenDoesntExist
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::TokenDoesntExist)
         );
 
         // create the token now
@@ -354,11 +1656,7 @@ mod tests {
             subdenom: subdenom.to_string(),
             metadata: Some(Metadata {
                 description: Some("Awesome token, get it now!".to_string()),
-                denom_units: vec![],
-                base: None,
-                display: Some("FUNDZ".to_string()),
-                name: Some("Fundz pays".to_string()),
-                symbol: Some("FUNDZ".to_string()),
+                ..Metadata::two_unit(&denom, "FUNDZ", 6, "FUNDZ", "Fundz pays")
             }),
         };
         app.execute(contract.clone(), create.into()).unwrap();
@@ -375,4 +1673,1851 @@ mod tests {
         let empty = app.wrap().query_balance(rcpt.as_str(), subdenom).unwrap();
         assert_eq!(empty.amount, Uint128::zero());
     }
+
+    #[test]
+    fn count_queries() {
+        let app = TokenFactoryApp::new();
+        assert_eq!(app.query_count(), 0);
+
+        let query = TokenQuery::FullDenom {
+            creator_addr: "creator".to_string(),
+            subdenom: "fundz".to_string(),
+        };
+        let _: FullDenomResponse = app.wrap().query(&query.into()).unwrap();
+        let _: MetadataResponse = app
+            .wrap()
+            .query(
+                &TokenQuery::Metadata {
+                    denom: "factory/creator/fundz".to_string(),
+                }
+                .into(),
+            )
+            .unwrap();
+        assert_eq!(app.query_count(), 2);
+
+        // bank queries are not counted
+        app.wrap().query_all_balances("creator").unwrap();
+        assert_eq!(app.query_count(), 2);
+
+        // failing queries still count
+        app.wrap()
+            .query::<AdminResponse>(
+                &TokenQuery::Admin {
+                    denom: "factory/creator/fundz".to_string(),
+                }
+                .into(),
+            )
+            .unwrap_err();
+        assert_eq!(app.query_count(), 3);
+    }
+
+    #[test]
+    fn intercept_messages() {
+        let contract = Addr::unchecked("govner");
+        let rcpt = Addr::unchecked("townies");
+        let denom = "factory/govner/fundz";
+
+        let mut app = TokenFactoryApp::builder().with_interception().build();
+
+        // minting a denom that was never created is acknowledged
+        let mint = TokenMsg::MintTokens {
+            denom: denom.to_string(),
+            amount: Uint128::new(1234),
+            mint_to_address: rcpt.to_string(),
+            dedup_key: None,
+        };
+        app.execute(contract.clone(), mint.clone().into()).unwrap();
+
+        // but not applied
+        let balance = app.wrap().query_balance(rcpt.as_str(), denom).unwrap();
+        assert_eq!(balance.amount, Uint128::zero());
+        assert_eq!(app.intercepted_msgs(), vec![(contract, mint)]);
+    }
+
+    #[test]
+    fn can_mint() {
+        let contract = Addr::unchecked("govner");
+        let other = Addr::unchecked("other");
+        let mut app = TokenFactoryApp::new();
+
+        let denom = "factory/govner/fundz".to_string();
+        let can_mint = |app: &TokenFactoryApp, minter: &Addr, amount: u128| {
+            let query = TokenQuery::CanMint {
+                denom: denom.clone(),
+                minter: minter.to_string(),
+                amount: Uint128::new(amount),
+            };
+            app.wrap().query::<CanMintResponse>(&query.into()).unwrap()
+        };
+        let refused = |err: ContractError| CanMintResponse {
+            allowed: false,
+            reason: Some(err.to_string()),
+        };
+
+        // denom doesn't exist yet
+        assert_eq!(
+            can_mint(&app, &contract, 100),
+            refused(ContractError::TokenDoesntExist)
+        );
+
+        let create = TokenMsg::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        };
+        app.execute(contract.clone(), create.into()).unwrap();
+
+        // the admin may mint
+        assert_eq!(
+            can_mint(&app, &contract, 100),
+            CanMintResponse {
+                allowed: true,
+                reason: None,
+            }
+        );
+        // but nobody else
+        assert_eq!(
+            can_mint(&app, &other, 100),
+            refused(ContractError::NotTokenAdmin)
+        );
+
+        // not while minting is disabled
+        app.set_mint_enabled(&denom, false).unwrap();
+        assert_eq!(
+            can_mint(&app, &contract, 100),
+            refused(ContractError::MintingDisabled {
+                denom: denom.clone()
+            })
+        );
+        app.set_mint_enabled(&denom, true).unwrap();
+
+        // and at most up to the cap
+        app.set_supply_cap(&denom, Some(Uint128::new(150))).unwrap();
+        let mint = TokenMsg::MintTokens {
+            denom: denom.clone(),
+            amount: Uint128::new(100),
+            mint_to_address: contract.to_string(),
+            dedup_key: None,
+        };
+        app.execute(contract.clone(), mint.into()).unwrap();
+        assert_eq!(
+            can_mint(&app, &contract, 50),
+            CanMintResponse {
+                allowed: true,
+                reason: None,
+            }
+        );
+        assert_eq!(
+            can_mint(&app, &contract, 51),
+            refused(ContractError::SupplyCapExceeded {
+                cap: Uint128::new(150),
+                supply: Uint128::new(100),
+                requested: Uint128::new(51),
+            })
+        );
+    }
+
+    fn create_denom(app: &mut TokenFactoryApp, creator: &Addr, subdenom: &str) -> String {
+        let create = TokenMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+            metadata: None,
+        };
+        app.execute(creator.clone(), create.into()).unwrap();
+        format!("factory/{}/{}", creator, subdenom)
+    }
+
+    fn supply(app: &TokenFactoryApp, denom: &str) -> Uint128 {
+        app.read_module(|_, _, storage| SUPPLY.may_load(storage, denom))
+            .unwrap()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn mint_overflow_errors() {
+        let contract = Addr::unchecked("govner");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &contract, "fundz");
+
+        let mint = |amount: Uint128| TokenMsg::MintTokens {
+            denom: denom.clone(),
+            amount,
+            mint_to_address: contract.to_string(),
+            dedup_key: None,
+        };
+        app.execute(contract.clone(), mint(Uint128::MAX).into())
+            .unwrap();
+        assert_eq!(supply(&app, &denom), Uint128::MAX);
+
+        let err = app
+            .execute(contract.clone(), mint(Uint128::new(1)).into())
+            .unwrap_err();
+        assert!(matches!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::Overflow(_))
+        ));
+        assert_eq!(supply(&app, &denom), Uint128::MAX);
+    }
+
+    #[test]
+    fn burn_to_zero() {
+        let contract = Addr::unchecked("govner");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &contract, "fundz");
+
+        let mint = TokenMsg::MintTokens {
+            denom: denom.clone(),
+            amount: Uint128::MAX,
+            mint_to_address: contract.to_string(),
+            dedup_key: None,
+        };
+        app.execute(contract.clone(), mint.into()).unwrap();
+
+        let burn = |amount: Uint128| TokenMsg::BurnTokens {
+            denom: denom.clone(),
+            amount,
+            burn_from_address: "".to_string(),
+        };
+        app.execute(contract.clone(), burn(Uint128::MAX).into())
+            .unwrap();
+        assert_eq!(supply(&app, &denom), Uint128::zero());
+        let balance = app.wrap().query_balance(contract.as_str(), &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::zero());
+
+        // nothing left to burn
+        app.execute(contract.clone(), burn(Uint128::new(1)).into())
+            .unwrap_err();
+        assert_eq!(supply(&app, &denom), Uint128::zero());
+
+        // only from ourselves
+        let burn_from = TokenMsg::BurnTokens {
+            denom,
+            amount: Uint128::new(1),
+            burn_from_address: "someone".to_string(),
+        };
+        let err = app.execute(contract, burn_from.into()).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::BurnFromNotSupported)
+        );
+    }
+
+    #[test]
+    fn burn_from_feature() {
+        let admin = Addr::unchecked("admin");
+        let features = TokenFactoryFeatures {
+            burn_from: true,
+            ..TokenFactoryModule::default().features()
+        };
+        let mut app = TokenFactoryApp::builder().with_features(features).build();
+        let denom = create_denom(&mut app, &admin, "fundz");
+        let mint =
+            TokenMsg::mint_contract_tokens(denom.clone(), Uint128::new(100), "holder".into());
+        app.execute(admin.clone(), mint.into()).unwrap();
+
+        let burn_from = |amount: u128| TokenMsg::BurnTokens {
+            denom: denom.clone(),
+            amount: Uint128::new(amount),
+            burn_from_address: "holder".to_string(),
+        };
+        let res = app.execute(admin.clone(), burn_from(30).into()).unwrap();
+        let event = res.events.iter().find(|e| e.ty == EVENT_BURN).unwrap();
+        assert!(event
+            .attributes
+            .contains(&Attribute::new(ATTR_BURN_FROM_ADDRESS, "holder")));
+        let balance = app.wrap().query_balance("holder", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(70));
+        assert_eq!(supply(&app, &denom), Uint128::new(70));
+
+        // still only by the admin, and within the balance of the holder
+        let err = app
+            .execute(Addr::unchecked("holder"), burn_from(1).into())
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+        );
+        app.execute(admin, burn_from(71).into()).unwrap_err();
+        assert_eq!(supply(&app, &denom), Uint128::new(70));
+    }
+
+    #[test]
+    fn allowlisted_minter() {
+        let contract = Addr::unchecked("govner");
+        let minter = Addr::unchecked("minter");
+        let other = Addr::unchecked("other");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &contract, "fundz");
+
+        let mint = TokenMsg::MintTokens {
+            denom: denom.clone(),
+            amount: Uint128::new(100),
+            mint_to_address: other.to_string(),
+            dedup_key: None,
+        };
+        app.allow_minter(&denom, minter.as_str()).unwrap();
+
+        // allowlisted address may mint without being admin
+        app.execute(minter, mint.clone().into()).unwrap();
+        let balance = app.wrap().query_balance(other.as_str(), &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(100));
+
+        // others may still not
+        let err = app.execute(other, mint.into()).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+        );
+    }
+
+    #[test]
+    fn creation_fee_in() {
+        let fee = vec![coin(10_000_000, "uosmo"), coin(500, "uion")];
+        let app = TokenFactoryApp::builder()
+            .with_creation_fee(fee.clone())
+            .build();
+
+        let ParamsResponse { params } = app.wrap().query(&TokenQuery::Params {}.into()).unwrap();
+        assert_eq!(params.denom_creation_fee, fee);
+
+        let fee_in = |denom: &str| {
+            let query = TokenQuery::CreationFeeIn {
+                denom: denom.to_string(),
+            };
+            let res: CreationFeeInResponse = app.wrap().query(&query.into()).unwrap();
+            res.amount
+        };
+        assert_eq!(fee_in("uosmo"), Some(Uint128::new(10_000_000)));
+        assert_eq!(fee_in("uion"), Some(Uint128::new(500)));
+        assert_eq!(fee_in("uatom"), None);
+
+        // every option is searched, in order
+        let options = vec![coins(100, "uosmo"), coins(5, "uion"), coins(7, "uosmo")];
+        let app = TokenFactoryApp::builder()
+            .with_creation_fee(options.clone())
+            .build();
+        let fee_in = |denom: &str| {
+            let res = TokenQuerier::new(&app.wrap())
+                .creation_fee_in(denom)
+                .unwrap();
+            res.amount
+        };
+        assert_eq!(fee_in("uosmo"), Some(Uint128::new(100)));
+        assert_eq!(fee_in("uion"), Some(Uint128::new(5)));
+        assert_eq!(fee_in("uatom"), None);
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(querier.creation_fee_options().unwrap(), options);
+        assert_eq!(
+            querier.params().unwrap().params.denom_creation_fee,
+            coins(100, "uosmo")
+        );
+    }
+
+    #[test]
+    fn creation_fee_is_charged() {
+        let creator = Addr::unchecked("creator");
+        let fee = vec![coin(100, "uosmo"), coin(5, "uion")];
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(fee.clone())
+            .build();
+
+        // fund exactly the fee
+        app.init_modules(|router, _, storage| {
+            router.bank.init_balance(storage, &creator, fee.clone())
+        })
+        .unwrap();
+
+        create_denom(&mut app, &creator, "first");
+        let balance = app.wrap().query_all_balances(creator.as_str()).unwrap();
+        assert_eq!(balance, vec![]);
+
+        // nothing left for a second one
+        let create = TokenMsg::CreateDenom {
+            subdenom: "second".to_string(),
+            metadata: None,
+        };
+        app.execute(creator, create.into()).unwrap_err();
+    }
+
+    #[test]
+    fn cannot_recreate_after_renounce() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+
+        let renounce = TokenMsg::ChangeAdmin {
+            denom: denom.clone(),
+            new_admin_address: "".to_string(),
+        };
+        app.execute(creator.clone(), renounce.into()).unwrap();
+
+        let AdminResponse { admin } = app
+            .wrap()
+            .query(
+                &TokenQuery::Admin {
+                    denom: denom.clone(),
+                }
+                .into(),
+            )
+            .unwrap();
+        assert_eq!(admin, "");
+
+        // nobody administers it anymore
+        let mint = TokenMsg::mint_contract_tokens(denom, Uint128::new(1), creator.to_string());
+        let err = app.execute(creator.clone(), mint.into()).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+        );
+
+        // and the pair is still taken
+        let create = TokenMsg::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        };
+        let err = app.execute(creator, create.into()).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::TokenExists)
+        );
+    }
+
+    #[test]
+    fn emits_chain_events() {
+        use token_bindings::events::{
+            parse_burn_event, parse_create_denom_event, parse_mint_event,
+        };
+
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+
+        let create = TokenMsg::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        };
+        let res = app.execute(creator.clone(), create.into()).unwrap();
+        let created = parse_create_denom_event(&res.events).unwrap();
+        assert_eq!(created.creator, creator.as_str());
+        let denom = created.new_token_denom;
+        assert_eq!(denom, "factory/creator/fundz");
+
+        let mint = TokenMsg::mint_contract_tokens(denom.clone(), Uint128::new(500), "rcpt".into());
+        let res = app.execute(creator.clone(), mint.into()).unwrap();
+        let minted = parse_mint_event(&res.events).unwrap();
+        assert_eq!(minted.denom, denom);
+        assert_eq!(minted.amount, Uint128::new(500));
+        assert_eq!(minted.mint_to_address, "rcpt");
+
+        let mint =
+            TokenMsg::mint_contract_tokens(denom.clone(), Uint128::new(20), creator.to_string());
+        app.execute(creator.clone(), mint.into()).unwrap();
+        let burn = TokenMsg::burn_contract_tokens(denom.clone(), Uint128::new(20), "".into());
+        let res = app.execute(creator.clone(), burn.into()).unwrap();
+        let burned = parse_burn_event(&res.events).unwrap();
+        assert_eq!(burned.denom, denom);
+        assert_eq!(burned.amount, Uint128::new(20));
+        assert_eq!(burned.burn_from_address, creator.as_str());
+    }
+
+    #[test]
+    fn created_denom_attribute() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+
+        let create = TokenMsg::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        };
+        let res = app.execute(creator.clone(), create.into()).unwrap();
+
+        // readable straight off the response, without decoding the protobuf data
+        let attribute = res
+            .events
+            .iter()
+            .filter(|e| e.ty == EVENT_CREATE_DENOM)
+            .flat_map(|e| e.attributes.iter())
+            .find(|attr| attr.key == ATTR_NEW_TOKEN_DENOM)
+            .unwrap();
+        let querier = app.wrap();
+        let expected = TokenQuerier::new(&querier)
+            .full_denom(creator.to_string(), "fundz".to_string())
+            .unwrap()
+            .denom;
+        assert_eq!(attribute.value, expected);
+    }
+
+    #[test]
+    fn failed_creation_leaves_no_traces() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(coins(1000, "uosmo"))
+            .build();
+        let block = app.block_info();
+
+        // call the module directly, as App::execute would revert everything anyway
+        let create = TokenFactoryMsg::Token(TokenMsg::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: Some(Metadata::two_unit("ufundz", "fundz", 6, "FUNDZ", "Fundz")),
+        });
+        let res = app.init_modules(|router, api, storage| {
+            router
+                .custom
+                .execute(api, storage, router, &block, creator.clone(), create)
+        });
+        // cannot pay the fee
+        res.unwrap_err();
+
+        let denom = "factory/creator/fundz";
+        app.read_module(|_, _, storage| {
+            assert!(!CREATED.has(storage, denom));
+            assert!(!ADMIN.has(storage, denom));
+            assert!(!METADATA.has(storage, denom));
+            assert!(!DENOMS_BY_CREATOR.has(storage, &creator));
+        });
+    }
+
+    #[test]
+    fn failed_mint_leaves_no_traces() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let block = app.block_info();
+
+        // the bank refuses the recipient
+        let mint = TokenFactoryMsg::Token(TokenMsg::MintTokens {
+            denom: denom.clone(),
+            amount: Uint128::new(100),
+            mint_to_address: "".to_string(),
+            dedup_key: Some("airdrop".to_string()),
+        });
+        let res = app.init_modules(|router, api, storage| {
+            router
+                .custom
+                .execute(api, storage, router, &block, creator.clone(), mint)
+        });
+        res.unwrap_err();
+
+        app.read_module(|_, _, storage| {
+            assert!(!SUPPLY.has(storage, &denom));
+            assert!(!MINT_KEYS.has(storage, (&denom, "airdrop")));
+        });
+    }
+
+    #[test]
+    fn custom_genesis() {
+        let owner = Addr::unchecked("owner");
+        let minter = Addr::unchecked("minter");
+        let denom = "factory/owner/fundz";
+
+        let app = TokenFactoryApp::new_with(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &owner, coins(1000, "uosmo"))
+                .unwrap();
+            router.custom.allow_minter(storage, denom, &minter).unwrap();
+        });
+
+        let balance = app.wrap().query_balance(owner.as_str(), "uosmo").unwrap();
+        assert_eq!(balance.amount, Uint128::new(1000));
+        let allowed = app.read_module(|_, _, storage| MINTERS.has(storage, (denom, &minter)));
+        assert!(allowed);
+    }
+
+    #[test]
+    fn staking_token_metadata() {
+        let mut app = TokenFactoryApp::new();
+        let query = TokenQuery::Metadata {
+            denom: "uosmo".to_string(),
+        };
+        let res: MetadataResponse = app.wrap().query(&query.clone().into()).unwrap();
+        assert_eq!(res.metadata, None);
+
+        let metadata = Metadata::two_unit("uosmo", "osmo", 6, "OSMO", "Osmosis");
+        app.set_staking_token_metadata(metadata.clone()).unwrap();
+        let res: MetadataResponse = app.wrap().query(&query.into()).unwrap();
+        assert_eq!(res.metadata, Some(metadata));
+
+        let mut no_base = Metadata::two_unit("uosmo", "osmo", 6, "OSMO", "Osmosis");
+        no_base.base = None;
+        app.set_staking_token_metadata(no_base).unwrap_err();
+    }
+
+    #[test]
+    fn parse_denom() {
+        let app = TokenFactoryApp::new();
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+
+        let res = querier
+            .parse_denom("factory/juno1creator/fundz".to_string())
+            .unwrap();
+        assert_eq!(
+            res,
+            ParseDenomResponse {
+                prefix: "factory".to_string(),
+                creator: "juno1creator".to_string(),
+                subdenom: "fundz".to_string(),
+            }
+        );
+
+        for malformed in [
+            "uosmo",
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+            "gamm/pool/1",
+            "factory/creator/fundz/more",
+            "factory/creator/fun-dz",
+        ] {
+            querier.parse_denom(malformed).unwrap_err();
+        }
+    }
+
+    mod hook {
+        use super::*;
+        use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
+
+        pub const BLOCKED: &str = "blocked";
+
+        pub fn instantiate(
+            _: DepsMut<TokenFactoryQuery>,
+            _: Env,
+            _: MessageInfo,
+            _: Empty,
+        ) -> StdResult<Response<TokenFactoryMsg>> {
+            Ok(Response::new())
+        }
+
+        pub fn query(_: Deps<TokenFactoryQuery>, _: Env, _: Empty) -> StdResult<Binary> {
+            to_binary(&Empty {})
+        }
+
+        /// Rejects every send to `BLOCKED`
+        pub fn sudo(
+            _: DepsMut<TokenFactoryQuery>,
+            _: Env,
+            msg: BeforeSendHookMsg,
+        ) -> StdResult<Response<TokenFactoryMsg>> {
+            let BeforeSendHookMsg::BlockBeforeSend { to, .. } = msg;
+            if to == BLOCKED {
+                return Err(StdError::generic_err("recipient is blocked"));
+            }
+            Ok(Response::new())
+        }
+    }
+
+    /// Creates a denom with a blocking before send hook, and mints 100 to alice
+    fn setup_hooked_denom(triggers_hook: bool) -> (TokenFactoryApp, Addr, String) {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::builder()
+            .with_force_transfer_triggers_hook(triggers_hook)
+            .build();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let mint = TokenMsg::mint_contract_tokens(denom.clone(), Uint128::new(100), "alice".into());
+        app.execute(creator.clone(), mint.into()).unwrap();
+
+        let code = ContractWrapper::new(hook::instantiate, hook::instantiate, hook::query)
+            .with_sudo(hook::sudo);
+        let code_id = app.store_code(Box::new(code));
+        let hook = app
+            .instantiate_contract(code_id, creator.clone(), &Empty {}, &[], "hook", None)
+            .unwrap();
+        let set_hook = TokenMsg::SetBeforeSendHook {
+            denom: denom.clone(),
+            cosmwasm_address: hook.to_string(),
+        };
+        app.execute(creator.clone(), set_hook.into()).unwrap();
+        (app, creator, denom)
+    }
+
+    fn force_transfer(denom: &str, amount: u128, to: &str) -> TokenMsg {
+        TokenMsg::ForceTransfer {
+            denom: denom.to_string(),
+            amount: Uint128::new(amount),
+            from_address: "alice".to_string(),
+            to_address: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn force_transfer_triggers_hook() {
+        let (mut app, creator, denom) = setup_hooked_denom(true);
+
+        // the hook rejects this one
+        let err = app
+            .execute(
+                creator.clone(),
+                force_transfer(&denom, 40, hook::BLOCKED).into(),
+            )
+            .unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("recipient is blocked"));
+        let balance = app.wrap().query_balance("alice", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(100));
+
+        // but lets others through
+        let res = app
+            .execute(creator.clone(), force_transfer(&denom, 40, "bob").into())
+            .unwrap();
+        res.assert_event(
+            &Event::new(EVENT_FORCE_TRANSFER)
+                .add_attribute(ATTR_TRANSFER_FROM_ADDRESS, "alice")
+                .add_attribute(ATTR_TRANSFER_TO_ADDRESS, "bob"),
+        );
+        let balance = app.wrap().query_balance("bob", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(40));
+
+        // removing the hook unblocks the recipient
+        let unset_hook = TokenMsg::SetBeforeSendHook {
+            denom: denom.clone(),
+            cosmwasm_address: "".to_string(),
+        };
+        app.execute(creator.clone(), unset_hook.into()).unwrap();
+        app.execute(creator, force_transfer(&denom, 10, hook::BLOCKED).into())
+            .unwrap();
+        let balance = app.wrap().query_balance(hook::BLOCKED, &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(10));
+    }
+
+    #[test]
+    fn force_transfer_bypasses_hook() {
+        let (mut app, creator, denom) = setup_hooked_denom(false);
+
+        app.execute(
+            creator.clone(),
+            force_transfer(&denom, 40, hook::BLOCKED).into(),
+        )
+        .unwrap();
+        let balance = app.wrap().query_balance(hook::BLOCKED, &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(40));
+        let balance = app.wrap().query_balance("alice", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(60));
+
+        // only the admin may force transfer, and no more than the balance
+        let err = app
+            .execute(
+                Addr::unchecked("bob"),
+                force_transfer(&denom, 10, "bob").into(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+        );
+        app.execute(creator.clone(), force_transfer(&denom, 61, "bob").into())
+            .unwrap_err();
+
+        // both addresses must be valid
+        let mut invalid_from = force_transfer(&denom, 10, "bob");
+        if let TokenMsg::ForceTransfer { from_address, .. } = &mut invalid_from {
+            *from_address = "Alice".to_string();
+        }
+        let err = app
+            .execute(creator.clone(), invalid_from.into())
+            .unwrap_err();
+        assert!(matches!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::InvalidAddress { .. }
+        ));
+        app.execute(creator, force_transfer(&denom, 10, "").into())
+            .unwrap_err();
+        let balance = app.wrap().query_balance("alice", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(60));
+    }
+
+    #[test]
+    fn advance_to_time_and_height() {
+        let mut app = TokenFactoryApp::new();
+        let start = app.block_info();
+
+        let target = start.time.plus_seconds(100);
+        app.advance_to_time(target).unwrap();
+        let block = app.block_info();
+        assert_eq!(block.time, target);
+        assert_eq!(block.height, start.height + 100 / BLOCK_TIME);
+
+        // less than a block still moves the height
+        let target = target.plus_nanos(1);
+        app.advance_to_time(target).unwrap();
+        assert_eq!(app.block_info().height, start.height + 100 / BLOCK_TIME + 1);
+
+        // staying put is a no-op
+        let before = app.block_info();
+        app.advance_to_time(target).unwrap();
+        app.advance_to_height(before.height).unwrap();
+        assert_eq!(app.block_info(), before);
+
+        let height = app.block_info().height + 10;
+        app.advance_to_height(height).unwrap();
+        let block = app.block_info();
+        assert_eq!(block.height, height);
+        assert_eq!(block.time, target.plus_seconds(10 * BLOCK_TIME));
+
+        // never backwards
+        let err = app.advance_to_height(height - 1).unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::Backwards {
+                current: height.to_string(),
+                target: (height - 1).to_string(),
+            }
+        );
+        app.advance_to_time(start.time).unwrap_err();
+        assert_eq!(app.block_info(), block);
+    }
+
+    #[test]
+    fn advance_to_absolute_height() {
+        let mut app = TokenFactoryApp::new();
+        let start = app.block_info();
+
+        app.advance_to_height(start.height + 1000).unwrap();
+        app.next_block();
+        app.advance_to_height(start.height + 1002).unwrap();
+        let block = app.block_info();
+        assert_eq!(block.height, start.height + 1002);
+        assert_eq!(block.time, start.time.plus_seconds(1002 * BLOCK_TIME));
+        assert_eq!(block.chain_id, start.chain_id);
+    }
+
+    #[test]
+    fn creation_respects_enabled_flag() {
+        let creator = Addr::unchecked("creator");
+        let create = TokenMsg::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: None,
+        };
+
+        let mut app = TokenFactoryApp::new();
+        let ParamsResponse { params } = app.wrap().query(&TokenQuery::Params {}.into()).unwrap();
+        assert!(params.enabled);
+        app.execute(creator.clone(), create.clone().into()).unwrap();
+
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(vec![coin(100, "uosmo")])
+            .disabled()
+            .build();
+        let ParamsResponse { params } = app.wrap().query(&TokenQuery::Params {}.into()).unwrap();
+        assert_eq!(
+            params,
+            Params {
+                denom_creation_fee: vec![coin(100, "uosmo")],
+                enabled: false,
+            }
+        );
+        let err = app.execute(creator, create.into()).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::TokenFactoryDisabled)
+        );
+    }
+
+    #[test]
+    fn count_denoms() {
+        let creators = ["alice", "bob", "carol"];
+        let mut app = TokenFactoryApp::builder()
+            .with_creation_fee(vec![coin(1, "uosmo")])
+            .build_with(|router, _, storage| {
+                for creator in creators {
+                    router
+                        .bank
+                        .init_balance(storage, &Addr::unchecked(creator), coins(200, "uosmo"))
+                        .unwrap();
+                }
+            });
+        assert_eq!(app.denom_count().unwrap(), 0);
+
+        for i in 0..300 {
+            let creator = Addr::unchecked(creators[i % 3]);
+            create_denom(&mut app, &creator, &format!("denom{}", i));
+        }
+        assert_eq!(app.denom_count().unwrap(), 300);
+        for creator in creators {
+            assert_eq!(app.denom_count_for(creator).unwrap(), 100);
+        }
+        assert_eq!(app.denom_count_for("dave").unwrap(), 0);
+
+        // failed creations are not counted, whether rejected before charging the fee...
+        let create = |subdenom: &str| TokenMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+            metadata: None,
+        };
+        app.execute(Addr::unchecked("alice"), create("denom0").into())
+            .unwrap_err();
+        // ...or by the bank
+        app.execute(Addr::unchecked("dave"), create("fundz").into())
+            .unwrap_err();
+        assert_eq!(app.denom_count().unwrap(), 300);
+        assert_eq!(app.denom_count_for("alice").unwrap(), 100);
+        assert_eq!(app.denom_count_for("dave").unwrap(), 0);
+    }
+
+    #[test]
+    fn mint_dedup_keys() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let fundz = create_denom(&mut app, &creator, "fundz");
+        let gems = create_denom(&mut app, &creator, "gems");
+        let mint = |denom: &str, key: &str| TokenMsg::MintTokens {
+            denom: denom.to_string(),
+            amount: Uint128::new(10),
+            mint_to_address: "rcpt".to_string(),
+            dedup_key: Some(key.to_string()),
+        };
+
+        app.execute(creator.clone(), mint(&fundz, "transfer-1").into())
+            .unwrap();
+        let err = app
+            .execute(creator.clone(), mint(&fundz, "transfer-1").into())
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::DuplicateMint {
+                key: "transfer-1".to_string()
+            })
+        );
+        assert_eq!(supply(&app, &fundz), Uint128::new(10));
+
+        // distinct keys, or the same key for another denom, succeed
+        app.execute(creator.clone(), mint(&fundz, "transfer-2").into())
+            .unwrap();
+        app.execute(creator.clone(), mint(&gems, "transfer-1").into())
+            .unwrap();
+        assert_eq!(supply(&app, &fundz), Uint128::new(20));
+        assert_eq!(supply(&app, &gems), Uint128::new(10));
+
+        // a failed mint doesn't use up its key
+        app.execute(Addr::unchecked("rcpt"), mint(&fundz, "transfer-3").into())
+            .unwrap_err();
+        app.execute(creator, mint(&fundz, "transfer-3").into())
+            .unwrap();
+    }
+
+    #[test]
+    fn creator_total_supply() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let fundz = create_denom(&mut app, &creator, "fundz");
+        let gems = create_denom(&mut app, &creator, "gems");
+        let dust = create_denom(&mut app, &creator, "dust");
+        let other = create_denom(&mut app, &Addr::unchecked("other"), "fundz");
+
+        for (denom, amount, sender) in [
+            (&fundz, 100, &creator),
+            (&gems, 7, &creator),
+            (&fundz, 50, &creator),
+            (&other, 1000, &Addr::unchecked("other")),
+        ] {
+            let mint = TokenMsg::mint_contract_tokens(
+                denom.clone(),
+                Uint128::new(amount),
+                creator.to_string(),
+            );
+            app.execute(sender.clone(), mint.into()).unwrap();
+        }
+        let burn = TokenMsg::burn_contract_tokens(fundz.clone(), Uint128::new(30), "".into());
+        app.execute(creator.clone(), burn.into()).unwrap();
+
+        let querier = app.wrap();
+        let res = TokenQuerier::new(&querier)
+            .creator_total_supply(creator.to_string())
+            .unwrap();
+        assert_eq!(
+            res.supply,
+            vec![coin(120, fundz), coin(7, gems), coin(0, dust)]
+        );
+
+        let res = TokenQuerier::new(&querier)
+            .creator_total_supply("nobody".to_string())
+            .unwrap();
+        assert_eq!(res.supply, vec![]);
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let fundz = create_denom(&mut app, &creator, "fundz");
+        let mint = TokenMsg::mint_contract_tokens(fundz.clone(), Uint128::new(100), "alice".into());
+        app.execute(creator.clone(), mint.into()).unwrap();
+
+        let snapshot = app.snapshot();
+        let state = app.dump_state().unwrap();
+        let block = app.block_info();
+
+        // path A: more denoms, supply and a handover
+        create_denom(&mut app, &creator, "gems");
+        let mint = TokenMsg::mint_contract_tokens(fundz.clone(), Uint128::new(50), "bob".into());
+        app.execute(creator.clone(), mint.into()).unwrap();
+        let change_admin = TokenMsg::ChangeAdmin {
+            denom: fundz.clone(),
+            new_admin_address: "bob".to_string(),
+        };
+        app.execute(creator.clone(), change_admin.into()).unwrap();
+        app.advance_blocks(10);
+        assert_ne!(app.dump_state().unwrap(), state);
+
+        app.restore(&snapshot);
+        assert_eq!(app.dump_state().unwrap(), state);
+        assert_eq!(app.block_info(), block);
+        let balance = app.wrap().query_balance("bob", &fundz).unwrap();
+        assert_eq!(balance.amount, Uint128::zero());
+        let balance = app.wrap().query_balance("alice", &fundz).unwrap();
+        assert_eq!(balance.amount, Uint128::new(100));
+
+        // path B, from the same point: the creator is still admin
+        let burn = TokenMsg::burn_contract_tokens(fundz.clone(), Uint128::new(1), "".into());
+        app.execute(creator.clone(), burn.into()).unwrap_err();
+        let mint = TokenMsg::mint_contract_tokens(fundz.clone(), Uint128::new(1), "carol".into());
+        app.execute(creator, mint.into()).unwrap();
+        assert_eq!(supply(&app, &fundz), Uint128::new(101));
+        assert_eq!(app.snapshot(), app.snapshot());
+    }
+
+    #[test]
+    fn admin_of_native_denom() {
+        let app = TokenFactoryApp::new();
+        let query_admin = |denom: &str| {
+            app.wrap()
+                .query::<AdminResponse>(
+                    &TokenQuery::Admin {
+                        denom: denom.to_string(),
+                    }
+                    .into(),
+                )
+                .unwrap_err()
+                .to_string()
+        };
+
+        let err = query_admin("uosmo");
+        let expected = ContractError::NotFactoryDenom {
+            denom: "uosmo".to_string(),
+        };
+        assert!(err.contains(&expected.to_string()), "{}", err);
+
+        // a factory denom that was never created is a different error
+        let err = query_admin("factory/creator/fundz");
+        assert!(
+            err.contains(&ContractError::TokenDoesntExist.to_string()),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn subdenom_rules() {
+        let creator = Addr::unchecked("creator");
+        let create = |subdenom: &str| TokenMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+            metadata: None,
+        };
+
+        let mut app = TokenFactoryApp::new();
+        let querier = app.wrap();
+        let rules = TokenQuerier::new(&querier).subdenom_rules().unwrap();
+        assert_eq!(
+            rules,
+            SubdenomRulesResponse {
+                allowed_chars: DEFAULT_SUBDENOM_CHARS.to_string(),
+                max_len: 44,
+            }
+        );
+        // same as the bindings' validation
+        let derived = derive_subdenom("My Cool Token!");
+        assert!(rules.allows(&derived));
+        assert!(!rules.allows("fun-dz"));
+        assert!(!rules.allows(&"x".repeat(45)));
+        app.execute(creator.clone(), create(&derived).into())
+            .unwrap();
+        app.execute(creator.clone(), create("fun-dz").into())
+            .unwrap_err();
+
+        let mut app = TokenFactoryApp::builder()
+            .with_subdenom_rules("abcdefghijklmnopqrstuvwxyz-", 8)
+            .build();
+        let querier = app.wrap();
+        let rules = TokenQuerier::new(&querier).subdenom_rules().unwrap();
+        assert_eq!(rules.allowed_chars, "abcdefghijklmnopqrstuvwxyz-");
+        assert_eq!(rules.max_len, 8);
+        app.execute(creator.clone(), create("fun-dz").into())
+            .unwrap();
+        let err = app
+            .execute(creator.clone(), create("fundz.2").into())
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::InvalidFullDenom {
+                full_denom: "factory/creator/fundz.2".to_string()
+            })
+        );
+        app.execute(creator, create("ninechars").into())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn admin_history() {
+        let creator = Addr::unchecked("creator");
+        let dao = Addr::unchecked("dao");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let created_at = app.block_info().height;
+
+        app.advance_blocks(3);
+        let change_admin = TokenMsg::ChangeAdmin {
+            denom: denom.clone(),
+            new_admin_address: dao.to_string(),
+        };
+        app.execute(creator.clone(), change_admin.into()).unwrap();
+
+        app.advance_blocks(2);
+        let renounce = TokenMsg::ChangeAdmin {
+            denom: denom.clone(),
+            new_admin_address: "".to_string(),
+        };
+        app.execute(dao.clone(), renounce.into()).unwrap();
+
+        assert_eq!(
+            app.admin_history(&denom).unwrap(),
+            vec![
+                AdminChange {
+                    sender: creator.clone(),
+                    new_admin: Some(creator),
+                    height: created_at,
+                },
+                AdminChange {
+                    sender: Addr::unchecked("creator"),
+                    new_admin: Some(dao.clone()),
+                    height: created_at + 3,
+                },
+                AdminChange {
+                    sender: dao,
+                    new_admin: None,
+                    height: created_at + 5,
+                },
+            ]
+        );
+        assert_eq!(app.admin_history("factory/creator/other").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn metadata_history() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::builder().with_metadata_history().build();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let history_query = TokenQuery::MetadataHistory {
+            denom: denom.clone(),
+        };
+
+        let res: MetadataHistoryResponse = app.wrap().query(&history_query.clone().into()).unwrap();
+        assert_eq!(res.history, vec![]);
+
+        let first = Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz");
+        let set_metadata = TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: first.clone(),
+        };
+        app.execute(creator.clone(), set_metadata.into()).unwrap();
+        let first_height = app.block_info().height;
+
+        app.advance_blocks(4);
+        let second = Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz v2");
+        let set_metadata = TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: second.clone(),
+        };
+        app.execute(creator, set_metadata.into()).unwrap();
+
+        let res: MetadataHistoryResponse = app.wrap().query(&history_query.into()).unwrap();
+        assert_eq!(
+            res.history,
+            vec![(first_height, first), (first_height + 4, second)]
+        );
+    }
+
+    #[test]
+    fn metadata_history_disabled() {
+        let app = TokenFactoryApp::new();
+        let history_query = TokenQuery::MetadataHistory {
+            denom: "factory/creator/fundz".to_string(),
+        };
+        let err = app
+            .wrap()
+            .query::<MetadataHistoryResponse>(&history_query.into())
+            .unwrap_err();
+        assert!(err.to_string().contains("Metadata history is not recorded"));
+    }
+
+    #[test]
+    fn module_in_plain_app() {
+        let creator = Addr::unchecked("creator");
+        let module = TokenFactoryModule::builder()
+            .with_creation_fee(coins(100, "uosmo"))
+            .with_fee_policy(Box::new(SendToCollector::new("community_pool")))
+            .with_subdenom_rules("abcdefghijklmnopqrstuvwxyz", 8);
+        let mut app = BasicAppBuilder::<TokenFactoryMsg, TokenFactoryQuery>::new_custom()
+            .with_custom(module)
+            .build(|router, _, storage| {
+                router.custom.init(storage).unwrap();
+                router
+                    .bank
+                    .init_balance(storage, &creator, coins(100, "uosmo"))
+                    .unwrap();
+            });
+
+        let params: ParamsResponse = app.wrap().query(&TokenQuery::Params {}.into()).unwrap();
+        assert_eq!(params.params.denom_creation_fee, coins(100, "uosmo"));
+
+        // the subdenom rules apply
+        let create = |subdenom: &str| TokenMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+            metadata: None,
+        };
+        app.execute(creator.clone(), create("fundz2").into())
+            .unwrap_err();
+
+        // and the fee goes to the collector
+        app.execute(creator.clone(), create("fundz").into())
+            .unwrap();
+        let balance = app.wrap().query_balance("community_pool", "uosmo").unwrap();
+        assert_eq!(balance.amount, Uint128::new(100));
+        assert_eq!(
+            app.wrap().query_all_balances(creator.as_str()).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn max_mint_per_tx() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::builder()
+            .with_max_mint_per_tx(Uint128::new(1000))
+            .build();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let mint = |amount: u128| TokenMsg::MintTokens {
+            denom: denom.clone(),
+            amount: Uint128::new(amount),
+            mint_to_address: "rcpt".to_string(),
+            dedup_key: None,
+        };
+
+        // at the limit
+        app.execute(creator.clone(), mint(1000).into()).unwrap();
+
+        // above the limit
+        let err = app.execute(creator.clone(), mint(1001).into()).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::MintLimitExceeded {
+                limit: Uint128::new(1000),
+                requested: Uint128::new(1001),
+            })
+        );
+        let res: CanMintResponse = app
+            .wrap()
+            .query(
+                &TokenQuery::CanMint {
+                    denom: denom.clone(),
+                    minter: creator.to_string(),
+                    amount: Uint128::new(1001),
+                }
+                .into(),
+            )
+            .unwrap();
+        assert!(!res.allowed);
+
+        // the limit is per mint, not per denom
+        app.execute(creator, mint(1000).into()).unwrap();
+        let balance = app.wrap().query_balance("rcpt", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(2000));
+    }
+
+    #[test]
+    fn features() {
+        let creator = Addr::unchecked("creator");
+        let app = TokenFactoryApp::new();
+        let querier = app.wrap();
+        let features = TokenQuerier::new(&querier).features();
+        assert_eq!(features.version, FEATURES_VERSION);
+        assert!(features.force_transfer && features.before_send_hook && features.set_metadata);
+        assert!(!features.burn_from);
+
+        // a chain without force transfer
+        let without_force_transfer = TokenFactoryFeatures {
+            force_transfer: false,
+            ..features
+        };
+        let mut app = TokenFactoryApp::builder()
+            .with_features(without_force_transfer.clone())
+            .build();
+        let querier = app.wrap();
+        assert_eq!(
+            TokenQuerier::new(&querier).features(),
+            without_force_transfer
+        );
+
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let force_transfer = TokenMsg::ForceTransfer {
+            denom: denom.clone(),
+            amount: Uint128::new(1),
+            from_address: "holder".to_string(),
+            to_address: "rcpt".to_string(),
+        };
+        let err = app
+            .execute(creator.clone(), force_transfer.into())
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::FeatureUnsupported {
+                features: "token_factory/v2:set_metadata,before_send_hook,lock_metadata"
+                    .to_string(),
+            })
+        );
+
+        // the others still work
+        let set_metadata = TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz"),
+        };
+        app.execute(creator, set_metadata.into()).unwrap();
+    }
+
+    #[test]
+    fn garbage_collect_empty_denoms() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::builder().with_gc_empty_denoms().build();
+        let burned = create_denom(&mut app, &creator, "burned");
+        let kept = create_denom(&mut app, &creator, "kept");
+        for denom in [&burned, &kept] {
+            let mint = TokenMsg::MintTokens {
+                denom: denom.clone(),
+                amount: Uint128::new(100),
+                mint_to_address: creator.to_string(),
+                dedup_key: None,
+            };
+            app.execute(creator.clone(), mint.into()).unwrap();
+        }
+        let burn = TokenMsg::BurnTokens {
+            denom: burned.clone(),
+            amount: Uint128::new(100),
+            burn_from_address: "".to_string(),
+        };
+        app.execute(creator.clone(), burn.into()).unwrap();
+        assert_eq!(app.dump_state().unwrap().denoms.len(), 2);
+
+        // only the admin may request it
+        let gc = |denom: &str| TokenFactorySudo::GarbageCollect {
+            denom: denom.to_string(),
+            admin: creator.clone(),
+        };
+        let not_admin = TokenFactorySudo::GarbageCollect {
+            denom: burned.clone(),
+            admin: Addr::unchecked("intruder"),
+        };
+        let err = app.sudo_token_factory(not_admin).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+        );
+
+        // only empty denoms are collected
+        let err = app.sudo_token_factory(gc(&kept)).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::DenomNotEmpty {
+                supply: Uint128::new(100)
+            })
+        );
+        app.sudo_token_factory(gc(&burned)).unwrap();
+        let state = app.dump_state().unwrap();
+        assert_eq!(state.denoms.len(), 1);
+        assert_eq!(state.denoms[0].denom, kept);
+        // the counters still include it, as they count every denom ever created
+        assert_eq!(app.denom_count().unwrap(), 2);
+
+        // once collected, it is gone, but can't be created again
+        let err = app.sudo_token_factory(gc(&burned)).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::TokenDoesntExist)
+        );
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(querier.metadata(&burned).unwrap().metadata, None);
+        // the admin is the tombstone
+        assert_eq!(querier.admin(&burned).unwrap().admin, creator.as_str());
+        let create = TokenMsg::CreateDenom {
+            subdenom: "burned".to_string(),
+            metadata: None,
+        };
+        let err = app.execute(creator.clone(), create.into()).unwrap_err();
+        assert!(matches!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::TokenExists)
+        ));
+
+        // disabled by default
+        let mut app = TokenFactoryApp::new();
+        let err = app.sudo_token_factory(gc(&burned)).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::GarbageCollectionDisabled)
+        );
+    }
+
+    #[test]
+    fn creator_format() {
+        let account = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks";
+        let contract = "osmo1z0qrq605sjgcqpylfl4aa6s90x738j7m58wyatt0tdzflg2ha26q67k743";
+        let other_chain = "cosmos1jv65s3grqf6v6jl3dp4t6c9t9rk99cd88lyufl";
+        let create = |app: &mut TokenFactoryApp, creator: &str| {
+            let create = TokenMsg::CreateDenom {
+                subdenom: "fundz".to_string(),
+                metadata: None,
+            };
+            app.execute(Addr::unchecked(creator), create.into())
+                .map_err(unwrap_token_factory_err)
+        };
+
+        // lenient by default
+        let mut app = TokenFactoryApp::new();
+        create(&mut app, other_chain).unwrap();
+        create(&mut app, "creator").unwrap();
+
+        let mut app = TokenFactoryApp::builder()
+            .with_creator_format("osmo", None)
+            .build();
+        create(&mut app, account).unwrap();
+        create(&mut app, contract).unwrap();
+        for creator in [other_chain, "creator"] {
+            let err = create(&mut app, creator).unwrap_err();
+            assert!(
+                matches!(
+                    &err,
+                    TokenFactoryModuleError::Module(ContractError::InvalidCreatorAddress { creator: c, .. })
+                        if c == creator
+                ),
+                "{:?}",
+                err
+            );
+        }
+
+        // only accounts
+        let mut app = TokenFactoryApp::builder()
+            .with_creator_format("osmo", Some(20))
+            .build();
+        create(&mut app, account).unwrap();
+        assert_eq!(
+            create(&mut app, contract).unwrap_err(),
+            TokenFactoryModuleError::Module(ContractError::InvalidCreatorAddress {
+                creator: contract.to_string(),
+                reason: "creator must be a 20 byte address".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn has_metadata() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let with = create_denom(&mut app, &creator, "fundz");
+        let without = create_denom(&mut app, &creator, "gems");
+        let set_metadata = TokenMsg::SetMetadata {
+            denom: with.clone(),
+            metadata: Metadata::two_unit(&with, "fundz", 6, "FUNDZ", "Fundz"),
+        };
+        app.execute(creator, set_metadata.into()).unwrap();
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert!(querier.has_metadata(&with).unwrap());
+        assert!(!querier.has_metadata(&without).unwrap());
+        assert!(!querier.has_metadata("uosmo").unwrap());
+    }
+
+    #[test]
+    fn admin_change_callback() {
+        use std::rc::Rc;
+
+        let creator = Addr::unchecked("creator");
+        let dao = Addr::unchecked("dao");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+
+        let seen = Rc::new(RefCell::new(vec![]));
+        let record = seen.clone();
+        app.on_admin_change(move |denom, old, new| {
+            record
+                .borrow_mut()
+                .push((denom.to_string(), old.clone(), new.cloned()))
+        });
+
+        let change_admin = TokenMsg::ChangeAdmin {
+            denom: denom.clone(),
+            new_admin_address: dao.to_string(),
+        };
+        app.execute(creator.clone(), change_admin.clone().into())
+            .unwrap();
+        // not the admin anymore, nothing changes
+        app.execute(creator.clone(), change_admin.into())
+            .unwrap_err();
+        let renounce = TokenMsg::ChangeAdmin {
+            denom: denom.clone(),
+            new_admin_address: "".to_string(),
+        };
+        app.execute(dao.clone(), renounce.into()).unwrap();
+
+        assert_eq!(
+            *seen.borrow(),
+            vec![
+                (denom.clone(), creator, Some(dao.clone())),
+                (denom, dao, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn every_event_has_sender_and_denom() {
+        // no wildcard, so a new message fails to compile until it is covered here
+        fn expected_event(msg: &TokenMsg) -> &'static str {
+            match msg {
+                TokenMsg::CreateDenom { .. } => EVENT_CREATE_DENOM,
+                TokenMsg::ChangeAdmin { .. } => EVENT_CHANGE_ADMIN,
+                TokenMsg::MintTokens { .. } => EVENT_MINT,
+                TokenMsg::BurnTokens { .. } => EVENT_BURN,
+                TokenMsg::SetMetadata { .. } => EVENT_SET_DENOM_METADATA,
+                TokenMsg::LockMetadata { .. } => EVENT_LOCK_DENOM_METADATA,
+                TokenMsg::ForceTransfer { .. } => EVENT_FORCE_TRANSFER,
+                TokenMsg::SetBeforeSendHook { .. } => EVENT_SET_BEFORE_SEND_HOOK,
+            }
+        }
+
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let denom = format!("factory/{}/fundz", creator);
+        let msgs = vec![
+            TokenMsg::CreateDenom {
+                subdenom: "fundz".to_string(),
+                metadata: None,
+            },
+            TokenMsg::MintTokens {
+                denom: denom.clone(),
+                amount: Uint128::new(100),
+                mint_to_address: creator.to_string(),
+                dedup_key: None,
+            },
+            TokenMsg::BurnTokens {
+                denom: denom.clone(),
+                amount: Uint128::new(10),
+                burn_from_address: "".to_string(),
+            },
+            TokenMsg::SetMetadata {
+                denom: denom.clone(),
+                metadata: Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz"),
+            },
+            TokenMsg::LockMetadata {
+                denom: denom.clone(),
+            },
+            TokenMsg::ForceTransfer {
+                denom: denom.clone(),
+                amount: Uint128::new(10),
+                from_address: creator.to_string(),
+                to_address: "alice".to_string(),
+            },
+            TokenMsg::SetBeforeSendHook {
+                denom: denom.clone(),
+                cosmwasm_address: "".to_string(),
+            },
+            TokenMsg::ChangeAdmin {
+                denom: denom.clone(),
+                new_admin_address: "dao".to_string(),
+            },
+        ];
+
+        for msg in msgs {
+            let ty = expected_event(&msg);
+            let res = app.execute(creator.clone(), msg.clone().into()).unwrap();
+            let event = res
+                .events
+                .iter()
+                .find(|e| e.ty == ty)
+                .unwrap_or_else(|| panic!("no {} event for {:?}", ty, msg));
+            let attribute = |key: &str| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|a| a.key == key)
+                    .map(|a| a.value.as_str())
+            };
+            assert_eq!(attribute(ATTR_SENDER), Some(creator.as_str()), "{:?}", msg);
+            assert_eq!(attribute(ATTR_DENOM), Some(denom.as_str()), "{:?}", msg);
+        }
+    }
+
+    #[test]
+    fn admin_is() {
+        let creator = Addr::unchecked("creator");
+        let dao = Addr::unchecked("dao");
+        let mut app = TokenFactoryApp::new();
+        let handed_over = create_denom(&mut app, &creator, "fundz");
+        let renounced = create_denom(&mut app, &creator, "gems");
+        let change_admin = |denom: &str, new_admin: &str| TokenMsg::ChangeAdmin {
+            denom: denom.to_string(),
+            new_admin_address: new_admin.to_string(),
+        };
+        app.execute(
+            creator.clone(),
+            change_admin(&handed_over, dao.as_str()).into(),
+        )
+        .unwrap();
+        app.execute(creator.clone(), change_admin(&renounced, "").into())
+            .unwrap();
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        // the admin
+        assert!(querier.admin_is(&handed_over, &dao).unwrap());
+        // another admin
+        assert!(!querier.admin_is(&handed_over, &creator).unwrap());
+        // no admin
+        assert!(!querier.admin_is(&renounced, &creator).unwrap());
+        assert!(!querier.admin_is(&renounced, &Addr::unchecked("")).unwrap());
+        // no such denom
+        assert!(!querier.admin_is("factory/creator/other", &creator).unwrap());
+        assert!(!querier.admin_is("uosmo", &creator).unwrap());
+    }
+
+    #[test]
+    fn set_metadata_overwrites_in_full() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+
+        let mut first = Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz");
+        first.description = Some("The first".to_string());
+        first.uri = Some("https://fundz.example/logo.png".to_string());
+        let second = Metadata::from_decimals(&denom, "fundz", 0, "FUNDZ");
+        for metadata in [&first, &second] {
+            let set_metadata = TokenMsg::SetMetadata {
+                denom: denom.clone(),
+                metadata: metadata.clone(),
+            };
+            app.execute(creator.clone(), set_metadata.into()).unwrap();
+        }
+
+        // nothing of the first is merged in
+        let querier = app.wrap();
+        let res = TokenQuerier::new(&querier).metadata(&denom).unwrap();
+        assert_eq!(res.metadata, Some(second));
+    }
+
+    #[test]
+    fn invalid_metadata_is_rejected() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let mut metadata = Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz");
+        metadata.denom_units[1].exponent = 0;
+        let rejected = TokenFactoryModuleError::Module(ContractError::InvalidMetadata(
+            MetadataError::NonMonotonicExponents {
+                denom: "fundz".to_string(),
+            },
+        ));
+
+        let set_metadata = TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: metadata.clone(),
+        };
+        let err = app
+            .execute(creator.clone(), set_metadata.into())
+            .unwrap_err();
+        assert_eq!(unwrap_token_factory_err(err), rejected);
+
+        // nor is the denom created with it
+        let gems = format!("{}/{}/gems", DENOM_PREFIX, creator);
+        let create = TokenMsg::CreateDenom {
+            subdenom: "gems".to_string(),
+            metadata: Some(Metadata::two_unit(&gems, "gems", 0, "GEMS", "Gems")),
+        };
+        let err = app.execute(creator.clone(), create.into()).unwrap_err();
+        let rejected = TokenFactoryModuleError::Module(ContractError::InvalidMetadata(
+            MetadataError::NonMonotonicExponents {
+                denom: "gems".to_string(),
+            },
+        ));
+        assert_eq!(unwrap_token_factory_err(err), rejected);
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        assert_eq!(querier.metadata(&denom).unwrap().metadata, None);
+        assert!(querier.admin(&gems).is_err());
+    }
+
+    #[test]
+    fn clear_metadata() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let set_metadata = TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz"),
+        };
+        app.execute(creator.clone(), set_metadata.into()).unwrap();
+
+        let clear = |denom: &str| TokenFactorySudo::ClearMetadata {
+            denom: denom.to_string(),
+        };
+        app.sudo_token_factory(clear(&denom)).unwrap();
+        let querier = app.wrap();
+        let res = TokenQuerier::new(&querier).metadata(&denom).unwrap();
+        assert_eq!(res.metadata, None);
+        // clearing again is fine
+        app.sudo_token_factory(clear(&denom)).unwrap();
+
+        let err = app
+            .sudo_token_factory(clear("factory/creator/other"))
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::TokenDoesntExist)
+        );
+
+        // locked metadata stays
+        let set_metadata = TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz"),
+        };
+        app.execute(creator.clone(), set_metadata.into()).unwrap();
+        let lock = TokenMsg::LockMetadata {
+            denom: denom.clone(),
+        };
+        app.execute(creator, lock.into()).unwrap();
+        let err = app.sudo_token_factory(clear(&denom)).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::MetadataLocked {
+                denom: denom.clone()
+            })
+        );
+        let querier = app.wrap();
+        let res = TokenQuerier::new(&querier).metadata(&denom).unwrap();
+        assert!(res.metadata.is_some());
+    }
+
+    #[test]
+    fn denom_creator() {
+        let creator = Addr::unchecked("creator");
+        let dao = Addr::unchecked("dao");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let change_admin = TokenMsg::ChangeAdmin {
+            denom: denom.clone(),
+            new_admin_address: dao.to_string(),
+        };
+        app.execute(creator.clone(), change_admin.into()).unwrap();
+
+        let querier = app.wrap();
+        let querier = TokenQuerier::new(&querier);
+        // the admin changed, the creator didn't
+        assert_eq!(querier.admin(&denom).unwrap().admin, dao.as_str());
+        assert_eq!(
+            querier.denom_creator(&denom).unwrap(),
+            Some(creator.to_string())
+        );
+        // not created by the token factory
+        assert_eq!(querier.denom_creator("uosmo").unwrap(), None);
+        assert_eq!(
+            querier.denom_creator("factory/creator/other").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn lock_metadata() {
+        let creator = Addr::unchecked("creator");
+        let mut app = TokenFactoryApp::new();
+        let denom = create_denom(&mut app, &creator, "fundz");
+        let metadata = Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz");
+        let set_metadata = |metadata: &Metadata| TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata: metadata.clone(),
+        };
+        let lock = TokenMsg::LockMetadata {
+            denom: denom.clone(),
+        };
+        app.execute(creator.clone(), set_metadata(&metadata).into())
+            .unwrap();
+
+        // admin only
+        let err = app
+            .execute(Addr::unchecked("alice"), lock.clone().into())
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+        );
+        let locked = |app: &TokenFactoryApp| {
+            let querier = app.wrap();
+            let res = TokenQuerier::new(&querier).metadata_locked(&denom);
+            res.unwrap()
+        };
+        assert!(!locked(&app));
+
+        app.execute(creator.clone(), lock.clone().into()).unwrap();
+        assert!(locked(&app));
+
+        // no update is accepted anymore, the metadata stays as it was
+        let mut update = metadata.clone();
+        update.description = Some("Changed".to_string());
+        let err = app
+            .execute(creator.clone(), set_metadata(&update).into())
+            .unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::MetadataLocked {
+                denom: denom.clone()
+            })
+        );
+        let querier = app.wrap();
+        let res = TokenQuerier::new(&querier).metadata(&denom).unwrap();
+        assert_eq!(res.metadata, Some(metadata));
+
+        // locking again changes nothing, and unknown denoms aren't locked
+        app.execute(creator, lock.into()).unwrap();
+        assert!(locked(&app));
+        let querier = app.wrap();
+        assert!(!TokenQuerier::new(&querier)
+            .metadata_locked("uosmo")
+            .unwrap());
+    }
+
+    #[test]
+    fn fixed_supply_token() {
+        let creator = Addr::unchecked("creator");
+        let denom = format!("factory/{}/fundz", creator);
+        let metadata = Metadata::two_unit(&denom, "fundz", 6, "FUNDZ", "Fundz");
+        let msgs = fixed_supply_token_msgs(
+            creator.as_str(),
+            "fundz",
+            metadata.clone(),
+            Uint128::new(1000),
+            "treasury",
+        )
+        .unwrap();
+        let mut app = TokenFactoryApp::new();
+        app.execute_multi(creator.clone(), msgs).unwrap();
+
+        let querier = app.wrap();
+        let tf = TokenQuerier::new(&querier);
+        assert_eq!(tf.admin(&denom).unwrap().admin, "");
+        assert_eq!(tf.metadata(&denom).unwrap().metadata, Some(metadata));
+        let balance = querier.query_balance("treasury", &denom).unwrap();
+        assert_eq!(balance.amount, Uint128::new(1000));
+
+        // nobody can mint more
+        let mint = TokenMsg::mint_contract_tokens(denom, Uint128::new(1), creator.to_string());
+        let err = app.execute(creator, mint.into()).unwrap_err();
+        assert_eq!(
+            unwrap_token_factory_err(err),
+            TokenFactoryModuleError::Module(ContractError::NotTokenAdmin)
+        );
+    }
 }
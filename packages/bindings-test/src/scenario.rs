@@ -0,0 +1,313 @@
+use anyhow::{Context, Result as AnyResult};
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{coins, Addr, Coin};
+use cw_multi_test::{BankSudo, Executor, SudoMsg};
+
+use token_bindings::{Metadata, TokenMsg, DENOM_PREFIX};
+
+use crate::TokenFactoryApp;
+
+/// A single setup step of a `Scenario`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScenarioStep {
+    /// Mints native tokens to an address, outside of the token factory
+    Fund {
+        /// The funded address
+        address: String,
+        /// The coins to add to its balance
+        amount: Vec<Coin>,
+    },
+    /// `creator` executes `TokenMsg::CreateDenom`
+    CreateDenom {
+        /// The sender, and so the creator of the denom
+        creator: String,
+        /// The subdenom to create
+        subdenom: String,
+        /// Metadata passed along the creation
+        metadata: Option<Metadata>,
+    },
+    /// `sender` executes `TokenMsg::SetMetadata`
+    SetMetadata {
+        /// The sender, which must be the admin of the denom
+        sender: String,
+        /// The full denom
+        denom: String,
+        /// The new metadata
+        metadata: Metadata,
+    },
+    /// `sender` executes `TokenMsg::ChangeAdmin`
+    ChangeAdmin {
+        /// The sender, which must be the admin of the denom
+        sender: String,
+        /// The full denom
+        denom: String,
+        /// The new admin, empty to renounce
+        new_admin: String,
+    },
+}
+
+/// A setup script captured as data, so the same fixture can be applied to any number
+/// of fresh apps, eg. to test several contract variants against it.
+///
+/// ```
+/// use cosmwasm_std::coins;
+/// use token_bindings_test::{Scenario, TokenFactoryApp};
+///
+/// let scenario = Scenario::new()
+///     .fund("alice", coins(100, "uosmo"))
+///     .create_denom("alice", "fundz", None);
+/// let mut app = TokenFactoryApp::new();
+/// scenario.apply(&mut app).unwrap();
+/// assert_eq!(app.dump_state().unwrap().denoms[0].denom, "factory/alice/fundz");
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Scenario {
+    /// The steps, applied in order
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// The users funded by the canned scenarios
+pub const SCENARIO_USERS: [&str; 3] = ["alice", "bob", "carol"];
+
+/// The native denom the canned scenarios fund users with
+pub const SCENARIO_FUNDING_DENOM: &str = "uosmo";
+
+impl Scenario {
+    /// An empty scenario
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Canned scenario: each of `SCENARIO_USERS` holds 1_000_000_000 `SCENARIO_FUNDING_DENOM`,
+    /// enough to pay any reasonable creation fee
+    pub fn funded_users() -> Self {
+        SCENARIO_USERS.iter().fold(Self::new(), |scenario, user| {
+            scenario.fund(user, coins(1_000_000_000, SCENARIO_FUNDING_DENOM))
+        })
+    }
+
+    /// Canned scenario: after `funded_users`, alice creates `factory/alice/fundz` and
+    /// bob creates `factory/bob/gems`, both with metadata, and alice hands `fundz` over to carol
+    pub fn two_denoms_with_handover() -> Self {
+        let fundz = Self::denom("alice", "fundz");
+        Self::funded_users()
+            .create_denom(
+                "alice",
+                "fundz",
+                Some(Metadata::two_unit(&fundz, "fundz", 6, "FUNDZ", "Fundz")),
+            )
+            .create_denom(
+                "bob",
+                "gems",
+                Some(Metadata {
+                    name: Some("Gems".to_string()),
+                    ..Metadata::from_decimals(&Self::denom("bob", "gems"), "gems", 0, "GEMS")
+                }),
+            )
+            .change_admin("alice", &fundz, "carol")
+    }
+
+    /// The full denom `creator` gets for `subdenom`
+    pub fn denom(creator: &str, subdenom: &str) -> String {
+        format!("{}/{}/{}", DENOM_PREFIX, creator, subdenom)
+    }
+
+    /// Appends a step
+    pub fn step(mut self, step: ScenarioStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Appends a `ScenarioStep::Fund`
+    pub fn fund(self, address: &str, amount: Vec<Coin>) -> Self {
+        self.step(ScenarioStep::Fund {
+            address: address.to_string(),
+            amount,
+        })
+    }
+
+    /// Appends a `ScenarioStep::CreateDenom`
+    pub fn create_denom(self, creator: &str, subdenom: &str, metadata: Option<Metadata>) -> Self {
+        self.step(ScenarioStep::CreateDenom {
+            creator: creator.to_string(),
+            subdenom: subdenom.to_string(),
+            metadata,
+        })
+    }
+
+    /// Appends a `ScenarioStep::SetMetadata`
+    pub fn set_metadata(self, sender: &str, denom: &str, metadata: Metadata) -> Self {
+        self.step(ScenarioStep::SetMetadata {
+            sender: sender.to_string(),
+            denom: denom.to_string(),
+            metadata,
+        })
+    }
+
+    /// Appends a `ScenarioStep::ChangeAdmin`
+    pub fn change_admin(self, sender: &str, denom: &str, new_admin: &str) -> Self {
+        self.step(ScenarioStep::ChangeAdmin {
+            sender: sender.to_string(),
+            denom: denom.to_string(),
+            new_admin: new_admin.to_string(),
+        })
+    }
+
+    /// Applies all steps to `app` in order, stopping at the first failing one.
+    /// The error says which step failed.
+    pub fn apply(&self, app: &mut TokenFactoryApp) -> AnyResult<()> {
+        for (i, step) in self.steps.iter().enumerate() {
+            apply_step(app, step.clone()).with_context(|| format!("scenario step {}", i))?;
+        }
+        Ok(())
+    }
+}
+
+fn apply_step(app: &mut TokenFactoryApp, step: ScenarioStep) -> AnyResult<()> {
+    match step {
+        ScenarioStep::Fund { address, amount } => {
+            let mint = BankSudo::Mint {
+                to_address: address,
+                amount,
+            };
+            app.sudo(SudoMsg::Bank(mint))?;
+        }
+        ScenarioStep::CreateDenom {
+            creator,
+            subdenom,
+            metadata,
+        } => {
+            let create = TokenMsg::CreateDenom { subdenom, metadata };
+            app.execute(Addr::unchecked(creator), create.into())?;
+        }
+        ScenarioStep::SetMetadata {
+            sender,
+            denom,
+            metadata,
+        } => {
+            let set_metadata = TokenMsg::SetMetadata { denom, metadata };
+            app.execute(Addr::unchecked(sender), set_metadata.into())?;
+        }
+        ScenarioStep::ChangeAdmin {
+            sender,
+            denom,
+            new_admin,
+        } => {
+            let change_admin = TokenMsg::ChangeAdmin {
+                denom,
+                new_admin_address: new_admin,
+            };
+            app.execute(Addr::unchecked(sender), change_admin.into())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{coin, from_slice, to_vec, Uint128};
+
+    fn balances(app: &TokenFactoryApp) -> Vec<Vec<Coin>> {
+        SCENARIO_USERS
+            .iter()
+            .map(|user| app.wrap().query_all_balances(*user).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn same_scenario_same_state() {
+        let scenario = Scenario::two_denoms_with_handover();
+        let mut app1 = TokenFactoryApp::builder()
+            .with_creation_fee(vec![coin(100, SCENARIO_FUNDING_DENOM)])
+            .build();
+        let mut app2 = TokenFactoryApp::builder()
+            .with_creation_fee(vec![coin(100, SCENARIO_FUNDING_DENOM)])
+            .build();
+        scenario.apply(&mut app1).unwrap();
+        scenario.apply(&mut app2).unwrap();
+
+        let dump = app1.dump_state().unwrap();
+        assert_eq!(dump, app2.dump_state().unwrap());
+        assert_eq!(balances(&app1), balances(&app2));
+
+        let denoms: Vec<_> = dump.denoms.iter().map(|d| d.denom.as_str()).collect();
+        assert_eq!(denoms, vec!["factory/alice/fundz", "factory/bob/gems"]);
+        assert_eq!(dump.denoms[0].admin.as_deref(), Some("carol"));
+        assert_eq!(dump.denoms[1].admin.as_deref(), Some("bob"));
+        assert!(dump.denoms.iter().all(|d| d.metadata.is_some()));
+        // alice and bob paid the creation fee
+        assert_eq!(
+            balances(&app1)[0],
+            coins(1_000_000_000 - 100, SCENARIO_FUNDING_DENOM)
+        );
+    }
+
+    #[test]
+    fn failing_step_is_reported() {
+        let fundz = Scenario::denom("alice", "fundz");
+        // carol is not the admin
+        let scenario = Scenario::funded_users()
+            .create_denom("alice", "fundz", None)
+            .set_metadata(
+                "carol",
+                &fundz,
+                Metadata::two_unit(&fundz, "fundz", 6, "FUNDZ", "Fundz"),
+            );
+        let mut app = TokenFactoryApp::new();
+        let err = scenario.apply(&mut app).unwrap_err();
+        assert_eq!(err.to_string(), "scenario step 4");
+
+        // the steps before it were applied
+        let dump = app.dump_state().unwrap();
+        assert_eq!(dump.denoms.len(), 1);
+        assert_eq!(dump.denoms[0].metadata, None);
+    }
+
+    #[test]
+    fn scenario_as_data() {
+        let scenario = Scenario::two_denoms_with_handover();
+        let json = to_vec(&scenario).unwrap();
+        let parsed: Scenario = from_slice(&json).unwrap();
+        assert_eq!(parsed, scenario);
+    }
+
+    #[test]
+    fn golden_dump() {
+        let mut app = TokenFactoryApp::new();
+        Scenario::two_denoms_with_handover()
+            .apply(&mut app)
+            .unwrap();
+        let mint = TokenMsg::MintTokens {
+            denom: Scenario::denom("bob", "gems"),
+            amount: Uint128::new(250),
+            mint_to_address: "alice".to_string(),
+            dedup_key: None,
+        };
+        app.execute(Addr::unchecked("bob"), mint.into()).unwrap();
+        let lock = TokenMsg::LockMetadata {
+            denom: Scenario::denom("bob", "gems"),
+        };
+        app.execute(Addr::unchecked("bob"), lock.into()).unwrap();
+
+        // the serialized form is part of the contract, golden files of users depend on it
+        let dump = String::from_utf8(to_vec(&app.dump_state().unwrap()).unwrap()).unwrap();
+        let expected = concat!(
+            r#"{"denoms":["#,
+            r#"{"denom":"factory/alice/fundz","admin":"carol","metadata":{"description":null,"#,
+            r#""denom_units":[{"denom":"factory/alice/fundz","exponent":0,"aliases":[]},"#,
+            r#"{"denom":"fundz","exponent":6,"aliases":[]}],"base":"factory/alice/fundz","#,
+            r#""display":"fundz","name":"Fundz","symbol":"FUNDZ","uri":null,"uri_hash":null},"#,
+            r#""locked":false,"supply":"0"},"#,
+            r#"{"denom":"factory/bob/gems","admin":"bob","metadata":{"description":null,"#,
+            r#""denom_units":[{"denom":"factory/bob/gems","exponent":0,"aliases":["gems"]}],"#,
+            r#""base":"factory/bob/gems","display":"factory/bob/gems","name":"Gems","#,
+            r#""symbol":"GEMS","uri":null,"uri_hash":null},"#,
+            r#""locked":true,"supply":"250"}"#,
+            r#"]}"#,
+        );
+        assert_eq!(dump, expected);
+    }
+}
@@ -0,0 +1,157 @@
+//! A common pattern: an issuer contract creates a denom and hands the admin
+//! rights over to a minter contract, which mints from then on.
+
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError, StdResult,
+    SubMsg, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use token_bindings::{CreateDenomResponse, TokenFactoryMsg, TokenFactoryQuery, TokenMsg};
+use token_bindings_test::TokenFactoryApp;
+
+const CREATE_DENOM_REPLY_ID: u64 = 1;
+
+// the issuer remembers who gets the admin rights once the denom exists
+const MINTER: Item<String> = Item::new("minter");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum IssuerExecuteMsg {
+    Create { subdenom: String, minter: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum MinterExecuteMsg {
+    Mint {
+        denom: String,
+        amount: Uint128,
+        recipient: String,
+    },
+}
+
+fn instantiate(
+    _deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    Ok(Response::new())
+}
+
+fn query(_deps: Deps<TokenFactoryQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("no queries"))
+}
+
+fn issuer_execute(
+    deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: IssuerExecuteMsg,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let IssuerExecuteMsg::Create { subdenom, minter } = msg;
+    MINTER.save(deps.storage, &minter)?;
+    let create = TokenMsg::CreateDenom {
+        subdenom,
+        metadata: None,
+    };
+    Ok(Response::new().add_submessage(SubMsg::reply_on_success(create, CREATE_DENOM_REPLY_ID)))
+}
+
+fn issuer_reply(
+    deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    reply: Reply,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let data = reply
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .ok_or_else(|| StdError::generic_err("no data in CreateDenom reply"))?;
+    let CreateDenomResponse { new_token_denom } = CreateDenomResponse::from_reply_data(data)?;
+
+    let change_admin = TokenMsg::ChangeAdmin {
+        denom: new_token_denom.clone(),
+        new_admin_address: MINTER.load(deps.storage)?,
+    };
+    Ok(Response::new()
+        .add_attribute("denom", new_token_denom)
+        .add_message(change_admin))
+}
+
+fn minter_execute(
+    _deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MinterExecuteMsg,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let MinterExecuteMsg::Mint {
+        denom,
+        amount,
+        recipient,
+    } = msg;
+    Ok(Response::new().add_message(TokenMsg::mint_contract_tokens(denom, amount, recipient)))
+}
+
+fn issuer_contract() -> Box<dyn Contract<TokenFactoryMsg, TokenFactoryQuery>> {
+    let contract =
+        ContractWrapper::new(issuer_execute, instantiate, query).with_reply(issuer_reply);
+    Box::new(contract)
+}
+
+fn minter_contract() -> Box<dyn Contract<TokenFactoryMsg, TokenFactoryQuery>> {
+    Box::new(ContractWrapper::new(minter_execute, instantiate, query))
+}
+
+#[test]
+fn create_change_admin_and_mint_from_another_contract() {
+    let owner = Addr::unchecked("owner");
+    let rcpt = Addr::unchecked("townies");
+    let mut app = TokenFactoryApp::new();
+
+    let issuer_id = app.store_code(issuer_contract());
+    let minter_id = app.store_code(minter_contract());
+    let issuer = app
+        .instantiate_contract(issuer_id, owner.clone(), &Empty {}, &[], "issuer", None)
+        .unwrap();
+    let minter = app
+        .instantiate_contract(minter_id, owner.clone(), &Empty {}, &[], "minter", None)
+        .unwrap();
+
+    // issuer creates the denom and passes admin to the minter in the reply
+    app.execute_contract(
+        owner.clone(),
+        issuer.clone(),
+        &IssuerExecuteMsg::Create {
+            subdenom: "fundz".to_string(),
+            minter: minter.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let denom = format!("factory/{}/fundz", issuer);
+
+    // the issuer may no longer mint
+    let mint = TokenMsg::mint_contract_tokens(denom.clone(), Uint128::new(500), rcpt.to_string());
+    app.execute(issuer, mint.into()).unwrap_err();
+
+    // but the minter can
+    app.execute_contract(
+        owner,
+        minter,
+        &MinterExecuteMsg::Mint {
+            denom: denom.clone(),
+            amount: Uint128::new(500),
+            recipient: rcpt.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance(rcpt.as_str(), &denom).unwrap();
+    assert_eq!(balance.amount, Uint128::new(500));
+}
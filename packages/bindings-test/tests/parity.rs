@@ -0,0 +1,90 @@
+//! Replays the scenarios of fixtures/parity.json against `TokenFactoryApp`,
+//! to keep the mock in line with the behavior of the chain module.
+//! To cover more behavior, add scenarios to the fixture, this runner shouldn't need changes.
+
+use cosmwasm_std::{from_slice, Addr, Uint128};
+use cw_multi_test::Executor;
+use serde::Deserialize;
+
+use token_bindings::{Metadata, TokenMsg, TokenQuerier};
+use token_bindings_test::TokenFactoryApp;
+
+#[derive(Deserialize)]
+struct Fixture {
+    scenarios: Vec<Scenario>,
+}
+
+#[derive(Deserialize)]
+struct Scenario {
+    name: String,
+    steps: Vec<Step>,
+    state: Vec<DenomState>,
+}
+
+#[derive(Deserialize)]
+struct Step {
+    sender: String,
+    msg: TokenMsg,
+    expect_ok: bool,
+}
+
+#[derive(Deserialize)]
+struct DenomState {
+    denom: String,
+    /// None if the denom doesn't exist
+    admin: Option<String>,
+    metadata: Option<Metadata>,
+    /// (address, amount) pairs
+    balances: Vec<(String, Uint128)>,
+}
+
+fn run(scenario: &Scenario) {
+    let name = &scenario.name;
+    let mut app = TokenFactoryApp::new();
+
+    for (i, step) in scenario.steps.iter().enumerate() {
+        let res = app.execute(Addr::unchecked(&step.sender), step.msg.clone().into());
+        assert_eq!(
+            res.is_ok(),
+            step.expect_ok,
+            "{}: step {} ({:?}) returned {:?}",
+            name,
+            i,
+            step.msg,
+            res
+        );
+    }
+
+    let querier = app.wrap();
+    let querier = TokenQuerier::new(&querier);
+    for expected in &scenario.state {
+        let denom = &expected.denom;
+        let admin = querier.admin(denom).ok().map(|res| res.admin);
+        assert_eq!(admin, expected.admin, "{}: admin of {}", name, denom);
+
+        let metadata = querier.metadata(denom).unwrap().metadata;
+        assert_eq!(
+            metadata, expected.metadata,
+            "{}: metadata of {}",
+            name, denom
+        );
+
+        for (address, amount) in &expected.balances {
+            let balance = app.wrap().query_balance(address, denom).unwrap();
+            assert_eq!(
+                balance.amount, *amount,
+                "{}: balance of {} in {}",
+                name, address, denom
+            );
+        }
+    }
+}
+
+#[test]
+fn mock_matches_chain_fixtures() {
+    let fixture: Fixture = from_slice(include_bytes!("fixtures/parity.json")).unwrap();
+    assert!(!fixture.scenarios.is_empty());
+    for scenario in &fixture.scenarios {
+        run(scenario);
+    }
+}
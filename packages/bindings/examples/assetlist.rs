@@ -0,0 +1,38 @@
+//! Prints the chain-registry assetlist fragment for all token factory denoms
+//! with metadata in a node's exported state.
+//!
+//! ```sh
+//! osmosisd export > state.json
+//! cargo run --example assetlist --features assetlist -- state.json osmosis
+//! ```
+
+use std::env;
+use std::fs;
+
+use serde_json::{json, Value};
+use token_bindings::{is_factory_denom, Metadata};
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let path = args
+        .next()
+        .expect("usage: assetlist <exported state.json> [chain name]");
+    let chain_name = args.next().unwrap_or_else(|| "osmosis".to_string());
+
+    let state: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    let denom_metadata = state["app_state"]["bank"]["denom_metadata"].clone();
+    let denom_metadata: Vec<Metadata> = serde_json::from_value(denom_metadata).unwrap();
+
+    let assets: Vec<Value> = denom_metadata
+        .iter()
+        .filter_map(|metadata| {
+            let base = metadata.base.as_deref()?;
+            is_factory_denom(base).then(|| metadata.to_assetlist_entry(base))
+        })
+        .collect();
+    let fragment = json!({
+        "chain_name": chain_name,
+        "assets": assets,
+    });
+    println!("{}", serde_json::to_string_pretty(&fragment).unwrap());
+}
@@ -0,0 +1,210 @@
+use crate::bech32;
+use crate::error::TokenBindingsError;
+use crate::msg::TokenMsg;
+
+/// Module accounts of the cosmos sdk and osmosis modules, by module name and address bytes
+/// (the first 20 bytes of the sha256 of the name). Those bytes are the same on every chain,
+/// only the bech32 prefix differs. Nobody can sign for these, so a denom administered
+/// by one of them is lost.
+const MODULE_ACCOUNTS: &[(&str, &str)] = &[
+    ("fee_collector", "f1829676db577682e944fc3493d451b67ff3e29f"),
+    ("distribution", "93354845030274cd4bf1686abd60ab28ec52e1a7"),
+    ("mint", "dc6f17bbec824fff8f86587966b2047db6ab7367"),
+    (
+        "bonded_tokens_pool",
+        "4fea76427b8345861e80a3540a8a9d936fd39391",
+    ),
+    (
+        "not_bonded_tokens_pool",
+        "5911b844d7bc224654fe0dcd16babd2d253f2fdf",
+    ),
+    ("gov", "7b5fe22b5446f7c62ea27b8bd71cef94e03f3df2"),
+    ("transfer", "27f576cafbb263ed44be8bd094f66114da268777"),
+    ("tokenfactory", "2e6443cca08670b1a6b0ceda12e0369a7ff6be5a"),
+    ("poolmanager", "34fa3d4e0044d2831f05fd80dfc51055d8d95be1"),
+];
+
+/// What `TokenMsg::change_admin_safe` rejects. The default rejects the well-known
+/// module accounts only.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChangeAdminCheck {
+    /// Further addresses that may not become admin, eg. module accounts of other modules
+    pub denylist: Vec<String>,
+    /// Accept module accounts and the denylist anyway, eg. to hand a denom to governance
+    pub allow_module_account: bool,
+    /// The address was validated already, eg. with `deps.api.addr_validate`, which knows
+    /// the address format of the chain. The bech32 and zero address checks are skipped,
+    /// module accounts are still recognized if the address is bech32.
+    pub address_validated: bool,
+}
+
+impl TokenMsg {
+    /// Like `TokenMsg::ChangeAdmin`, but refuses new admins which would most likely brick
+    /// the denom: strings that are not bech32 addresses (eg. a typo breaking the checksum),
+    /// the all-zero address, and module accounts or `check.denylist` entries unless
+    /// `check.allow_module_account` is set.
+    ///
+    /// An empty `new_admin` renounces the admin on purpose and is accepted.
+    pub fn change_admin_safe(
+        denom: String,
+        new_admin: String,
+        check: &ChangeAdminCheck,
+    ) -> Result<Self, TokenBindingsError> {
+        if !new_admin.is_empty() {
+            check_new_admin(&new_admin, check)?;
+        }
+        Ok(TokenMsg::ChangeAdmin {
+            denom,
+            new_admin_address: new_admin,
+        })
+    }
+}
+
+fn check_new_admin(new_admin: &str, check: &ChangeAdminCheck) -> Result<(), TokenBindingsError> {
+    let invalid = |reason: &str| TokenBindingsError::InvalidAdmin {
+        address: new_admin.to_string(),
+        reason: reason.to_string(),
+    };
+    let data = match bech32::decode(new_admin) {
+        Some((_, data)) => Some(data),
+        None if check.address_validated => None,
+        None => return Err(invalid("not a bech32 address")),
+    };
+    if let Some(data) = &data {
+        if !check.address_validated && data.iter().all(|b| *b == 0) {
+            return Err(invalid("the zero address"));
+        }
+    }
+    if check.allow_module_account {
+        return Ok(());
+    }
+
+    if let Some(data) = &data {
+        let hex: String = data.iter().map(|b| format!("{:02x}", b)).collect();
+        if let Some((module, _)) = MODULE_ACCOUNTS.iter().find(|(_, addr)| *addr == hex) {
+            return Err(TokenBindingsError::ModuleAccountAdmin {
+                address: new_admin.to_string(),
+                module: module.to_string(),
+            });
+        }
+    }
+    if check.denylist.iter().any(|denied| denied == new_admin) {
+        return Err(invalid("denylisted"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DENOM: &str = "factory/osmo1creator/fundz";
+
+    fn change_admin(new_admin: &str, check: &ChangeAdminCheck) -> Result<(), TokenBindingsError> {
+        let msg = TokenMsg::change_admin_safe(DENOM.to_string(), new_admin.to_string(), check)?;
+        assert_eq!(
+            msg,
+            TokenMsg::ChangeAdmin {
+                denom: DENOM.to_string(),
+                new_admin_address: new_admin.to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn accepts_regular_addresses() {
+        let check = ChangeAdminCheck::default();
+        change_admin("osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks", &check).unwrap();
+        change_admin(
+            "osmo1z0qrq605sjgcqpylfl4aa6s90x738j7m58wyatt0tdzflg2ha26q67k743",
+            &check,
+        )
+        .unwrap();
+        // renouncing
+        change_admin("", &check).unwrap();
+    }
+
+    #[test]
+    fn rejects_module_accounts() {
+        let check = ChangeAdminCheck::default();
+        // the osmosis community pool, and the same module on another chain
+        for address in [
+            "osmo1jv65s3grqf6v6jl3dp4t6c9t9rk99cd80yhvld",
+            "cosmos1jv65s3grqf6v6jl3dp4t6c9t9rk99cd88lyufl",
+        ] {
+            assert_eq!(
+                change_admin(address, &check).unwrap_err(),
+                TokenBindingsError::ModuleAccountAdmin {
+                    address: address.to_string(),
+                    module: "distribution".to_string(),
+                }
+            );
+        }
+
+        let override_check = ChangeAdminCheck {
+            allow_module_account: true,
+            ..ChangeAdminCheck::default()
+        };
+        change_admin(
+            "osmo1jv65s3grqf6v6jl3dp4t6c9t9rk99cd80yhvld",
+            &override_check,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_denylist() {
+        let denied = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks";
+        let check = ChangeAdminCheck {
+            denylist: vec![denied.to_string()],
+            ..ChangeAdminCheck::default()
+        };
+        change_admin(denied, &check).unwrap_err();
+        change_admin(
+            denied,
+            &ChangeAdminCheck {
+                allow_module_account: true,
+                ..check
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        let check = ChangeAdminCheck {
+            allow_module_account: true,
+            ..ChangeAdminCheck::default()
+        };
+        // last character changed
+        let err = change_admin("osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakakt", &check).unwrap_err();
+        assert!(matches!(err, TokenBindingsError::InvalidAdmin { .. }));
+        change_admin("newadmin", &check).unwrap_err();
+        // valid checksum, but nobody has that key
+        let err = change_admin("osmo1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqmcn030", &check).unwrap_err();
+        assert_eq!(
+            err,
+            TokenBindingsError::InvalidAdmin {
+                address: "osmo1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqmcn030".to_string(),
+                reason: "the zero address".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn trusts_validated_addresses() {
+        let check = ChangeAdminCheck {
+            address_validated: true,
+            denylist: vec!["denied".to_string()],
+            ..ChangeAdminCheck::default()
+        };
+        // addresses of chains or tests with another format
+        change_admin("newadmin", &check).unwrap();
+        change_admin("osmo1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqmcn030", &check).unwrap();
+        // module accounts and the denylist are still refused
+        let err = change_admin("osmo1jv65s3grqf6v6jl3dp4t6c9t9rk99cd80yhvld", &check).unwrap_err();
+        assert!(matches!(err, TokenBindingsError::ModuleAccountAdmin { .. }));
+        change_admin("denied", &check).unwrap_err();
+    }
+}
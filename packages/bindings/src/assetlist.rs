@@ -0,0 +1,153 @@
+//! Export of bank metadata to the cosmos chain-registry assetlist format
+//! (https://github.com/cosmos/chain-registry/blob/master/assetlist.schema.json),
+//! as expected by wallets and explorers. Only meant for off-chain tooling.
+
+use serde_json::{json, Map, Value};
+
+use crate::types::Metadata;
+
+impl Metadata {
+    /// Builds the chain-registry asset object for `denom`, which is used as `base`.
+    ///
+    /// Unset fields are omitted, except `display`, `name` and `symbol` (required by
+    /// the registry), which fall back to `denom`. A `uri` ending in `.png` or `.svg`
+    /// becomes the matching `logo_URIs` entry, other uris are not exported.
+    pub fn to_assetlist_entry(&self, denom: &str) -> Value {
+        let mut entry = Map::new();
+        if let Some(description) = &self.description {
+            entry.insert("description".to_string(), json!(description));
+        }
+        let denom_units: Vec<Value> = self
+            .denom_units
+            .iter()
+            .map(|unit| {
+                let mut obj = json!({
+                    "denom": unit.denom,
+                    "exponent": unit.exponent,
+                });
+                if !unit.aliases.is_empty() {
+                    obj["aliases"] = json!(unit.aliases);
+                }
+                obj
+            })
+            .collect();
+        entry.insert("denom_units".to_string(), json!(denom_units));
+        entry.insert("type_asset".to_string(), json!("sdk.coin"));
+        entry.insert("base".to_string(), json!(denom));
+        for (field, value) in [
+            ("name", &self.name),
+            ("display", &self.display),
+            ("symbol", &self.symbol),
+        ] {
+            let value = value.as_deref().unwrap_or(denom);
+            entry.insert(field.to_string(), json!(value));
+        }
+        if let Some(uri) = &self.uri {
+            let format = if uri.ends_with(".png") {
+                Some("png")
+            } else if uri.ends_with(".svg") {
+                Some("svg")
+            } else {
+                None
+            };
+            if let Some(format) = format {
+                entry.insert("logo_URIs".to_string(), json!({ format: uri }));
+            }
+        }
+        Value::Object(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DenomUnit;
+
+    const DENOM: &str =
+        "factory/osmo1z0qrq605sjgcqpylfl4aa6s90x738j7m58wyatt0tdzflg2ha26q67k743/wbtc";
+
+    /// An assetlist in the chain-registry format, with an entry for a factory token.
+    /// Registry entries have more fields than the export, e.g. `coingecko_id` or `images`.
+    const ASSETLIST: &str = include_str!("../testdata/assetlist.json");
+
+    fn metadata() -> Metadata {
+        Metadata {
+            description: Some("Wrapped Bitcoin, bridged to Osmosis".to_string()),
+            denom_units: vec![
+                DenomUnit {
+                    denom: DENOM.to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: "wbtc".to_string(),
+                    exponent: 8,
+                    aliases: vec!["btc".to_string()],
+                },
+            ],
+            base: Some(DENOM.to_string()),
+            display: Some("wbtc".to_string()),
+            name: Some("Wrapped Bitcoin".to_string()),
+            symbol: Some("WBTC".to_string()),
+            uri: Some("https://raw.githubusercontent.com/cosmos/chain-registry/master/_non-cosmos/ethereum/images/wbtc.png".to_string()),
+            uri_hash: None,
+        }
+    }
+
+    /// The entry of the assetlist with `base`
+    fn registry_entry(base: &str) -> Map<String, Value> {
+        let assetlist: Value = serde_json::from_str(ASSETLIST).unwrap();
+        let assets = assetlist["assets"].as_array().unwrap();
+        let entry = assets.iter().find(|asset| asset["base"] == base).unwrap();
+        entry.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn matches_chain_registry() {
+        let registry = registry_entry(DENOM);
+        let exported = metadata().to_assetlist_entry(DENOM);
+        let exported = exported.as_object().unwrap();
+
+        // every exported field has the registry's value
+        for (field, value) in exported {
+            assert_eq!(Some(value), registry.get(field), "{}", field);
+        }
+        // and every field the registry requires is exported
+        for field in [
+            "denom_units",
+            "type_asset",
+            "base",
+            "name",
+            "display",
+            "symbol",
+        ] {
+            assert!(exported.contains_key(field), "{}", field);
+        }
+    }
+
+    #[test]
+    fn minimal_metadata() {
+        let metadata = Metadata {
+            description: None,
+            denom_units: vec![],
+            base: None,
+            display: None,
+            name: Some("Fundz".to_string()),
+            symbol: None,
+            uri: Some("ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()),
+            uri_hash: None,
+        };
+        let denom = "factory/osmo1creator/fundz";
+        assert_eq!(
+            metadata.to_assetlist_entry(denom),
+            json!({
+                "denom_units": [],
+                "type_asset": "sdk.coin",
+                "base": denom,
+                "name": "Fundz",
+                "display": denom,
+                "symbol": denom,
+            })
+        );
+    }
+}
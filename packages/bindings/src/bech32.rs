@@ -0,0 +1,99 @@
+//! Minimal bech32 (BIP-173) decoding, just enough to check cosmos addresses
+//! without depending on a chain's `Api`.
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const CHECKSUM_LENGTH: usize = 6;
+
+/// Splits a bech32 string into its human readable part and data bytes,
+/// verifying the checksum. Returns `None` for anything malformed.
+pub(crate) fn decode(address: &str) -> Option<(&str, Vec<u8>)> {
+    // mixed case is invalid, and cosmos addresses are lowercase
+    if address.bytes().any(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    let separator = address.rfind('1')?;
+    let (hrp, data) = (&address[..separator], &address[separator + 1..]);
+    if hrp.is_empty()
+        || data.len() < CHECKSUM_LENGTH
+        || !hrp.bytes().all(|b| (33..=126).contains(&b))
+    {
+        return None;
+    }
+    let values = data
+        .bytes()
+        .map(|b| CHARSET.iter().position(|c| *c == b).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()?;
+
+    let mut checked = expand_hrp(hrp);
+    checked.extend_from_slice(&values);
+    if polymod(&checked) != 1 {
+        return None;
+    }
+    let bytes = convert_bits(&values[..values.len() - CHECKSUM_LENGTH])?;
+    Some((hrp, bytes))
+}
+
+fn expand_hrp(hrp: &str) -> Vec<u8> {
+    let mut expanded: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    expanded.push(0);
+    expanded.extend(hrp.bytes().map(|b| b & 31));
+    expanded
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    values.iter().fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = (chk & 0x1ffffff) << 5 ^ *value as u32;
+        (0..5)
+            .filter(|i| (top >> i) & 1 == 1)
+            .fold(chk, |chk, i| chk ^ GENERATOR[i])
+    })
+}
+
+/// Regroups 5 bit values into bytes. The padding must be less than 5 bits and zero.
+fn convert_bits(values: &[u8]) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    let mut bytes = Vec::with_capacity(values.len() * 5 / 8);
+    for value in values {
+        acc = (acc << 5) | *value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+        }
+    }
+    if bits >= 5 || acc & ((1 << bits) - 1) != 0 {
+        return None;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bip173_vectors() {
+        assert_eq!(decode("a12uel5l"), Some(("a", vec![])));
+        // all 32 values in order
+        let (hrp, data) = decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap();
+        assert_eq!(hrp, "abcdef");
+        assert_eq!(
+            data,
+            [
+                0x00, 0x44, 0x32, 0x14, 0xc7, 0x42, 0x54, 0xb6, 0x35, 0xcf, 0x84, 0x65, 0x3a, 0x56,
+                0xd7, 0xc6, 0x75, 0xbe, 0x77, 0xdf
+            ]
+        );
+        assert!(decode("split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w").is_some());
+
+        // invalid checksum, mixed case, no separator, empty hrp, invalid character
+        assert_eq!(decode("a12uel5m"), None);
+        assert_eq!(decode("A12uel5l"), None);
+        assert_eq!(decode("pzry9x0s0muk"), None);
+        assert_eq!(decode("1pzry9x0s0muk"), None);
+        assert_eq!(decode("x1b4n0q5v"), None);
+    }
+}
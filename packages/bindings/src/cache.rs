@@ -0,0 +1,165 @@
+//! Caching of rarely changing query results in contract storage.
+//!
+//! The params only change through governance, yet contracts checking the creation fee
+//! would query them on every execution. Keep the last response in an `Item` instead,
+//! and only query again once it is older than the accepted age.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::Item;
+
+use crate::querier::TokenQuerier;
+use crate::query::ParamsResponse;
+
+/// A `ParamsResponse` with the block height it was queried at, see
+/// `TokenQuerier::params_cached`
+#[cw_serde]
+pub struct CachedParams {
+    pub params: ParamsResponse,
+    pub height: u64,
+}
+
+impl<'a> TokenQuerier<'a> {
+    /// Returns the params stored in `cache` if they were queried at most `max_age_blocks`
+    /// before `height` (usually `env.block.height`), otherwise queries and stores them.
+    ///
+    /// The params may thus be up to `max_age_blocks` out of date. Call `force_refresh`
+    /// after a change is known to have happened.
+    pub fn params_cached(
+        &self,
+        storage: &mut dyn Storage,
+        cache: &Item<CachedParams>,
+        height: u64,
+        max_age_blocks: u64,
+    ) -> StdResult<ParamsResponse> {
+        if let Some(cached) = cache.may_load(storage)? {
+            // a height before the cached one means the storage was restored, don't trust it
+            if cached.height <= height && height - cached.height <= max_age_blocks {
+                return Ok(cached.params);
+            }
+        }
+        self.force_refresh(storage, cache, height)
+    }
+
+    /// Queries the params and stores them in `cache` as of `height`
+    pub fn force_refresh(
+        &self,
+        storage: &mut dyn Storage,
+        cache: &Item<CachedParams>,
+        height: u64,
+    ) -> StdResult<ParamsResponse> {
+        let params = self.params()?;
+        cache.save(
+            storage,
+            &CachedParams {
+                params: params.clone(),
+                height,
+            },
+        )?;
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::{TokenFactoryQuery, TokenQuery};
+    use crate::types::Params;
+    use cosmwasm_std::testing::{MockQuerier, MockStorage};
+    use cosmwasm_std::{coins, to_binary, ContractResult, QuerierWrapper, SystemResult};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    const CACHE: Item<CachedParams> = Item::new("params_cache");
+
+    /// Answers Params queries with a fee of the number of queries so far,
+    /// so the tests see both how often and when the chain was queried
+    fn counting_querier(count: Rc<Cell<u128>>) -> MockQuerier<TokenFactoryQuery> {
+        MockQuerier::new(&[]).with_custom_handler(move |query| {
+            assert_eq!(query, &TokenFactoryQuery::Token(TokenQuery::Params {}));
+            count.set(count.get() + 1);
+            let res = ParamsResponse {
+                params: Params {
+                    denom_creation_fee: coins(count.get(), "uosmo"),
+                    enabled: true,
+                },
+            };
+            SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+        })
+    }
+
+    fn fee(res: &ParamsResponse) -> u128 {
+        res.params.denom_creation_fee[0].amount.u128()
+    }
+
+    #[test]
+    fn queries_only_when_stale() {
+        let count = Rc::new(Cell::new(0));
+        let mock = counting_querier(count.clone());
+        let wrapper = QuerierWrapper::new(&mock);
+        let querier = TokenQuerier::new(&wrapper);
+        let mut storage = MockStorage::new();
+
+        // the first call always queries
+        let res = querier
+            .params_cached(&mut storage, &CACHE, 100, 10)
+            .unwrap();
+        assert_eq!((fee(&res), count.get()), (1, 1));
+
+        // within the max age, also at its edge
+        for height in [100, 105, 110] {
+            let res = querier
+                .params_cached(&mut storage, &CACHE, height, 10)
+                .unwrap();
+            assert_eq!((fee(&res), count.get()), (1, 1));
+        }
+
+        // stale, then cached as of the new height
+        let res = querier
+            .params_cached(&mut storage, &CACHE, 111, 10)
+            .unwrap();
+        assert_eq!((fee(&res), count.get()), (2, 2));
+        let res = querier
+            .params_cached(&mut storage, &CACHE, 121, 10)
+            .unwrap();
+        assert_eq!((fee(&res), count.get()), (2, 2));
+
+        // a max age of zero only caches within a block
+        let res = querier.params_cached(&mut storage, &CACHE, 122, 0).unwrap();
+        assert_eq!((fee(&res), count.get()), (3, 3));
+        let res = querier.params_cached(&mut storage, &CACHE, 122, 0).unwrap();
+        assert_eq!((fee(&res), count.get()), (3, 3));
+
+        // an earlier height than cached is not trusted
+        let res = querier.params_cached(&mut storage, &CACHE, 50, 10).unwrap();
+        assert_eq!((fee(&res), count.get()), (4, 4));
+    }
+
+    #[test]
+    fn force_refresh() {
+        let count = Rc::new(Cell::new(0));
+        let mock = counting_querier(count.clone());
+        let wrapper = QuerierWrapper::new(&mock);
+        let querier = TokenQuerier::new(&wrapper);
+        let mut storage = MockStorage::new();
+
+        querier
+            .params_cached(&mut storage, &CACHE, 100, 10)
+            .unwrap();
+        let res = querier.force_refresh(&mut storage, &CACHE, 101).unwrap();
+        assert_eq!((fee(&res), count.get()), (2, 2));
+        assert_eq!(
+            CACHE.load(&storage).unwrap(),
+            CachedParams {
+                params: res,
+                height: 101,
+            }
+        );
+
+        // the refreshed params are served from then on
+        let res = querier
+            .params_cached(&mut storage, &CACHE, 111, 10)
+            .unwrap();
+        assert_eq!((fee(&res), count.get()), (2, 2));
+    }
+}
@@ -0,0 +1,425 @@
+use cosmwasm_std::{Addr, Api, StdError, StdResult};
+
+use crate::bech32;
+use crate::error::DenomError;
+
+/// Prefix of all denoms created by the token factory
+pub const DENOM_PREFIX: &str = "factory";
+
+/// Maximum length of a subdenom accepted by the token factory
+pub const MAX_SUBDENOM_LENGTH: usize = 44;
+
+/// Characters `validate_full_denom` accepts in subdenoms
+pub const SUBDENOM_CHARS: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.";
+
+/// Length of addresses derived from a public key, ie. externally owned accounts
+const ACCOUNT_ADDRESS_LENGTH: usize = 20;
+/// Length of addresses of contracts and module accounts
+const CONTRACT_ADDRESS_LENGTH: usize = 32;
+
+/// The components of a token factory denom: `factory/{creator}/{subdenom}`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenomParts {
+    pub creator: String,
+    pub subdenom: String,
+}
+
+impl DenomParts {
+    /// Splits a full denom into its creator and subdenom.
+    /// This is purely structural, the creator address is not validated.
+    pub fn parse(denom: &str) -> Result<Self, DenomError> {
+        let parts: Vec<&str> = denom.split('/').collect();
+        if parts.len() != 3 {
+            return Err(DenomError::WrongPartCount { parts: parts.len() });
+        }
+        if parts[0] != DENOM_PREFIX {
+            return Err(DenomError::InvalidPrefix {
+                prefix: parts[0].to_string(),
+            });
+        }
+        Ok(DenomParts {
+            creator: parts[1].to_string(),
+            subdenom: parts[2].to_string(),
+        })
+    }
+
+    /// Validates the creator against the given api, ie. that it round-trips as an address
+    /// of the current chain. Only use this for denoms which must have been created here.
+    pub fn validate_creator(&self, api: &dyn Api) -> StdResult<Addr> {
+        api.addr_validate(&self.creator)
+    }
+}
+
+/// Whether the denom is meant to be a token factory denom, ie. starts with `factory/`.
+/// Native (`uosmo`), IBC (`ibc/...`) and other module denoms (`gamm/pool/1`) are not.
+/// This does not check the structure, use `validate_full_denom` for that.
+pub fn is_factory_denom(denom: &str) -> bool {
+    denom.split('/').next() == Some(DENOM_PREFIX) && denom.len() > DENOM_PREFIX.len()
+}
+
+/// Shortens a token factory denom for display, eg. in event attributes, by keeping only the
+/// last 4 characters of the creator after an ellipsis: `factory/…k743/wbtc`.
+/// Creators of up to 4 characters and all other denoms are returned unchanged.
+///
+/// The result is not a denom, always emit the full denom next to it for machine consumers.
+pub fn short_denom(denom: &str) -> String {
+    const CREATOR_TAIL: usize = 4;
+
+    let parts = match DenomParts::parse(denom) {
+        Ok(parts) => parts,
+        Err(_) => return denom.to_string(),
+    };
+    let chars = parts.creator.chars().count();
+    if chars <= CREATOR_TAIL {
+        return denom.to_string();
+    }
+    let tail: String = parts.creator.chars().skip(chars - CREATOR_TAIL).collect();
+    format!("{}/…{}/{}", DENOM_PREFIX, tail, parts.subdenom)
+}
+
+/// Whether two token factory denoms were created by the same address.
+/// Errors if either is not a valid token factory denom (see `validate_full_denom`).
+/// Creators are compared as strings, so the same account on two chains doesn't match.
+pub fn same_creator(a: &str, b: &str) -> StdResult<bool> {
+    let creator = |denom: &str| {
+        validate_full_denom(denom)
+            .map(|parts| parts.creator)
+            .map_err(|err| StdError::generic_err(format!("Invalid denom {}: {}", denom, err)))
+    };
+    Ok(creator(a)? == creator(b)?)
+}
+
+/// Checks the structure of a full denom, including the subdenom charset and length.
+/// The creator is treated as an opaque string, so denoms created on chains with any
+/// bech32 prefix pass. Use `DenomParts::validate_creator` to check it against the chain,
+/// or `validate_creator_prefix` for a given chain prefix.
+pub fn validate_full_denom(denom: &str) -> Result<DenomParts, DenomError> {
+    let parts = DenomParts::parse(denom)?;
+    let valid_subdenom = parts.subdenom.len() <= MAX_SUBDENOM_LENGTH
+        && parts.subdenom.chars().all(|c| SUBDENOM_CHARS.contains(c));
+    if !valid_subdenom {
+        return Err(DenomError::InvalidSubdenom {
+            subdenom: parts.subdenom,
+        });
+    }
+    Ok(parts)
+}
+
+/// Checks the full denom like `validate_full_denom`, and that the creator is a bech32
+/// address of the chain with the human readable part `expected_hrp` (eg. `osmo`).
+///
+/// Unlike `DenomParts::validate_creator`, this does not need the chain's `Api`, so it works
+/// for denoms of any chain. The address must be 20 (account) or 32 (contract) bytes long.
+pub fn validate_creator_prefix(denom: &str, expected_hrp: &str) -> Result<DenomParts, DenomError> {
+    let parts = validate_full_denom(denom)?;
+    let (hrp, data) = bech32::decode(&parts.creator).ok_or_else(|| DenomError::InvalidCreator {
+        creator: parts.creator.clone(),
+    })?;
+    if hrp != expected_hrp {
+        return Err(DenomError::WrongCreatorPrefix {
+            expected: expected_hrp.to_string(),
+            prefix: hrp.to_string(),
+        });
+    }
+    if data.len() != ACCOUNT_ADDRESS_LENGTH && data.len() != CONTRACT_ADDRESS_LENGTH {
+        return Err(DenomError::InvalidCreatorLength { length: data.len() });
+    }
+    Ok(parts)
+}
+
+/// Derives a valid subdenom from an arbitrary label, so frontends can predict it.
+///
+/// The label is lowercased, every run of characters outside `[a-z0-9]` becomes a single `.`
+/// (leading and trailing ones are dropped), and the result is truncated to 35 characters.
+/// Then a `.` and the 8 hex digits of the 32 bit FNV-1a hash of the original label are
+/// appended, so labels that normalize identically still get distinct subdenoms.
+/// A label with no usable characters yields just the hash.
+pub fn derive_subdenom(label: &str) -> String {
+    const HASH_LEN: usize = 8;
+
+    let mut normalized = String::with_capacity(label.len());
+    for c in label.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            normalized.push(c);
+        } else if !normalized.is_empty() && !normalized.ends_with('.') {
+            normalized.push('.');
+        }
+    }
+    // only ascii is left, so we can truncate at any byte
+    normalized.truncate(MAX_SUBDENOM_LENGTH - HASH_LEN - 1);
+    let normalized = normalized.trim_end_matches('.');
+
+    let hash = format!("{:08x}", fnv1a_32(label.as_bytes()));
+    if normalized.is_empty() {
+        hash
+    } else {
+        format!("{}.{}", normalized, hash)
+    }
+}
+
+fn fnv1a_32(data: &[u8]) -> u32 {
+    const OFFSET_BASIS: u32 = 0x811c9dc5;
+    const PRIME: u32 = 0x01000193;
+    data.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u32).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+
+    #[test]
+    fn parse_factory_denom() {
+        let parts = DenomParts::parse("factory/osmo1creator/fundz").unwrap();
+        assert_eq!(
+            parts,
+            DenomParts {
+                creator: "osmo1creator".to_string(),
+                subdenom: "fundz".to_string(),
+            }
+        );
+
+        // empty subdenoms are valid
+        let parts = DenomParts::parse("factory/osmo1creator/").unwrap();
+        assert_eq!(parts.subdenom, "");
+    }
+
+    #[test]
+    fn parse_other_denoms() {
+        assert_eq!(
+            DenomParts::parse("uosmo").unwrap_err(),
+            DenomError::WrongPartCount { parts: 1 }
+        );
+        assert_eq!(
+            DenomParts::parse("factory/osmo1creator/fundz/more").unwrap_err(),
+            DenomError::WrongPartCount { parts: 4 }
+        );
+        assert_eq!(
+            DenomParts::parse("gamm/pool/1").unwrap_err(),
+            DenomError::InvalidPrefix {
+                prefix: "gamm".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn classify_factory_denoms() {
+        assert!(is_factory_denom("factory/osmo1creator/fundz"));
+        // malformed, but still meant as factory denoms
+        assert!(is_factory_denom("factory/osmo1creator"));
+        assert!(is_factory_denom("factory/osmo1creator/fundz/more"));
+
+        assert!(!is_factory_denom("uosmo"));
+        assert!(!is_factory_denom("factory"));
+        assert!(!is_factory_denom("factoryz/osmo1creator/fundz"));
+        assert!(!is_factory_denom(
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        ));
+        assert!(!is_factory_denom("gamm/pool/1"));
+    }
+
+    #[test]
+    fn derive_subdenom_normalizes() {
+        // reference values of FNV-1a
+        assert_eq!(fnv1a_32(b""), 0x811c9dc5);
+        assert_eq!(fnv1a_32(b"a"), 0xe40c292c);
+
+        assert_eq!(derive_subdenom("My Cool Token!"), "my.cool.token.a8723934");
+        assert_eq!(derive_subdenom(""), "811c9dc5");
+        assert_eq!(derive_subdenom("!!!"), format!("{:08x}", fnv1a_32(b"!!!")));
+
+        let long = derive_subdenom(&"Very long token name ".repeat(10));
+        assert_eq!(long.len(), MAX_SUBDENOM_LENGTH);
+        for label in [
+            "My Cool Token!",
+            "",
+            "!!!",
+            "Über ☃ token",
+            &"x".repeat(100),
+        ] {
+            let subdenom = derive_subdenom(label);
+            let denom = format!("factory/osmo1creator/{}", subdenom);
+            validate_full_denom(&denom).unwrap();
+            // deterministic
+            assert_eq!(subdenom, derive_subdenom(label));
+        }
+    }
+
+    #[test]
+    fn derive_subdenom_avoids_collisions() {
+        let labels = [
+            "My Cool Token!",
+            "My Cool Token",
+            "my-cool-token",
+            "MY COOL TOKEN?",
+        ];
+        let subdenoms: Vec<_> = labels.iter().map(|l| derive_subdenom(l)).collect();
+        for (i, a) in subdenoms.iter().enumerate() {
+            assert!(a.starts_with("my.cool.token."));
+            for b in &subdenoms[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn validate_any_creator_prefix() {
+        for creator in [
+            "osmo1q8tq5qhrhw6t970egemuuwywhlhpnmdmts6xnu",
+            "juno1q8tq5qhrhw6t970egemuuwywhlhpnmdmp6r0dh",
+        ] {
+            let denom = format!("factory/{}/fundz", creator);
+            let parts = validate_full_denom(&denom).unwrap();
+            assert_eq!(parts.creator, creator);
+            assert_eq!(parts.subdenom, "fundz");
+        }
+
+        let too_long = format!("factory/juno1creator/{}", "x".repeat(45));
+        assert_eq!(
+            validate_full_denom(&too_long).unwrap_err(),
+            DenomError::InvalidSubdenom {
+                subdenom: "x".repeat(45)
+            }
+        );
+        let err = validate_full_denom("factory/juno1creator/fun-dz").unwrap_err();
+        assert_eq!(
+            err,
+            DenomError::InvalidSubdenom {
+                subdenom: "fun-dz".to_string()
+            }
+        );
+        // the message lists what is accepted
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "subdenom must be at most 44 characters of {}, was fun-dz",
+                SUBDENOM_CHARS
+            )
+        );
+    }
+
+    #[test]
+    fn validate_creator_round_trip() {
+        let api = MockApi::default();
+
+        let parts = validate_full_denom("factory/creator/fundz").unwrap();
+        assert_eq!(parts.validate_creator(&api).unwrap(), "creator");
+
+        // structurally fine, but not normalized for this chain
+        let parts = validate_full_denom("factory/Creator/fundz").unwrap();
+        parts.validate_creator(&api).unwrap_err();
+    }
+
+    #[test]
+    fn validate_creator_of_chain() {
+        // accounts and contracts of three chains
+        for (creator, hrp) in [
+            ("osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks", "osmo"),
+            (
+                "osmo1z0qrq605sjgcqpylfl4aa6s90x738j7m58wyatt0tdzflg2ha26q67k743",
+                "osmo",
+            ),
+            (
+                "juno1mkw83sv6c7sjdvsaplrzc8yaes9l42p4mhy0ssuxjnyzl87c9eps7ce3m9",
+                "juno",
+            ),
+            ("neutron1m9l358xunhhwds0568za49mzhvuxx9ux8xafx2", "neutron"),
+        ] {
+            let denom = format!("factory/{}/fundz", creator);
+            let parts = validate_creator_prefix(&denom, hrp).unwrap();
+            assert_eq!(parts.creator, creator);
+
+            let err = validate_creator_prefix(&denom, "cosmos").unwrap_err();
+            assert_eq!(
+                err,
+                DenomError::WrongCreatorPrefix {
+                    expected: "cosmos".to_string(),
+                    prefix: hrp.to_string(),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn validate_creator_rejects_invalid_addresses() {
+        // last character changed
+        let creator = "neutron1m9l358xunhhwds0568za49mzhvuxx9ux8xafx3";
+        let denom = format!("factory/{}/fundz", creator);
+        assert_eq!(
+            validate_creator_prefix(&denom, "neutron").unwrap_err(),
+            DenomError::InvalidCreator {
+                creator: creator.to_string()
+            }
+        );
+        // uppercase is not used for addresses
+        let denom = "factory/NEUTRON1M9L358XUNHHWDS0568ZA49MZHVUXX9UX8XAFX2/fundz";
+        assert!(matches!(
+            validate_creator_prefix(denom, "neutron").unwrap_err(),
+            DenomError::InvalidCreator { .. }
+        ));
+        // the mock addresses used in tests are not bech32
+        assert!(matches!(
+            validate_creator_prefix("factory/osmo1creator/fundz", "osmo").unwrap_err(),
+            DenomError::InvalidCreator { .. }
+        ));
+
+        // valid checksum, but 33 bytes
+        let denom =
+            "factory/osmo1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0jq93n5yp/fundz";
+        assert_eq!(
+            validate_creator_prefix(denom, "osmo").unwrap_err(),
+            DenomError::InvalidCreatorLength { length: 33 }
+        );
+
+        // the denom structure is checked first
+        assert_eq!(
+            validate_creator_prefix("uosmo", "osmo").unwrap_err(),
+            DenomError::WrongPartCount { parts: 1 }
+        );
+    }
+
+    #[test]
+    fn same_creators() {
+        assert!(same_creator("factory/osmo1creator/fundz", "factory/osmo1creator/gems").unwrap());
+        assert!(same_creator("factory/osmo1creator/fundz", "factory/osmo1creator/fundz").unwrap());
+        assert!(!same_creator("factory/osmo1creator/fundz", "factory/osmo1other/fundz").unwrap());
+        // creators are not normalized
+        assert!(!same_creator("factory/osmo1creator/fundz", "factory/OSMO1CREATOR/fundz").unwrap());
+
+        // malformed on either side
+        let err = same_creator("factory/osmo1creator/fundz", "uosmo").unwrap_err();
+        assert!(err.to_string().contains("Invalid denom uosmo"));
+        let err = same_creator("factory/osmo1creator/fund$", "factory/osmo1creator/fundz");
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("factory/osmo1creator/fund$"));
+        same_creator("factory/osmo1creator", "factory/osmo1creator/fundz").unwrap_err();
+    }
+
+    #[test]
+    fn short_denoms() {
+        assert_eq!(
+            short_denom(
+                "factory/osmo1z0qrq605sjgcqpylfl4aa6s90x738j7m58wyatt0tdzflg2ha26q67k743/wbtc"
+            ),
+            "factory/…k743/wbtc"
+        );
+        assert_eq!(
+            short_denom("factory/osmo1creator/fundz"),
+            "factory/…ator/fundz"
+        );
+        // nothing to shorten
+        assert_eq!(short_denom("factory/abcd/fundz"), "factory/abcd/fundz");
+        assert_eq!(short_denom("factory/abcde/"), "factory/…bcde/");
+        // not factory denoms
+        assert_eq!(short_denom("uosmo"), "uosmo");
+        assert_eq!(short_denom("gamm/pool/1"), "gamm/pool/1");
+        assert_eq!(
+            short_denom("factory/osmo1creator/fundz/more"),
+            "factory/osmo1creator/fundz/more"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use cosmwasm_std::OverflowError;
+use thiserror::Error;
+
+use crate::denom::{MAX_SUBDENOM_LENGTH, SUBDENOM_CHARS};
+
+/// Reasons a `Metadata` would be rejected by the bank module
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MetadataError {
+    #[error("Metadata field '{field}' cannot be blank")]
+    MissingField { field: String },
+
+    #[error("First denom unit must be the base denom '{base}' with exponent 0")]
+    InvalidBaseUnit { base: String },
+
+    #[error("Display denom '{display}' is not one of the denom units")]
+    DisplayNotFound { display: String },
+
+    #[error("Duplicate denom unit or alias '{denom}'")]
+    DuplicateDenomUnit { denom: String },
+
+    #[error("Denom unit '{denom}' must have a greater exponent than the previous one")]
+    NonMonotonicExponents { denom: String },
+}
+
+/// Reasons a string is not a well-formed token factory denom
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DenomError {
+    #[error("denom must have 3 parts separated by /, had {parts}")]
+    WrongPartCount { parts: usize },
+
+    #[error("prefix must be 'factory', was {prefix}")]
+    InvalidPrefix { prefix: String },
+
+    #[error(
+        "subdenom must be at most {} characters of {}, was {subdenom}",
+        MAX_SUBDENOM_LENGTH,
+        SUBDENOM_CHARS
+    )]
+    InvalidSubdenom { subdenom: String },
+
+    #[error("creator {creator} is not a valid bech32 address")]
+    InvalidCreator { creator: String },
+
+    #[error("creator must have the prefix {expected}, had {prefix}")]
+    WrongCreatorPrefix { expected: String, prefix: String },
+
+    #[error("creator must be a 20 byte account or 32 byte contract address, had {length} bytes")]
+    InvalidCreatorLength { length: usize },
+}
+
+/// Reasons the message helpers reject their input
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum TokenBindingsError {
+    #[error("{0}")]
+    Denom(#[from] DenomError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Metadata(#[from] MetadataError),
+
+    #[error("metadata base {base:?} must be the denom {denom}")]
+    MetadataBaseMismatch { base: Option<String>, denom: String },
+
+    #[error("amount of {denom} was zero, must be positive")]
+    ZeroAmount { denom: String },
+
+    #[error("address for {denom} cannot be empty")]
+    EmptyAddress { denom: String },
+
+    #[error("refusing to make {address} the admin: {reason}")]
+    InvalidAdmin { address: String, reason: String },
+
+    #[error("refusing to make {address}, the {module} module account, the admin")]
+    ModuleAccountAdmin { address: String, module: String },
+
+    #[error("{msg} is not supported on {chain}")]
+    UnsupportedMsg { msg: String, chain: String },
+}
@@ -0,0 +1,183 @@
+//! Event types and attributes emitted by the token factory module
+//! (see osmosis x/tokenfactory/types/events.go), and helpers to extract
+//! them from the events of a `Reply`, as mint and burn return no data.
+
+use cosmwasm_std::{Event, Uint128};
+
+pub const EVENT_CREATE_DENOM: &str = "create_denom";
+pub const EVENT_MINT: &str = "tf_mint";
+pub const EVENT_BURN: &str = "tf_burn";
+pub const EVENT_CHANGE_ADMIN: &str = "change_admin";
+pub const EVENT_SET_DENOM_METADATA: &str = "set_denom_metadata";
+pub const EVENT_LOCK_DENOM_METADATA: &str = "lock_denom_metadata";
+pub const EVENT_FORCE_TRANSFER: &str = "force_transfer";
+pub const EVENT_SET_BEFORE_SEND_HOOK: &str = "set_before_send_hook";
+
+pub const ATTR_AMOUNT: &str = "amount";
+/// Who sent the message, on every event of the mock in token-bindings-test
+pub const ATTR_SENDER: &str = "sender";
+pub const ATTR_CREATOR: &str = "creator";
+pub const ATTR_NEW_TOKEN_DENOM: &str = "new_token_denom";
+pub const ATTR_MINT_TO_ADDRESS: &str = "mint_to_address";
+pub const ATTR_BURN_FROM_ADDRESS: &str = "burn_from_address";
+pub const ATTR_DENOM: &str = "denom";
+pub const ATTR_NEW_ADMIN: &str = "new_admin";
+pub const ATTR_TRANSFER_FROM_ADDRESS: &str = "transfer_from_address";
+pub const ATTR_TRANSFER_TO_ADDRESS: &str = "transfer_to_address";
+pub const ATTR_BEFORE_SEND_HOOK_ADDRESS: &str = "before_send_hook_address";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateDenomInfo {
+    pub creator: String,
+    pub new_token_denom: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintInfo {
+    pub denom: String,
+    pub amount: Uint128,
+    pub mint_to_address: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BurnInfo {
+    pub denom: String,
+    pub amount: Uint128,
+    pub burn_from_address: String,
+}
+
+/// Returns the info of the first `create_denom` event, if any
+pub fn parse_create_denom_event(events: &[Event]) -> Option<CreateDenomInfo> {
+    let event = find_event(events, EVENT_CREATE_DENOM)?;
+    Some(CreateDenomInfo {
+        creator: attribute(event, ATTR_CREATOR)?.to_string(),
+        new_token_denom: attribute(event, ATTR_NEW_TOKEN_DENOM)?.to_string(),
+    })
+}
+
+/// Returns the info of the first `tf_mint` event, if any
+pub fn parse_mint_event(events: &[Event]) -> Option<MintInfo> {
+    let event = find_event(events, EVENT_MINT)?;
+    let (amount, denom) = parse_coin(attribute(event, ATTR_AMOUNT)?)?;
+    Some(MintInfo {
+        denom,
+        amount,
+        mint_to_address: attribute(event, ATTR_MINT_TO_ADDRESS)?.to_string(),
+    })
+}
+
+/// Returns the info of the first `tf_burn` event, if any
+pub fn parse_burn_event(events: &[Event]) -> Option<BurnInfo> {
+    let event = find_event(events, EVENT_BURN)?;
+    let (amount, denom) = parse_coin(attribute(event, ATTR_AMOUNT)?)?;
+    Some(BurnInfo {
+        denom,
+        amount,
+        burn_from_address: attribute(event, ATTR_BURN_FROM_ADDRESS)?.to_string(),
+    })
+}
+
+/// Matches the type as emitted by the module, or prefixed by `wasm-`
+/// as it is when the event passes through a contract
+fn find_event<'a>(events: &'a [Event], ty: &str) -> Option<&'a Event> {
+    events
+        .iter()
+        .find(|e| e.ty == ty || e.ty.strip_prefix("wasm-") == Some(ty))
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> Option<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|a| a.key == key)
+        .map(|a| a.value.as_str())
+}
+
+/// Splits an sdk.Coin string like `100factory/osmo1.../sub` into amount and denom
+fn parse_coin(coin: &str) -> Option<(Uint128, String)> {
+    let split = coin.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, denom) = coin.split_at(split);
+    let amount = amount.parse::<u128>().ok()?;
+    Some((Uint128::new(amount), denom.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREATOR: &str = "osmo1q8tq5qhrhw6t970egemuuwywhlhpnmdmts6xnu";
+    const DENOM: &str = "factory/osmo1q8tq5qhrhw6t970egemuuwywhlhpnmdmts6xnu/fundz";
+
+    // shape of the events in a tx result on chain
+    fn chain_events() -> Vec<Event> {
+        vec![
+            Event::new("message").add_attribute("action", "/osmosis.tokenfactory.v1beta1.MsgMint"),
+            Event::new("create_denom")
+                .add_attribute("creator", CREATOR)
+                .add_attribute("new_token_denom", DENOM),
+            Event::new("tf_mint")
+                .add_attribute("mint_to_address", CREATOR)
+                .add_attribute("amount", format!("1000000{}", DENOM)),
+            Event::new("tf_burn")
+                .add_attribute("burn_from_address", CREATOR)
+                .add_attribute("amount", format!("250{}", DENOM)),
+        ]
+    }
+
+    #[test]
+    fn parse_chain_events() {
+        let events = chain_events();
+        assert_eq!(
+            parse_create_denom_event(&events),
+            Some(CreateDenomInfo {
+                creator: CREATOR.to_string(),
+                new_token_denom: DENOM.to_string(),
+            })
+        );
+        assert_eq!(
+            parse_mint_event(&events),
+            Some(MintInfo {
+                denom: DENOM.to_string(),
+                amount: Uint128::new(1000000),
+                mint_to_address: CREATOR.to_string(),
+            })
+        );
+        assert_eq!(
+            parse_burn_event(&events),
+            Some(BurnInfo {
+                denom: DENOM.to_string(),
+                amount: Uint128::new(250),
+                burn_from_address: CREATOR.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_wasm_prefixed_events() {
+        let events: Vec<_> = chain_events()
+            .into_iter()
+            .map(|mut e| {
+                e.ty = format!("wasm-{}", e.ty);
+                e
+            })
+            .collect();
+        assert_eq!(
+            parse_mint_event(&events).unwrap().amount,
+            Uint128::new(1000000)
+        );
+        assert_eq!(
+            parse_create_denom_event(&events).unwrap().new_token_denom,
+            DENOM
+        );
+    }
+
+    #[test]
+    fn parse_missing_or_malformed() {
+        assert_eq!(parse_mint_event(&[]), None);
+
+        let events = vec![Event::new("tf_mint")
+            .add_attribute("mint_to_address", CREATOR)
+            .add_attribute("amount", DENOM)];
+        assert_eq!(parse_mint_event(&events), None);
+    }
+}
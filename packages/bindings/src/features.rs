@@ -0,0 +1,157 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::TokenMsg;
+
+/// Version of the feature set below. Chains answer with the version they implement,
+/// fields added in later versions read as unsupported from older chains.
+pub const FEATURES_VERSION: u32 = 2;
+
+/// The optional parts of the token factory a chain supports, as returned by
+/// `TokenQuery::Features`. Creating denoms, minting, burning from the sender and
+/// changing the admin are always supported.
+///
+/// Unlike the other responses this accepts unknown fields, and missing fields read
+/// as false, so contracts keep working when chains report newer features.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
+pub struct TokenFactoryFeatures {
+    /// `FEATURES_VERSION` of the chain, 0 if the chain doesn't report its features
+    pub version: u32,
+    /// `SetMetadata`, and metadata in `CreateDenom`
+    pub set_metadata: bool,
+    /// `ForceTransfer`
+    pub force_transfer: bool,
+    /// `SetBeforeSendHook`
+    pub before_send_hook: bool,
+    /// `BurnTokens` from other addresses than the sender
+    pub burn_from: bool,
+    /// `LockMetadata`, since version 2
+    pub lock_metadata: bool,
+}
+
+impl TokenFactoryFeatures {
+    /// What can be assumed of a chain which doesn't answer `TokenQuery::Features`:
+    /// only the messages every token factory supports
+    pub fn conservative() -> Self {
+        Self::default()
+    }
+
+    /// Whether the chain supports `msg`, judging by its type and options only
+    pub fn supports(&self, msg: &TokenMsg) -> bool {
+        match msg {
+            TokenMsg::CreateDenom { metadata, .. } => metadata.is_none() || self.set_metadata,
+            TokenMsg::ChangeAdmin { .. } | TokenMsg::MintTokens { .. } => true,
+            TokenMsg::BurnTokens {
+                burn_from_address, ..
+            } => burn_from_address.is_empty() || self.burn_from,
+            TokenMsg::SetMetadata { .. } => self.set_metadata,
+            TokenMsg::LockMetadata { .. } => self.lock_metadata,
+            TokenMsg::ForceTransfer { .. } => self.force_transfer,
+            TokenMsg::SetBeforeSendHook { .. } => self.before_send_hook,
+        }
+    }
+}
+
+/// The capability string, eg. `token_factory/v1:set_metadata,force_transfer`,
+/// for logs and attributes
+impl fmt::Display for TokenFactoryFeatures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let features: Vec<&str> = [
+            ("set_metadata", self.set_metadata),
+            ("force_transfer", self.force_transfer),
+            ("before_send_hook", self.before_send_hook),
+            ("burn_from", self.burn_from),
+            ("lock_metadata", self.lock_metadata),
+        ]
+        .iter()
+        .filter(|(_, supported)| *supported)
+        .map(|(name, _)| *name)
+        .collect();
+        write!(f, "token_factory/v{}:{}", self.version, features.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{from_slice, Uint128};
+
+    #[test]
+    fn forward_compatible() {
+        // newer chains may report features we don't know about
+        let features: TokenFactoryFeatures = from_slice(
+            br#"{"version":3,"set_metadata":true,"force_transfer":true,"clawback":true}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            features,
+            TokenFactoryFeatures {
+                version: 3,
+                set_metadata: true,
+                force_transfer: true,
+                before_send_hook: false,
+                burn_from: false,
+                lock_metadata: false,
+            }
+        );
+        assert_eq!(
+            from_slice::<TokenFactoryFeatures>(b"{}").unwrap(),
+            TokenFactoryFeatures::conservative()
+        );
+    }
+
+    #[test]
+    fn supports() {
+        let denom = "factory/osmo1creator/fundz".to_string();
+        let force_transfer = TokenMsg::ForceTransfer {
+            denom: denom.clone(),
+            amount: Uint128::new(1),
+            from_address: "osmo1from".to_string(),
+            to_address: "osmo1to".to_string(),
+        };
+        let burn_from = TokenMsg::BurnTokens {
+            denom: denom.clone(),
+            amount: Uint128::new(1),
+            burn_from_address: "osmo1holder".to_string(),
+        };
+        let burn = TokenMsg::burn_contract_tokens(denom.clone(), Uint128::new(1), "".to_string());
+        let mint = TokenMsg::mint_contract_tokens(denom, Uint128::new(1), "osmo1rcpt".to_string());
+
+        let conservative = TokenFactoryFeatures::conservative();
+        assert!(!conservative.supports(&force_transfer));
+        assert!(!conservative.supports(&burn_from));
+        assert!(conservative.supports(&burn));
+        assert!(conservative.supports(&mint));
+
+        let features = TokenFactoryFeatures {
+            version: FEATURES_VERSION,
+            force_transfer: true,
+            ..TokenFactoryFeatures::default()
+        };
+        assert!(features.supports(&force_transfer));
+        assert!(!features.supports(&burn_from));
+    }
+
+    #[test]
+    fn capability_string() {
+        let features = TokenFactoryFeatures {
+            version: 1,
+            set_metadata: true,
+            force_transfer: true,
+            before_send_hook: false,
+            burn_from: false,
+            lock_metadata: false,
+        };
+        assert_eq!(
+            features.to_string(),
+            "token_factory/v1:set_metadata,force_transfer"
+        );
+        assert_eq!(
+            TokenFactoryFeatures::conservative().to_string(),
+            "token_factory/v0:"
+        );
+    }
+}
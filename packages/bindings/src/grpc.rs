@@ -0,0 +1,402 @@
+//! Mapping of the token factory queries to the node's gRPC endpoints, for off-chain
+//! indexers reusing the bindings types. Requests and responses are encoded by hand,
+//! like `CreateDenomResponse`, to avoid pulling in a protobuf library.
+//!
+//! Only the queries backed by a chain endpoint convert to a `GrpcQuery`. The others
+//! (`FullDenom`, `HasMetadata`, `MetadataLocked`, `CreationFeeIn`, `CreationFeeOptions`,
+//! `CanMint`, `ParseDenom`, `DenomCreator`, `CreatorTotalSupply`, `SubdenomRules`, `Features`,
+//! `MetadataHistory`) have no gRPC endpoint. Chains answer them in their wasm query handler, if at all.
+
+use cosmwasm_std::{Coin, StdResult, Uint128};
+
+use crate::query::{
+    AdminResponse, DenomsByCreatorResponse, MetadataResponse, ParamsResponse, TokenQuery,
+};
+use crate::types::{DenomUnit, Metadata, Params};
+
+/// The token factory queries a node answers over gRPC
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GrpcQuery {
+    Metadata { denom: String },
+    Admin { denom: String },
+    DenomsByCreator { creator: String },
+    Params {},
+}
+
+impl TokenQuery {
+    /// The gRPC query answering this query, None if the chain has no endpoint for it
+    pub fn to_grpc(&self) -> Option<GrpcQuery> {
+        match self {
+            TokenQuery::Metadata { denom } => Some(GrpcQuery::Metadata {
+                denom: denom.clone(),
+            }),
+            TokenQuery::Admin { denom } => Some(GrpcQuery::Admin {
+                denom: denom.clone(),
+            }),
+            TokenQuery::DenomsByCreator { creator } => Some(GrpcQuery::DenomsByCreator {
+                creator: creator.clone(),
+            }),
+            TokenQuery::Params {} => Some(GrpcQuery::Params {}),
+            _ => None,
+        }
+    }
+}
+
+impl From<GrpcQuery> for TokenQuery {
+    fn from(query: GrpcQuery) -> Self {
+        match query {
+            GrpcQuery::Metadata { denom } => TokenQuery::Metadata { denom },
+            GrpcQuery::Admin { denom } => TokenQuery::Admin { denom },
+            GrpcQuery::DenomsByCreator { creator } => TokenQuery::DenomsByCreator { creator },
+            GrpcQuery::Params {} => TokenQuery::Params {},
+        }
+    }
+}
+
+impl GrpcQuery {
+    /// The gRPC method answering this query
+    pub fn grpc_path(&self) -> &'static str {
+        match self {
+            GrpcQuery::Metadata { .. } => "/cosmos.bank.v1beta1.Query/DenomMetadata",
+            GrpcQuery::Admin { .. } => "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata",
+            GrpcQuery::DenomsByCreator { .. } => {
+                "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator"
+            }
+            GrpcQuery::Params {} => "/osmosis.tokenfactory.v1beta1.Query/Params",
+        }
+    }
+
+    /// The protobuf encoded request for `grpc_path`
+    pub fn to_proto_bytes(&self) -> Vec<u8> {
+        match self {
+            GrpcQuery::Metadata { denom } | GrpcQuery::Admin { denom } => {
+                proto::encode_string(1, denom)
+            }
+            GrpcQuery::DenomsByCreator { creator } => proto::encode_string(1, creator),
+            GrpcQuery::Params {} => vec![],
+        }
+    }
+}
+
+impl AdminResponse {
+    /// Decodes a `QueryDenomAuthorityMetadataResponse`
+    pub fn from_proto_bytes(data: &[u8]) -> StdResult<Self> {
+        let mut admin = String::new();
+        for field in proto::decode(data)? {
+            // authority_metadata
+            if let (1, proto::Value::Bytes(metadata)) = field {
+                for field in proto::decode(&metadata)? {
+                    if let (1, value) = field {
+                        admin = value.into_string()?;
+                    }
+                }
+            }
+        }
+        Ok(AdminResponse { admin })
+    }
+}
+
+impl DenomsByCreatorResponse {
+    /// Decodes a `QueryDenomsFromCreatorResponse`
+    pub fn from_proto_bytes(data: &[u8]) -> StdResult<Self> {
+        let mut denoms = vec![];
+        for field in proto::decode(data)? {
+            if let (1, value) = field {
+                denoms.push(value.into_string()?);
+            }
+        }
+        Ok(DenomsByCreatorResponse { denoms })
+    }
+}
+
+impl ParamsResponse {
+    /// Decodes a `QueryParamsResponse`. Fields of newer chain versions are ignored.
+    pub fn from_proto_bytes(data: &[u8]) -> StdResult<Self> {
+        let mut denom_creation_fee = vec![];
+        for field in proto::decode(data)? {
+            if let (1, proto::Value::Bytes(params)) = field {
+                for field in proto::decode(&params)? {
+                    if let (1, proto::Value::Bytes(coin)) = field {
+                        denom_creation_fee.push(decode_coin(&coin)?);
+                    }
+                }
+            }
+        }
+        Ok(ParamsResponse {
+            params: Params {
+                denom_creation_fee,
+                enabled: true,
+            },
+        })
+    }
+}
+
+impl MetadataResponse {
+    /// Decodes a `QueryDenomMetadataResponse`. The node errors for unknown denoms,
+    /// so an empty response decodes as no metadata.
+    pub fn from_proto_bytes(data: &[u8]) -> StdResult<Self> {
+        let mut metadata = None;
+        for field in proto::decode(data)? {
+            if let (1, proto::Value::Bytes(bytes)) = field {
+                metadata = Some(decode_metadata(&bytes)?);
+            }
+        }
+        Ok(MetadataResponse { metadata })
+    }
+}
+
+fn decode_coin(data: &[u8]) -> StdResult<Coin> {
+    let mut coin = Coin {
+        denom: String::new(),
+        amount: Uint128::zero(),
+    };
+    for (field, value) in proto::decode(data)? {
+        match field {
+            1 => coin.denom = value.into_string()?,
+            2 => coin.amount = value.into_string()?.parse()?,
+            _ => {}
+        }
+    }
+    Ok(coin)
+}
+
+fn decode_metadata(data: &[u8]) -> StdResult<Metadata> {
+    // proto3 doesn't distinguish unset from empty strings, we map both to None
+    let non_empty = |s: String| if s.is_empty() { None } else { Some(s) };
+    let mut metadata = Metadata {
+        description: None,
+        denom_units: vec![],
+        base: None,
+        display: None,
+        name: None,
+        symbol: None,
+        uri: None,
+        uri_hash: None,
+    };
+    for (field, value) in proto::decode(data)? {
+        match field {
+            1 => metadata.description = non_empty(value.into_string()?),
+            2 => metadata
+                .denom_units
+                .push(decode_denom_unit(&value.into_bytes()?)?),
+            3 => metadata.base = non_empty(value.into_string()?),
+            4 => metadata.display = non_empty(value.into_string()?),
+            5 => metadata.name = non_empty(value.into_string()?),
+            6 => metadata.symbol = non_empty(value.into_string()?),
+            7 => metadata.uri = non_empty(value.into_string()?),
+            8 => metadata.uri_hash = non_empty(value.into_string()?),
+            _ => {}
+        }
+    }
+    Ok(metadata)
+}
+
+fn decode_denom_unit(data: &[u8]) -> StdResult<DenomUnit> {
+    let mut unit = DenomUnit {
+        denom: String::new(),
+        exponent: 0,
+        aliases: vec![],
+    };
+    for (field, value) in proto::decode(data)? {
+        match field {
+            1 => unit.denom = value.into_string()?,
+            2 => unit.exponent = value.into_u32()?,
+            3 => unit.aliases.push(value.into_string()?),
+            _ => {}
+        }
+    }
+    Ok(unit)
+}
+
+/// Just enough protobuf for the messages above
+mod proto {
+    use cosmwasm_std::{StdError, StdResult};
+    use std::convert::TryFrom;
+
+    const WIRE_TYPE_VARINT: u64 = 0;
+    const WIRE_TYPE_LENGTH_DELIMITED: u64 = 2;
+
+    pub enum Value {
+        Varint(u64),
+        Bytes(Vec<u8>),
+    }
+
+    impl Value {
+        pub fn into_bytes(self) -> StdResult<Vec<u8>> {
+            match self {
+                Value::Bytes(bytes) => Ok(bytes),
+                Value::Varint(_) => Err(wire_type_error()),
+            }
+        }
+
+        pub fn into_string(self) -> StdResult<String> {
+            Ok(String::from_utf8(self.into_bytes()?)?)
+        }
+
+        pub fn into_u32(self) -> StdResult<u32> {
+            match self {
+                Value::Varint(value) => u32::try_from(value)
+                    .map_err(|_| StdError::parse_err("u32", "protobuf varint out of range")),
+                Value::Bytes(_) => Err(wire_type_error()),
+            }
+        }
+    }
+
+    fn wire_type_error() -> StdError {
+        StdError::parse_err("protobuf", "unexpected wire type")
+    }
+
+    pub fn encode_string(field: u64, value: &str) -> Vec<u8> {
+        let mut data = vec![];
+        encode_varint(&mut data, field << 3 | WIRE_TYPE_LENGTH_DELIMITED);
+        encode_varint(&mut data, value.len() as u64);
+        data.extend_from_slice(value.as_bytes());
+        data
+    }
+
+    fn encode_varint(data: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            data.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        data.push(value as u8);
+    }
+
+    /// Splits a message into its (field number, value) pairs, in order
+    pub fn decode(mut data: &[u8]) -> StdResult<Vec<(u64, Value)>> {
+        let mut fields = vec![];
+        while !data.is_empty() {
+            let key = decode_varint(&mut data)?;
+            let value = match key & 0b111 {
+                WIRE_TYPE_VARINT => Value::Varint(decode_varint(&mut data)?),
+                WIRE_TYPE_LENGTH_DELIMITED => {
+                    let len = decode_varint(&mut data)? as usize;
+                    if data.len() < len {
+                        return Err(StdError::parse_err("protobuf", "message too short"));
+                    }
+                    let (bytes, rest) = data.split_at(len);
+                    data = rest;
+                    Value::Bytes(bytes.to_vec())
+                }
+                _ => return Err(wire_type_error()),
+            };
+            fields.push((key >> 3, value));
+        }
+        Ok(fields)
+    }
+
+    fn decode_varint(data: &mut &[u8]) -> StdResult<u64> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let byte = *data
+                .get(i)
+                .ok_or_else(|| StdError::parse_err("protobuf", "varint too short"))?;
+            value |= ((byte & 0x7f) as u64) << (i * 7);
+            if byte & 0x80 == 0 {
+                *data = &data[i + 1..];
+                return Ok(value);
+            }
+        }
+        Err(StdError::parse_err("protobuf", "varint too long"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::coin;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn requests() {
+        let query = TokenQuery::Admin {
+            denom: "factory/osmo1creator/fundz".to_string(),
+        }
+        .to_grpc()
+        .unwrap();
+        assert_eq!(
+            query.grpc_path(),
+            "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata"
+        );
+        assert_eq!(
+            query.to_proto_bytes(),
+            hex("0a1a666163746f72792f6f736d6f3163726561746f722f66756e647a")
+        );
+
+        let query = TokenQuery::Params {}.to_grpc().unwrap();
+        assert_eq!(
+            query.grpc_path(),
+            "/osmosis.tokenfactory.v1beta1.Query/Params"
+        );
+        assert_eq!(query.to_proto_bytes(), Vec::<u8>::new());
+        assert_eq!(TokenQuery::from(query), TokenQuery::Params {});
+
+        let query = TokenQuery::FullDenom {
+            creator_addr: "osmo1creator".to_string(),
+            subdenom: "fundz".to_string(),
+        };
+        assert_eq!(query.to_grpc(), None);
+    }
+
+    #[test]
+    fn admin_response() {
+        let res = AdminResponse::from_proto_bytes(&hex("0a0c0a0a6f736d6f3161646d696e")).unwrap();
+        assert_eq!(res.admin, "osmo1admin");
+
+        // renounced admin
+        let res = AdminResponse::from_proto_bytes(&hex("0a00")).unwrap();
+        assert_eq!(res.admin, "");
+    }
+
+    #[test]
+    fn denoms_by_creator_response() {
+        let data = hex("0a1a666163746f72792f6f736d6f3163726561746f722f66756e647a0a19666163746f72792f6f736d6f3163726561746f722f67656d73");
+        let res = DenomsByCreatorResponse::from_proto_bytes(&data).unwrap();
+        assert_eq!(
+            res.denoms,
+            vec!["factory/osmo1creator/fundz", "factory/osmo1creator/gems"]
+        );
+    }
+
+    #[test]
+    fn params_response() {
+        // also has denom_creation_gas_consume (field 2) of newer versions
+        let data =
+            hex("0a240a110a05756f736d6f120831303030303030300a0b0a0475696f6e120335303010c0843d");
+        let res = ParamsResponse::from_proto_bytes(&data).unwrap();
+        assert_eq!(
+            res.params.denom_creation_fee,
+            vec![coin(10_000_000, "uosmo"), coin(500, "uion")]
+        );
+    }
+
+    #[test]
+    fn metadata_response() {
+        let data = hex("0a6c121c0a1a666163746f72792f6f736d6f3163726561746f722f66756e647a120d0a0566756e647a10061a02667a1a1a666163746f72792f6f736d6f3163726561746f722f66756e647a220566756e647a2a0546756e647a320546554e445a3a0c697066733a2f2f66756e647a");
+        let res = MetadataResponse::from_proto_bytes(&data).unwrap();
+        let mut expected =
+            Metadata::two_unit("factory/osmo1creator/fundz", "fundz", 6, "FUNDZ", "Fundz");
+        expected.denom_units[1].aliases = vec!["fz".to_string()];
+        expected.uri = Some("ipfs://fundz".to_string());
+        assert_eq!(res.metadata, Some(expected));
+
+        assert_eq!(
+            MetadataResponse::from_proto_bytes(&[]).unwrap().metadata,
+            None
+        );
+    }
+
+    #[test]
+    fn malformed_responses() {
+        // truncated
+        AdminResponse::from_proto_bytes(&hex("0a0c0a0a6f736d6f")).unwrap_err();
+        // varint where a string is expected
+        DenomsByCreatorResponse::from_proto_bytes(&hex("0801")).unwrap_err();
+    }
+}
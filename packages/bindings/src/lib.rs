@@ -1,15 +1,59 @@
+mod admin;
+#[cfg(feature = "assetlist")]
+mod assetlist;
+mod bech32;
+#[cfg(feature = "storage")]
+mod cache;
+mod denom;
+mod error;
+pub mod events;
+mod features;
+#[cfg(feature = "grpc")]
+mod grpc;
 mod msg;
+#[cfg(feature = "neutron")]
+mod neutron;
+mod pagination;
+mod plan;
 mod querier;
 mod query;
+#[cfg(feature = "storage")]
+mod reply;
 mod types;
 
-pub use msg::{CreateDenomResponse, TokenFactoryMsg, TokenMsg};
+pub use admin::ChangeAdminCheck;
+#[cfg(feature = "storage")]
+pub use cache::CachedParams;
+pub use denom::{
+    derive_subdenom, is_factory_denom, same_creator, short_denom, validate_creator_prefix,
+    validate_full_denom, DenomParts, DENOM_PREFIX, MAX_SUBDENOM_LENGTH, SUBDENOM_CHARS,
+};
+pub use error::{DenomError, MetadataError, TokenBindingsError};
+pub use features::{TokenFactoryFeatures, FEATURES_VERSION};
+#[cfg(feature = "grpc")]
+pub use grpc::GrpcQuery;
+pub use msg::{
+    create_denoms_submsgs, BeforeSendHookMsg, CreateDenomResponse, TokenFactoryMsg, TokenMsg,
+};
+#[cfg(feature = "neutron")]
+pub use neutron::NeutronTokenMsg;
+pub use pagination::{
+    clamp_limit, next_start_after, PageRequest, PageResponse, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT,
+};
+pub use plan::{fixed_supply_token_msgs, CreateDenomPlan, InitialMint};
 pub use querier::TokenQuerier;
 pub use query::{
-    AdminResponse, DenomsByCreatorResponse, FullDenomResponse, MetadataResponse, ParamsResponse,
-    TokenFactoryQuery, TokenQuery,
+    AdminResponse, CanMintResponse, CreationFeeInResponse, CreationFeeOptionsResponse,
+    CreatorTotalSupplyResponse, DenomCreatorResponse, DenomsByCreatorResponse, FullDenomResponse,
+    HasMetadataResponse, MetadataHistoryResponse, MetadataLockedResponse, MetadataResponse,
+    ParamsResponse, ParseDenomResponse, SubdenomRulesResponse, TokenFactoryQuery, TokenQuery,
+};
+#[cfg(feature = "storage")]
+pub use reply::ReplyDispatcher;
+pub use types::{
+    has_sufficient_funds, metadata_diff, validate_metadata, DenomUnit, Metadata,
+    MetadataFieldChange, Params,
 };
-pub use types::{Metadata, Params};
 
 // This is a signal, such that any contract that imports these helpers will only run on
 // blockchains that support token_factory feature
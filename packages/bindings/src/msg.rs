@@ -1,6 +1,11 @@
+use crate::denom::validate_full_denom;
+use crate::error::TokenBindingsError;
 use crate::types::Metadata;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Binary, CosmosMsg, CustomMsg, StdResult, Uint128};
+use cosmwasm_std::{
+    to_vec, Addr, Binary, Coin, CosmosMsg, CustomMsg, OverflowError, OverflowOperation, StdError,
+    StdResult, SubMsg, Uint128,
+};
 
 /// A top-level Custom message for the token factory.
 /// It is embedded like this to easily allow adding other variants that are custom
@@ -15,7 +20,7 @@ pub enum TokenFactoryMsg {
 pub enum TokenMsg {
     /// CreateDenom creates a new factory denom, of denomination:
     /// factory/{creating contract bech32 address}/{Subdenom}
-    /// Subdenom can be of length at most 44 characters, in [0-9a-zA-Z./]
+    /// Subdenom can be of length at most 44 characters, in [0-9a-zA-Z.]
     /// Empty subdenoms are valid.
     /// The (creating contract address, subdenom) pair must be unique.
     /// The created denom's admin is the creating contract address,
@@ -40,6 +45,11 @@ pub enum TokenMsg {
         denom: String,
         amount: Uint128,
         mint_to_address: String,
+        /// Only understood by the multitest mock, leave it unset when targeting a chain.
+        /// A key already used for this denom makes the mint fail, to test
+        /// exactly-once semantics of contracts replaying messages.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dedup_key: Option<String>,
     },
     /// Contracts can burn native tokens for an existing factory denom
     /// that they are the admin of.
@@ -53,6 +63,39 @@ pub enum TokenMsg {
         denom: String,
         metadata: Metadata,
     },
+    /// LockMetadata freezes the metadata of the denom for good, any later SetMetadata fails.
+    /// Can only be called by the denom admin, and only on chains reporting the
+    /// `lock_metadata` feature.
+    LockMetadata {
+        denom: String,
+    },
+    /// ForceTransfer moves tokens of a factory denom between any two addresses,
+    /// without the owner's consent. Can only be called by the denom admin.
+    /// Whether this triggers the denom's before send hook depends on the chain.
+    ForceTransfer {
+        denom: String,
+        amount: Uint128,
+        from_address: String,
+        to_address: String,
+    },
+    /// SetBeforeSendHook registers a contract that is called (via sudo, with
+    /// `BeforeSendHookMsg`) before tokens of the denom are sent, and may reject the send.
+    /// An empty address removes the hook. Can only be called by the denom admin.
+    SetBeforeSendHook {
+        denom: String,
+        cosmwasm_address: String,
+    },
+}
+
+/// The sudo message the chain sends to a before send hook contract.
+/// Returning an error from the hook blocks the send.
+#[cw_serde]
+pub enum BeforeSendHookMsg {
+    BlockBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
 }
 
 impl TokenMsg {
@@ -61,6 +104,7 @@ impl TokenMsg {
             denom,
             amount,
             mint_to_address,
+            dedup_key: None,
         }
     }
 
@@ -75,6 +119,110 @@ impl TokenMsg {
             burn_from_address: "".to_string(), // burn_from_address is currently disabled.
         }
     }
+
+    /// Like `mint_contract_tokens`, for an already validated recipient
+    pub fn mint_to(denom: String, amount: Uint128, mint_to_address: &Addr) -> Self {
+        Self::mint_contract_tokens(denom, amount, mint_to_address.to_string())
+    }
+
+    /// Burns from another address than the sender, which chains only support with the
+    /// `burn_from` feature (see `TokenFactoryFeatures`). `burn_contract_tokens` always
+    /// burns from the sender.
+    pub fn burn_from(denom: String, amount: Uint128, burn_from_address: &Addr) -> Self {
+        TokenMsg::BurnTokens {
+            denom,
+            amount,
+            burn_from_address: burn_from_address.to_string(),
+        }
+    }
+
+    /// The JSON of this message as a contract emits it, ie. wrapped in `CosmosMsg::Custom`,
+    /// for tooling building the same messages off-chain
+    pub fn to_cosmos_json(&self) -> StdResult<String> {
+        let msg: CosmosMsg<TokenFactoryMsg> = self.clone().into();
+        String::from_utf8(to_vec(&msg)?).map_err(StdError::from)
+    }
+
+    /// Builds the mint messages for a list of (denom, amount, recipient).
+    /// Every entry is validated before any message is built, so one bad entry rejects the batch.
+    /// Entries with the same denom and recipient are merged into one message.
+    pub fn mint_batch(
+        mints: Vec<(String, Uint128, String)>,
+    ) -> Result<Vec<CosmosMsg<TokenFactoryMsg>>, TokenBindingsError> {
+        let mut merged: Vec<(String, Uint128, String)> = Vec::with_capacity(mints.len());
+        for (denom, amount, recipient) in mints {
+            validate_batch_entry(&denom, amount)?;
+            if recipient.is_empty() {
+                return Err(TokenBindingsError::EmptyAddress { denom });
+            }
+            match merged
+                .iter_mut()
+                .find(|(d, _, r)| *d == denom && *r == recipient)
+            {
+                Some(entry) => entry.1 = entry.1.checked_add(amount)?,
+                None => merged.push((denom, amount, recipient)),
+            }
+        }
+        Ok(merged
+            .into_iter()
+            .map(|(denom, amount, recipient)| {
+                Self::mint_contract_tokens(denom, amount, recipient).into()
+            })
+            .collect())
+    }
+
+    /// Builds the burn messages for a list of (denom, amount), burning from the contract.
+    /// Validated and merged per denom like `mint_batch`.
+    pub fn burn_batch(
+        burns: Vec<(String, Uint128)>,
+    ) -> Result<Vec<CosmosMsg<TokenFactoryMsg>>, TokenBindingsError> {
+        let mut merged: Vec<(String, Uint128)> = Vec::with_capacity(burns.len());
+        for (denom, amount) in burns {
+            validate_batch_entry(&denom, amount)?;
+            match merged.iter_mut().find(|(d, _)| *d == denom) {
+                Some(entry) => entry.1 = entry.1.checked_add(amount)?,
+                None => merged.push((denom, amount)),
+            }
+        }
+        Ok(merged
+            .into_iter()
+            .map(|(denom, amount)| Self::burn_contract_tokens(denom, amount, "".to_string()).into())
+            .collect())
+    }
+}
+
+/// Builds a CreateDenom SubMsg without metadata for each subdenom, replying on success
+/// with the ids `base_reply_id`, `base_reply_id + 1`, ... in the order of `subdenoms`.
+/// The reply with id `base_reply_id + i` is about `subdenoms[i]`.
+/// Fails with an overflow if the ids don't fit in a u64.
+pub fn create_denoms_submsgs(
+    subdenoms: &[String],
+    base_reply_id: u64,
+) -> Result<Vec<SubMsg<TokenFactoryMsg>>, TokenBindingsError> {
+    subdenoms
+        .iter()
+        .enumerate()
+        .map(|(i, subdenom)| {
+            let reply_id = base_reply_id
+                .checked_add(i as u64)
+                .ok_or_else(|| OverflowError::new(OverflowOperation::Add, base_reply_id, i))?;
+            let msg = TokenMsg::CreateDenom {
+                subdenom: subdenom.clone(),
+                metadata: None,
+            };
+            Ok(SubMsg::reply_on_success(msg, reply_id))
+        })
+        .collect()
+}
+
+fn validate_batch_entry(denom: &str, amount: Uint128) -> Result<(), TokenBindingsError> {
+    validate_full_denom(denom)?;
+    if amount.is_zero() {
+        return Err(TokenBindingsError::ZeroAmount {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
 }
 
 impl From<TokenMsg> for CosmosMsg<TokenFactoryMsg> {
@@ -101,9 +249,10 @@ impl CreateDenomResponse {
         Ok(CreateDenomResponse { new_token_denom })
     }
 
+    /// Encodes this as the chain does, so mocks can return it in the SubMsg data field
     pub fn encode(&self) -> StdResult<Binary> {
-        // TODO
-        Ok(b"".into())
+        let data = copied_from_cw_utils::encode_protobuf_string(&self.new_token_denom, 1);
+        Ok(data.into())
     }
 }
 
@@ -116,6 +265,19 @@ mod copied_from_cw_utils {
     // Up to 9 bytes of varints as a practical limit (https://github.com/multiformats/unsigned-varint#practical-maximum-of-9-bytes-for-security)
     const VARINT_MAX_BYTES: usize = 9;
 
+    /// Inverse of `parse_protobuf_string` (not part of cw_utils)
+    pub fn encode_protobuf_string(value: &str, field_number: u8) -> Vec<u8> {
+        let mut data = vec![(field_number << 3) | WIRE_TYPE_LENGTH_DELIMITED];
+        let mut len = value.len();
+        while len >= 0x80 {
+            data.push((len as u8 & 0x7f) | 0x80);
+            len >>= 7;
+        }
+        data.push(len as u8);
+        data.extend_from_slice(value.as_bytes());
+        data
+    }
+
     pub fn parse_protobuf_string(data: &mut Vec<u8>, field_number: u8) -> StdResult<String> {
         let str_field = parse_protobuf_length_prefixed(data, field_number)?;
         Ok(String::from_utf8(str_field)?)
@@ -201,3 +363,151 @@ mod copied_from_cw_utils {
         Ok(len as usize) // Gently fall back to the arch's max addressable size
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_denoms_with_sequential_reply_ids() {
+        let subdenoms = vec!["fundz".to_string(), "gems".to_string(), "coins".to_string()];
+        let msgs = create_denoms_submsgs(&subdenoms, 40).unwrap();
+        assert_eq!(msgs.len(), 3);
+        for (i, (msg, subdenom)) in msgs.iter().zip(&subdenoms).enumerate() {
+            let create = TokenMsg::CreateDenom {
+                subdenom: subdenom.clone(),
+                metadata: None,
+            };
+            assert_eq!(msg, &SubMsg::reply_on_success(create, 40 + i as u64));
+        }
+        assert_eq!(create_denoms_submsgs(&[], 40).unwrap(), vec![]);
+
+        // the last id is u64::MAX at most
+        let msgs = create_denoms_submsgs(&subdenoms, u64::MAX - 2).unwrap();
+        assert_eq!(msgs[2].id, u64::MAX);
+        let err = create_denoms_submsgs(&subdenoms, u64::MAX - 1).unwrap_err();
+        assert_eq!(
+            err,
+            TokenBindingsError::Overflow(OverflowError::new(
+                OverflowOperation::Add,
+                u64::MAX - 1,
+                2
+            ))
+        );
+    }
+
+    #[test]
+    fn create_denom_response_round_trip() {
+        // long enough to need a multi-byte length varint
+        let new_token_denom = format!("factory/{}/{}", "osmo1".repeat(30), "sub");
+        let response = CreateDenomResponse {
+            new_token_denom: new_token_denom.clone(),
+        };
+        let data = response.encode().unwrap();
+        assert_eq!(&data[..3], &[0x0a, 0xa2, 0x01]);
+
+        let decoded = CreateDenomResponse::from_reply_data(data).unwrap();
+        assert_eq!(decoded.new_token_denom, new_token_denom);
+    }
+
+    #[test]
+    fn addr_constructors() {
+        let denom = "factory/creator/fundz".to_string();
+        let amount = Uint128::new(1234);
+        let addr = Addr::unchecked("recipient");
+
+        assert_eq!(
+            TokenMsg::mint_to(denom.clone(), amount, &addr),
+            TokenMsg::mint_contract_tokens(denom.clone(), amount, addr.to_string())
+        );
+        assert_eq!(
+            TokenMsg::burn_from(denom.clone(), amount, &addr),
+            TokenMsg::BurnTokens {
+                denom,
+                amount,
+                burn_from_address: addr.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn mint_batch_merges_duplicates() {
+        let fundz = "factory/creator/fundz".to_string();
+        let gems = "factory/creator/gems".to_string();
+        let mints = vec![
+            (fundz.clone(), Uint128::new(10), "alice".to_string()),
+            (gems.clone(), Uint128::new(5), "alice".to_string()),
+            (fundz.clone(), Uint128::new(7), "bob".to_string()),
+            (fundz.clone(), Uint128::new(3), "alice".to_string()),
+        ];
+        let msgs = TokenMsg::mint_batch(mints).unwrap();
+        let expected: Vec<CosmosMsg<TokenFactoryMsg>> = vec![
+            TokenMsg::mint_contract_tokens(fundz.clone(), Uint128::new(13), "alice".to_string())
+                .into(),
+            TokenMsg::mint_contract_tokens(gems, Uint128::new(5), "alice".to_string()).into(),
+            TokenMsg::mint_contract_tokens(fundz, Uint128::new(7), "bob".to_string()).into(),
+        ];
+        assert_eq!(msgs, expected);
+
+        assert_eq!(TokenMsg::mint_batch(vec![]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn batch_rejects_any_invalid_entry() {
+        let fundz = "factory/creator/fundz".to_string();
+        let mints = vec![
+            (fundz.clone(), Uint128::new(10), "alice".to_string()),
+            (fundz.clone(), Uint128::zero(), "bob".to_string()),
+            (fundz.clone(), Uint128::new(3), "carl".to_string()),
+        ];
+        assert_eq!(
+            TokenMsg::mint_batch(mints).unwrap_err(),
+            TokenBindingsError::ZeroAmount {
+                denom: fundz.clone()
+            }
+        );
+
+        let mints = vec![(fundz.clone(), Uint128::MAX, "alice".to_string()); 2];
+        assert!(matches!(
+            TokenMsg::mint_batch(mints).unwrap_err(),
+            TokenBindingsError::Overflow(_)
+        ));
+
+        let burns = vec![
+            (fundz, Uint128::new(1)),
+            ("uosmo".to_string(), Uint128::new(1)),
+        ];
+        assert!(matches!(
+            TokenMsg::burn_batch(burns).unwrap_err(),
+            TokenBindingsError::Denom(_)
+        ));
+    }
+
+    #[test]
+    fn burn_batch_merges_duplicates() {
+        let fundz = "factory/creator/fundz".to_string();
+        let burns = vec![
+            (fundz.clone(), Uint128::new(10)),
+            (fundz.clone(), Uint128::new(5)),
+        ];
+        let msgs = TokenMsg::burn_batch(burns).unwrap();
+        let expected: Vec<CosmosMsg<TokenFactoryMsg>> =
+            vec![TokenMsg::burn_contract_tokens(fundz, Uint128::new(15), "".to_string()).into()];
+        assert_eq!(msgs, expected);
+
+        assert_eq!(TokenMsg::burn_batch(vec![]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn mint_cosmos_json() {
+        let msg = TokenMsg::mint_contract_tokens(
+            "factory/creator/fundz".to_string(),
+            Uint128::new(100),
+            "alice".to_string(),
+        );
+        assert_eq!(
+            msg.to_cosmos_json().unwrap(),
+            r#"{"custom":{"token":{"mint_tokens":{"denom":"factory/creator/fundz","amount":"100","mint_to_address":"alice"}}}}"#
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! The token factory messages in the wire format of Neutron's wasmbinding.
+//!
+//! Neutron has no `token` envelope: the messages are variants of its top-level custom
+//! message, next to its other bindings. Contracts targeting Neutron convert their
+//! `TokenMsg` into a `NeutronTokenMsg` and emit that as the custom message.
+//! Only the messages Neutron supports convert.
+
+use std::convert::TryFrom;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CosmosMsg, CustomMsg, Uint128};
+
+use crate::error::TokenBindingsError;
+use crate::msg::TokenMsg;
+
+/// The subset of Neutron's custom message handled by its token factory
+#[cw_serde]
+pub enum NeutronTokenMsg {
+    CreateDenom {
+        subdenom: String,
+    },
+    ChangeAdmin {
+        denom: String,
+        new_admin_address: String,
+    },
+    MintTokens {
+        denom: String,
+        amount: Uint128,
+        mint_to_address: String,
+    },
+    BurnTokens {
+        denom: String,
+        amount: Uint128,
+        burn_from_address: String,
+    },
+    SetBeforeSendHook {
+        denom: String,
+        contract_addr: String,
+    },
+}
+
+impl CustomMsg for NeutronTokenMsg {}
+
+impl From<NeutronTokenMsg> for CosmosMsg<NeutronTokenMsg> {
+    fn from(msg: NeutronTokenMsg) -> CosmosMsg<NeutronTokenMsg> {
+        CosmosMsg::Custom(msg)
+    }
+}
+
+impl TryFrom<TokenMsg> for NeutronTokenMsg {
+    type Error = TokenBindingsError;
+
+    /// Fails for messages, or options of them, that Neutron doesn't support
+    fn try_from(msg: TokenMsg) -> Result<Self, Self::Error> {
+        let unsupported = |msg: &str| TokenBindingsError::UnsupportedMsg {
+            msg: msg.to_string(),
+            chain: "neutron".to_string(),
+        };
+        match msg {
+            TokenMsg::CreateDenom {
+                subdenom,
+                metadata: None,
+            } => Ok(NeutronTokenMsg::CreateDenom { subdenom }),
+            TokenMsg::CreateDenom { .. } => Err(unsupported("create_denom with metadata")),
+            TokenMsg::ChangeAdmin {
+                denom,
+                new_admin_address,
+            } => Ok(NeutronTokenMsg::ChangeAdmin {
+                denom,
+                new_admin_address,
+            }),
+            TokenMsg::MintTokens {
+                denom,
+                amount,
+                mint_to_address,
+                dedup_key: None,
+            } => Ok(NeutronTokenMsg::MintTokens {
+                denom,
+                amount,
+                mint_to_address,
+            }),
+            TokenMsg::MintTokens { .. } => Err(unsupported("mint_tokens with dedup_key")),
+            TokenMsg::BurnTokens {
+                denom,
+                amount,
+                burn_from_address,
+            } => Ok(NeutronTokenMsg::BurnTokens {
+                denom,
+                amount,
+                burn_from_address,
+            }),
+            TokenMsg::SetMetadata { .. } => Err(unsupported("set_metadata")),
+            TokenMsg::LockMetadata { .. } => Err(unsupported("lock_metadata")),
+            TokenMsg::ForceTransfer { .. } => Err(unsupported("force_transfer")),
+            TokenMsg::SetBeforeSendHook {
+                denom,
+                cosmwasm_address,
+            } => Ok(NeutronTokenMsg::SetBeforeSendHook {
+                denom,
+                contract_addr: cosmwasm_address,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
+    use serde_json::{Map, Value};
+
+    const DENOM: &str = "factory/neutron1m9l358xunhhwds0568za49mzhvuxx9ux8xafx2/fundz";
+
+    /// The JSON schema of `NeutronMsg`, copied unchanged from neutron-sdk 0.8.0
+    /// (schema/neutron_msg.json)
+    const NEUTRON_MSG_SCHEMA: &str = include_str!("../testdata/neutron_msg.json");
+
+    fn neutron_schema() -> Value {
+        serde_json::from_str(NEUTRON_MSG_SCHEMA).unwrap()
+    }
+
+    /// The `oneOf` variants of `NeutronMsg` handled by its token factory, by name
+    fn token_factory_variants(schema: &Value) -> Map<String, Value> {
+        schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|variant| {
+                variant["description"]
+                    .as_str()
+                    .unwrap_or("")
+                    .starts_with("TokenFactory message.")
+            })
+            .map(|variant| {
+                let name = variant["required"][0].as_str().unwrap().to_string();
+                (name.clone(), variant["properties"][&name].clone())
+            })
+            .collect()
+    }
+
+    /// Checks `msg` has the fields of its variant in the schema and no others,
+    /// with the right JSON types, and returns the variant name
+    fn assert_matches_schema(schema: &Value, msg: &NeutronTokenMsg) -> String {
+        let json: Value = serde_json::from_slice(&to_vec(msg).unwrap()).unwrap();
+        let (name, fields) = json.as_object().unwrap().iter().next().unwrap();
+        let variant = &token_factory_variants(schema)[name];
+        let fields = fields.as_object().unwrap();
+
+        for required in variant["required"].as_array().unwrap() {
+            assert!(
+                fields.contains_key(required.as_str().unwrap()),
+                "{} misses {}",
+                name,
+                required
+            );
+        }
+        for (field, value) in fields {
+            let mut property = &variant["properties"][field];
+            assert!(!property.is_null(), "{} has unknown field {}", name, field);
+            if let Some(reference) = property["$ref"].as_str() {
+                let definition = reference.trim_start_matches("#/definitions/");
+                property = &schema["definitions"][definition];
+            }
+            // Uint128 is a string as well
+            assert_eq!(property["type"], "string", "{}.{}", name, field);
+            assert!(value.is_string(), "{}.{} is not a string", name, field);
+        }
+
+        assert_eq!(
+            from_slice::<NeutronTokenMsg>(&to_vec(msg).unwrap()).unwrap(),
+            *msg
+        );
+        name.clone()
+    }
+
+    #[test]
+    fn wire_format() {
+        let schema = neutron_schema();
+        let msgs = vec![
+            TokenMsg::CreateDenom {
+                subdenom: "fundz".to_string(),
+                metadata: None,
+            },
+            TokenMsg::ChangeAdmin {
+                denom: DENOM.to_string(),
+                new_admin_address: "neutron1admin".to_string(),
+            },
+            TokenMsg::mint_contract_tokens(
+                DENOM.to_string(),
+                Uint128::new(1000),
+                "neutron1rcpt".to_string(),
+            ),
+            TokenMsg::burn_contract_tokens(DENOM.to_string(), Uint128::new(10), "".to_string()),
+            TokenMsg::SetBeforeSendHook {
+                denom: DENOM.to_string(),
+                cosmwasm_address: "neutron1hook".to_string(),
+            },
+        ];
+        let names: Vec<String> = msgs
+            .into_iter()
+            .map(|msg| assert_matches_schema(&schema, &NeutronTokenMsg::try_from(msg).unwrap()))
+            .collect();
+
+        // every token factory message of Neutron is covered
+        let expected: Vec<String> = token_factory_variants(&schema).keys().cloned().collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn before_send_hook() {
+        let hook = TokenMsg::SetBeforeSendHook {
+            denom: DENOM.to_string(),
+            cosmwasm_address: "neutron1hook".to_string(),
+        };
+        assert_eq!(
+            NeutronTokenMsg::try_from(hook).unwrap(),
+            NeutronTokenMsg::SetBeforeSendHook {
+                denom: DENOM.to_string(),
+                contract_addr: "neutron1hook".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn unsupported_messages() {
+        let create = TokenMsg::CreateDenom {
+            subdenom: "fundz".to_string(),
+            metadata: Some(crate::Metadata::two_unit(
+                DENOM, "fundz", 6, "FUNDZ", "Fundz",
+            )),
+        };
+        assert_eq!(
+            NeutronTokenMsg::try_from(create).unwrap_err(),
+            TokenBindingsError::UnsupportedMsg {
+                msg: "create_denom with metadata".to_string(),
+                chain: "neutron".to_string(),
+            }
+        );
+        let force_transfer = TokenMsg::ForceTransfer {
+            denom: DENOM.to_string(),
+            amount: Uint128::new(10),
+            from_address: "neutron1from".to_string(),
+            to_address: "neutron1to".to_string(),
+        };
+        NeutronTokenMsg::try_from(force_transfer).unwrap_err();
+    }
+}
@@ -0,0 +1,131 @@
+//! Pagination shared by the list queries of contracts using these bindings,
+//! so every list pages the same way and with the same limits.
+
+use cosmwasm_schema::cw_serde;
+
+/// Page size when the request doesn't set a limit
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+/// Largest page returned, higher limits are lowered to this
+pub const MAX_PAGE_LIMIT: u32 = 30;
+
+/// Which page of a list to return
+#[cw_serde]
+#[derive(Default)]
+pub struct PageRequest {
+    /// Exclusive, the first page if None
+    pub start_after: Option<String>,
+    /// `DEFAULT_PAGE_LIMIT` if None, at most `MAX_PAGE_LIMIT`
+    pub limit: Option<u32>,
+}
+
+impl PageRequest {
+    /// The number of items to return for this request
+    pub fn limit(&self) -> usize {
+        clamp_limit(self.limit)
+    }
+}
+
+/// One page of a list
+#[cw_serde]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    /// Pass as `start_after` to get the next page, None on the last page
+    pub next_start_after: Option<String>,
+}
+
+impl<T> PageResponse<T> {
+    /// Builds the page from up to `limit + 1` items read from storage. The extra item
+    /// only tells whether there is a next page, so the last page is always recognized,
+    /// even when it is exactly `limit` items long.
+    pub fn new(mut items: Vec<T>, limit: usize, key: impl Fn(&T) -> String) -> Self {
+        let next_start_after = next_start_after(&items, limit, key);
+        items.truncate(limit);
+        PageResponse {
+            items,
+            next_start_after,
+        }
+    }
+}
+
+/// Applies the default and maximum page limit
+pub fn clamp_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize
+}
+
+/// The `start_after` of the page following `items`, given up to `limit + 1` items
+/// were read: the key of the last returned item if more follow, None otherwise
+pub fn next_start_after<T>(
+    items: &[T],
+    limit: usize,
+    key: impl Fn(&T) -> String,
+) -> Option<String> {
+    if items.len() > limit && limit > 0 {
+        Some(key(&items[limit - 1]))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(items: &[&str], limit: usize) -> PageResponse<String> {
+        let items = items.iter().map(|item| item.to_string()).collect();
+        PageResponse::new(items, limit, String::clone)
+    }
+
+    #[test]
+    fn limits() {
+        assert_eq!(PageRequest::default().limit(), 10);
+        assert_eq!(clamp_limit(Some(3)), 3);
+        assert_eq!(clamp_limit(Some(0)), 0);
+        assert_eq!(clamp_limit(Some(1000)), 30);
+    }
+
+    #[test]
+    fn empty_page() {
+        let res = page(&[], 2);
+        assert!(res.items.is_empty());
+        assert_eq!(res.next_start_after, None);
+    }
+
+    #[test]
+    fn exact_limit_page() {
+        // a third item was read, so there is a next page
+        let res = page(&["a", "b", "c"], 2);
+        assert_eq!(res.items, vec!["a", "b"]);
+        assert_eq!(res.next_start_after, Some("b".to_string()));
+    }
+
+    #[test]
+    fn terminal_page() {
+        // exactly `limit` items left
+        let res = page(&["a", "b"], 2);
+        assert_eq!(res.items, vec!["a", "b"]);
+        assert_eq!(res.next_start_after, None);
+
+        let res = page(&["a"], 2);
+        assert_eq!(res.items, vec!["a"]);
+        assert_eq!(res.next_start_after, None);
+    }
+
+    #[test]
+    fn zero_limit() {
+        let res = page(&["a"], 0);
+        assert!(res.items.is_empty());
+        assert_eq!(res.next_start_after, None);
+    }
+
+    #[test]
+    fn wire_format() {
+        let req: PageRequest = cosmwasm_std::from_slice(br#"{"limit":5}"#).unwrap();
+        assert_eq!(
+            req,
+            PageRequest {
+                start_after: None,
+                limit: Some(5)
+            }
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CosmosMsg, Reply, StdError, StdResult, SubMsg, Uint128};
+
+use crate::denom::{validate_full_denom, DENOM_PREFIX};
+use crate::error::TokenBindingsError;
+use crate::msg::{CreateDenomResponse, TokenFactoryMsg, TokenMsg};
+use crate::types::{validate_metadata, Metadata};
+
+/// Creates a denom, then sets its metadata and mints an initial supply.
+///
+/// The denom is only known once the CreateDenom reply comes back, so this is done in two steps:
+/// send `create_msg` as a SubMsg, store the plan, and call `handle_reply` from the contract's
+/// `reply` entry point to get the follow-up messages.
+#[cw_serde]
+pub struct CreateDenomPlan {
+    pub subdenom: String,
+    pub metadata: Option<Metadata>,
+    pub mint: Option<InitialMint>,
+    /// Whether the chain accepts metadata in CreateDenom. If so, it is sent there and
+    /// no SetMetadata follows.
+    pub metadata_in_create: bool,
+}
+
+#[cw_serde]
+pub struct InitialMint {
+    pub to: String,
+    pub amount: Uint128,
+}
+
+impl CreateDenomPlan {
+    pub fn new(subdenom: impl Into<String>) -> Self {
+        CreateDenomPlan {
+            subdenom: subdenom.into(),
+            metadata: None,
+            mint: None,
+            metadata_in_create: false,
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub fn with_mint(mut self, to: impl Into<String>, amount: Uint128) -> Self {
+        self.mint = Some(InitialMint {
+            to: to.into(),
+            amount,
+        });
+        self
+    }
+
+    pub fn with_metadata_in_create(mut self, supported: bool) -> Self {
+        self.metadata_in_create = supported;
+        self
+    }
+
+    /// The CreateDenom SubMsg, replying on success with the given id
+    pub fn create_msg(&self, reply_id: u64) -> SubMsg<TokenFactoryMsg> {
+        let metadata = if self.metadata_in_create {
+            self.metadata.clone()
+        } else {
+            None
+        };
+        let msg = TokenMsg::CreateDenom {
+            subdenom: self.subdenom.clone(),
+            metadata,
+        };
+        SubMsg::reply_on_success(msg, reply_id)
+    }
+
+    /// Parses the new denom from the CreateDenom reply and returns the remaining messages
+    pub fn handle_reply(&self, reply: Reply) -> StdResult<Vec<CosmosMsg<TokenFactoryMsg>>> {
+        let data = reply
+            .result
+            .into_result()
+            .map_err(StdError::generic_err)?
+            .data
+            .ok_or_else(|| StdError::generic_err("CreateDenom reply has no data"))?;
+        let denom = CreateDenomResponse::from_reply_data(data)?.new_token_denom;
+
+        let mut msgs = vec![];
+        if let Some(metadata) = &self.metadata {
+            if !self.metadata_in_create {
+                let msg = TokenMsg::SetMetadata {
+                    denom: denom.clone(),
+                    metadata: metadata.clone(),
+                };
+                msgs.push(msg.into());
+            }
+        }
+        if let Some(mint) = &self.mint {
+            let msg = TokenMsg::mint_contract_tokens(denom, mint.amount, mint.to.clone());
+            msgs.push(msg.into());
+        }
+        Ok(msgs)
+    }
+}
+
+/// The messages issuing an immutable token with a fixed supply, in order: create the denom,
+/// set its metadata, mint `total_supply` to `recipient` and renounce the admin, so nobody
+/// can mint, burn or change the metadata afterwards.
+///
+/// All messages go out in one response, so the denom can't be taken from the CreateDenom
+/// reply like in `CreateDenomPlan`. It is computed from `creator`, the address sending the
+/// messages (usually the contract), and `metadata` must already use it as base.
+/// As nothing can be fixed once the admin is gone, the metadata is validated here.
+pub fn fixed_supply_token_msgs(
+    creator: &str,
+    subdenom: &str,
+    metadata: Metadata,
+    total_supply: Uint128,
+    recipient: &str,
+) -> Result<Vec<CosmosMsg<TokenFactoryMsg>>, TokenBindingsError> {
+    let denom = format!("{}/{}/{}", DENOM_PREFIX, creator, subdenom);
+    validate_full_denom(&denom)?;
+    if metadata.base.as_deref() != Some(denom.as_str()) {
+        return Err(TokenBindingsError::MetadataBaseMismatch {
+            base: metadata.base,
+            denom,
+        });
+    }
+    validate_metadata(&metadata)?;
+    if total_supply.is_zero() {
+        return Err(TokenBindingsError::ZeroAmount { denom });
+    }
+    if recipient.is_empty() {
+        return Err(TokenBindingsError::EmptyAddress { denom });
+    }
+
+    Ok(vec![
+        TokenMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+            metadata: None,
+        }
+        .into(),
+        TokenMsg::SetMetadata {
+            denom: denom.clone(),
+            metadata,
+        }
+        .into(),
+        TokenMsg::mint_contract_tokens(denom.clone(), total_supply, recipient.to_string()).into(),
+        TokenMsg::ChangeAdmin {
+            denom,
+            new_admin_address: String::new(),
+        }
+        .into(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{SubMsgResponse, SubMsgResult};
+
+    const DENOM: &str = "factory/creator/fundz";
+
+    fn reply(id: u64) -> Reply {
+        let data = CreateDenomResponse {
+            new_token_denom: DENOM.to_string(),
+        }
+        .encode()
+        .unwrap();
+        Reply {
+            id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(data),
+            }),
+        }
+    }
+
+    fn metadata() -> Metadata {
+        Metadata::two_unit("ufundz", "fundz", 6, "FUNDZ", "Fundz")
+    }
+
+    #[test]
+    fn metadata_and_mint_follow_creation() {
+        let plan = CreateDenomPlan::new("fundz")
+            .with_metadata(metadata())
+            .with_mint("rcpt", Uint128::new(100));
+
+        let submsg = plan.create_msg(7);
+        assert_eq!(submsg.id, 7);
+        assert_eq!(
+            submsg.msg,
+            TokenMsg::CreateDenom {
+                subdenom: "fundz".to_string(),
+                metadata: None,
+            }
+            .into()
+        );
+
+        let msgs = plan.handle_reply(reply(7)).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                TokenMsg::SetMetadata {
+                    denom: DENOM.to_string(),
+                    metadata: metadata(),
+                }
+                .into(),
+                TokenMsg::mint_contract_tokens(
+                    DENOM.to_string(),
+                    Uint128::new(100),
+                    "rcpt".to_string()
+                )
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_in_create_skips_set_metadata() {
+        let plan = CreateDenomPlan::new("fundz")
+            .with_metadata(metadata())
+            .with_metadata_in_create(true);
+
+        assert_eq!(
+            plan.create_msg(1).msg,
+            TokenMsg::CreateDenom {
+                subdenom: "fundz".to_string(),
+                metadata: Some(metadata()),
+            }
+            .into()
+        );
+        assert_eq!(plan.handle_reply(reply(1)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn reply_without_data_errors() {
+        let plan = CreateDenomPlan::new("fundz");
+        let reply = Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        };
+        plan.handle_reply(reply).unwrap_err();
+    }
+
+    #[test]
+    fn fixed_supply_token() {
+        let metadata = Metadata::two_unit(DENOM, "fundz", 6, "FUNDZ", "Fundz");
+        let msgs = fixed_supply_token_msgs(
+            "creator",
+            "fundz",
+            metadata.clone(),
+            Uint128::new(1000),
+            "rcpt",
+        )
+        .unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                TokenMsg::CreateDenom {
+                    subdenom: "fundz".to_string(),
+                    metadata: None,
+                }
+                .into(),
+                TokenMsg::SetMetadata {
+                    denom: DENOM.to_string(),
+                    metadata,
+                }
+                .into(),
+                TokenMsg::mint_contract_tokens(
+                    DENOM.to_string(),
+                    Uint128::new(1000),
+                    "rcpt".to_string()
+                )
+                .into(),
+                TokenMsg::ChangeAdmin {
+                    denom: DENOM.to_string(),
+                    new_admin_address: "".to_string(),
+                }
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn fixed_supply_token_rejects_what_cant_be_fixed_later() {
+        let metadata = || Metadata::two_unit(DENOM, "fundz", 6, "FUNDZ", "Fundz");
+        let supply = Uint128::new(1000);
+
+        // metadata of another denom
+        let err =
+            fixed_supply_token_msgs("creator", "gems", metadata(), supply, "rcpt").unwrap_err();
+        assert_eq!(
+            err,
+            TokenBindingsError::MetadataBaseMismatch {
+                base: Some(DENOM.to_string()),
+                denom: "factory/creator/gems".to_string(),
+            }
+        );
+        // invalid metadata
+        let mut unnamed = metadata();
+        unnamed.name = None;
+        let err = fixed_supply_token_msgs("creator", "fundz", unnamed, supply, "rcpt").unwrap_err();
+        assert!(matches!(err, TokenBindingsError::Metadata(_)));
+        // nothing minted
+        let err = fixed_supply_token_msgs("creator", "fundz", metadata(), Uint128::zero(), "rcpt")
+            .unwrap_err();
+        assert!(matches!(err, TokenBindingsError::ZeroAmount { .. }));
+        // nobody to mint to
+        let err = fixed_supply_token_msgs("creator", "fundz", metadata(), supply, "").unwrap_err();
+        assert!(matches!(err, TokenBindingsError::EmptyAddress { .. }));
+    }
+}
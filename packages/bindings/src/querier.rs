@@ -1,12 +1,25 @@
-use cosmwasm_std::{QuerierWrapper, StdResult};
+use cosmwasm_std::{
+    from_binary, to_vec, Addr, Coin, ContractResult, QuerierWrapper, QueryRequest, StdError,
+    StdResult, SystemError, SystemResult, Uint128,
+};
 
-use crate::query::{FullDenomResponse, TokenFactoryQuery, TokenQuery};
+use crate::denom::{validate_full_denom, DenomParts};
+use crate::features::TokenFactoryFeatures;
+use crate::query::{
+    AdminResponse, CanMintResponse, CreationFeeInResponse, CreationFeeOptionsResponse,
+    CreatorTotalSupplyResponse, DenomCreatorResponse, DenomsByCreatorResponse, FullDenomResponse,
+    HasMetadataResponse, MetadataHistoryResponse, MetadataLockedResponse, MetadataResponse,
+    ParamsResponse, ParseDenomResponse, SubdenomRulesResponse, TokenFactoryQuery, TokenQuery,
+};
+use crate::types::{DenomUnit, Metadata};
 
 /// This is a helper wrapper to easily use our custom queries
 pub struct TokenQuerier<'a> {
     querier: &'a QuerierWrapper<'a, TokenFactoryQuery>,
 }
 
+/// The methods take `impl Into<String>`, so `&str`, `&String` and `&Addr` can be passed
+/// without cloning at the call site. The query owns its strings, so they are copied once.
 impl<'a> TokenQuerier<'a> {
     pub fn new(querier: &'a QuerierWrapper<TokenFactoryQuery>) -> Self {
         TokenQuerier { querier }
@@ -14,13 +27,555 @@ impl<'a> TokenQuerier<'a> {
 
     pub fn full_denom(
         &self,
-        creator_addr: String,
-        subdenom: String,
+        creator_addr: impl Into<String>,
+        subdenom: impl Into<String>,
     ) -> StdResult<FullDenomResponse> {
         let full_denom_query = TokenQuery::FullDenom {
-            creator_addr,
-            subdenom,
+            creator_addr: creator_addr.into(),
+            subdenom: subdenom.into(),
         };
         self.querier.query(&full_denom_query.into())
     }
+
+    pub fn metadata(&self, denom: impl Into<String>) -> StdResult<MetadataResponse> {
+        let metadata_query = TokenQuery::Metadata {
+            denom: denom.into(),
+        };
+        self.querier.query(&metadata_query.into())
+    }
+
+    /// Whether the denom has metadata, cheaper than `metadata` if that is all you need
+    pub fn has_metadata(&self, denom: impl Into<String>) -> StdResult<bool> {
+        let has_metadata_query = TokenQuery::HasMetadata {
+            denom: denom.into(),
+        };
+        let res: HasMetadataResponse = self.querier.query(&has_metadata_query.into())?;
+        Ok(res.has_metadata)
+    }
+
+    /// Whether the metadata of the denom was locked by `TokenMsg::LockMetadata`
+    pub fn metadata_locked(&self, denom: impl Into<String>) -> StdResult<bool> {
+        let metadata_locked_query = TokenQuery::MetadataLocked {
+            denom: denom.into(),
+        };
+        let res: MetadataLockedResponse = self.querier.query(&metadata_locked_query.into())?;
+        Ok(res.locked)
+    }
+
+    /// Only answered by the mock in token-bindings-test, see `TokenQuery::MetadataHistory`
+    pub fn metadata_history(&self, denom: impl Into<String>) -> StdResult<MetadataHistoryResponse> {
+        let metadata_history_query = TokenQuery::MetadataHistory {
+            denom: denom.into(),
+        };
+        self.querier.query(&metadata_history_query.into())
+    }
+
+    /// Returns the metadata set on chain, or, if there is none, synthesizes a minimal one
+    /// with the denom as base, display and name, and the upper-cased subdenom as symbol
+    /// (upper-cased denom for non-factory denoms).
+    ///
+    /// The result may thus not reflect chain state, use `metadata` if you need to know that.
+    pub fn metadata_or_default(&self, denom: impl Into<String>) -> StdResult<Metadata> {
+        let denom = denom.into();
+        if let Some(metadata) = self.metadata(&denom)?.metadata {
+            return Ok(metadata);
+        }
+        let symbol = match DenomParts::parse(&denom) {
+            Ok(parts) => parts.subdenom.to_uppercase(),
+            Err(_) => denom.to_uppercase(),
+        };
+        Ok(Metadata {
+            description: None,
+            denom_units: vec![DenomUnit {
+                denom: denom.clone(),
+                exponent: 0,
+                aliases: vec![],
+            }],
+            base: Some(denom.clone()),
+            display: Some(denom.clone()),
+            name: Some(denom),
+            symbol: Some(symbol),
+            uri: None,
+            uri_hash: None,
+        })
+    }
+
+    /// Errors if the denom doesn't exist or was not created by the token factory
+    pub fn admin(&self, denom: impl Into<String>) -> StdResult<AdminResponse> {
+        let admin_query = TokenQuery::Admin {
+            denom: denom.into(),
+        };
+        self.querier.query(&admin_query.into())
+    }
+
+    /// Whether `addr` is the current admin of `denom`. False if the denom has another admin
+    /// or none (renounced), and also if it doesn't exist or is not a token factory denom,
+    /// as the chain rejects the admin query for those.
+    /// Only system errors, like a chain without token factory queries, are returned as errors.
+    pub fn admin_is(&self, denom: impl Into<String>, addr: &Addr) -> StdResult<bool> {
+        let request: QueryRequest<TokenFactoryQuery> = TokenQuery::Admin {
+            denom: denom.into(),
+        }
+        .into();
+        let raw = to_vec(&request)?;
+        match self.querier.raw_query(&raw) {
+            SystemResult::Ok(ContractResult::Ok(value)) => {
+                let res: AdminResponse = from_binary(&value)?;
+                // renounced denoms have an empty admin, which nobody is
+                Ok(!res.admin.is_empty() && res.admin == addr.as_str())
+            }
+            SystemResult::Ok(ContractResult::Err(_)) => Ok(false),
+            SystemResult::Err(err) => Err(StdError::generic_err(format!(
+                "Querier system error: {}",
+                err
+            ))),
+        }
+    }
+
+    /// Errors unless `denom` is a token factory denom whose creator is `expected_creator`.
+    /// This only parses the denom, use `assert_created_by_existing` to also confirm
+    /// the chain created it.
+    pub fn assert_created_by(&self, denom: &str, expected_creator: &Addr) -> StdResult<()> {
+        let parts = validate_full_denom(denom)
+            .map_err(|err| StdError::generic_err(format!("Invalid denom {}: {}", denom, err)))?;
+        if parts.creator != expected_creator.as_str() {
+            return Err(StdError::generic_err(format!(
+                "Denom {} was created by {}, not {}",
+                denom, parts.creator, expected_creator
+            )));
+        }
+        Ok(())
+    }
+
+    /// Like `assert_created_by`, and confirms the denom exists with a `DenomsByCreator` query
+    pub fn assert_created_by_existing(
+        &self,
+        denom: &str,
+        expected_creator: &Addr,
+    ) -> StdResult<()> {
+        self.assert_created_by(denom, expected_creator)?;
+        let denoms_query = TokenQuery::DenomsByCreator {
+            creator: expected_creator.to_string(),
+        };
+        let res: DenomsByCreatorResponse = self.querier.query(&denoms_query.into())?;
+        if !res.denoms.iter().any(|d| d == denom) {
+            return Err(StdError::generic_err(format!(
+                "Denom {} was never created",
+                denom
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn params(&self) -> StdResult<ParamsResponse> {
+        let params_query = TokenQuery::Params {};
+        self.querier.query(&params_query.into())
+    }
+
+    pub fn creator_total_supply(
+        &self,
+        creator: impl Into<String>,
+    ) -> StdResult<CreatorTotalSupplyResponse> {
+        let creator_total_supply_query = TokenQuery::CreatorTotalSupply {
+            creator: creator.into(),
+        };
+        self.querier.query(&creator_total_supply_query.into())
+    }
+
+    /// Returns the balance of `holder` in each of `creator_denoms` (eg. the denoms of a
+    /// `DenomsByCreatorResponse`), in the same order, including zero balances.
+    /// Issues one bank query per denom.
+    pub fn resolve_balances(
+        &self,
+        creator_denoms: &[String],
+        holder: impl Into<String>,
+    ) -> StdResult<Vec<Coin>> {
+        let holder = holder.into();
+        creator_denoms
+            .iter()
+            .map(|denom| self.querier.query_balance(&holder, denom))
+            .collect()
+    }
+
+    /// The optional features of this chain. Chains which don't answer the query,
+    /// for whatever reason, are assumed to have none of them.
+    pub fn features(&self) -> TokenFactoryFeatures {
+        self.querier
+            .query(&TokenQuery::Features {}.into())
+            .unwrap_or_else(|_| TokenFactoryFeatures::conservative())
+    }
+
+    /// The subdenom rules of this chain, use `SubdenomRulesResponse::allows` to check one
+    pub fn subdenom_rules(&self) -> StdResult<SubdenomRulesResponse> {
+        let subdenom_rules_query = TokenQuery::SubdenomRules {};
+        self.querier.query(&subdenom_rules_query.into())
+    }
+
+    /// Like `validate_full_denom`, but done by the chain
+    pub fn parse_denom(&self, denom: impl Into<String>) -> StdResult<ParseDenomResponse> {
+        let parse_denom_query = TokenQuery::ParseDenom {
+            denom: denom.into(),
+        };
+        self.querier.query(&parse_denom_query.into())
+    }
+
+    /// The original creator of the denom, None if it wasn't created by the token factory
+    pub fn denom_creator(&self, denom: impl Into<String>) -> StdResult<Option<String>> {
+        let denom_creator_query = TokenQuery::DenomCreator {
+            denom: denom.into(),
+        };
+        let res: DenomCreatorResponse = self.querier.query(&denom_creator_query.into())?;
+        Ok(res.creator)
+    }
+
+    /// Probes whether this chain supports the token factory queries at all, with a
+    /// Params query. Chains without custom queries answer with an unsupported or unknown
+    /// request error, which maps to false. Any other failure is returned as an error.
+    ///
+    /// Limitations: a chain with custom queries for other bindings may reject the query
+    /// as a malformed request instead, which is returned as an error. And a chain
+    /// supporting Params is assumed to support all other token factory queries.
+    pub fn is_supported(&self) -> StdResult<bool> {
+        let request: QueryRequest<TokenFactoryQuery> = TokenQuery::Params {}.into();
+        let raw = to_vec(&request)?;
+        match self.querier.raw_query(&raw) {
+            SystemResult::Ok(ContractResult::Ok(_)) => Ok(true),
+            SystemResult::Ok(ContractResult::Err(err)) => Err(StdError::generic_err(format!(
+                "Querier contract error: {}",
+                err
+            ))),
+            SystemResult::Err(SystemError::UnsupportedRequest { .. })
+            | SystemResult::Err(SystemError::Unknown {}) => Ok(false),
+            SystemResult::Err(err) => Err(StdError::generic_err(format!(
+                "Querier system error: {}",
+                err
+            ))),
+        }
+    }
+
+    pub fn creation_fee_in(&self, denom: impl Into<String>) -> StdResult<CreationFeeInResponse> {
+        let creation_fee_in_query = TokenQuery::CreationFeeIn {
+            denom: denom.into(),
+        };
+        self.querier.query(&creation_fee_in_query.into())
+    }
+
+    /// Every creation fee this chain accepts. Chains which don't answer the query
+    /// take the single fee of their params.
+    pub fn creation_fee_options(&self) -> StdResult<Vec<Vec<Coin>>> {
+        let query = TokenQuery::CreationFeeOptions {}.into();
+        match self.querier.query::<CreationFeeOptionsResponse>(&query) {
+            Ok(res) => Ok(res.options),
+            Err(_) => Ok(vec![self.params()?.params.denom_creation_fee]),
+        }
+    }
+
+    pub fn can_mint(
+        &self,
+        denom: impl Into<String>,
+        minter: impl Into<String>,
+        amount: Uint128,
+    ) -> StdResult<CanMintResponse> {
+        let can_mint_query = TokenQuery::CanMint {
+            denom: denom.into(),
+            minter: minter.into(),
+            amount,
+        };
+        self.querier.query(&can_mint_query.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Params;
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{coin, to_binary, ContractResult, SystemResult};
+
+    const FACTORY_DENOM: &str = "factory/osmo1creator/fundz";
+
+    fn mock_querier() -> MockQuerier<TokenFactoryQuery> {
+        MockQuerier::new(&[]).with_custom_handler(|query| {
+            let TokenFactoryQuery::Token(query) = query;
+            let res = match query {
+                TokenQuery::Metadata { denom } if denom == "factory/osmo1creator/meta" => {
+                    MetadataResponse {
+                        metadata: Some(Metadata::two_unit("ufundz", "fundz", 6, "FUN", "Fundz")),
+                    }
+                }
+                TokenQuery::Metadata { .. } => MetadataResponse { metadata: None },
+                _ => unimplemented!(),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+        })
+    }
+
+    #[test]
+    fn metadata_or_default_passes_through() {
+        let mock = mock_querier();
+        let wrapper = QuerierWrapper::new(&mock);
+        let querier = TokenQuerier::new(&wrapper);
+
+        let metadata = querier
+            .metadata_or_default("factory/osmo1creator/meta".to_string())
+            .unwrap();
+        assert_eq!(
+            metadata,
+            Metadata::two_unit("ufundz", "fundz", 6, "FUN", "Fundz")
+        );
+    }
+
+    #[test]
+    fn metadata_or_default_synthesizes() {
+        let mock = mock_querier();
+        let wrapper = QuerierWrapper::new(&mock);
+        let querier = TokenQuerier::new(&wrapper);
+
+        let metadata = querier
+            .metadata_or_default(FACTORY_DENOM.to_string())
+            .unwrap();
+        assert_eq!(metadata.symbol, Some("FUNDZ".to_string()));
+        assert_eq!(metadata.base, Some(FACTORY_DENOM.to_string()));
+        assert_eq!(metadata.display, Some(FACTORY_DENOM.to_string()));
+        assert_eq!(metadata.name, Some(FACTORY_DENOM.to_string()));
+        assert_eq!(
+            metadata.denom_units,
+            vec![DenomUnit {
+                denom: FACTORY_DENOM.to_string(),
+                exponent: 0,
+                aliases: vec![],
+            }]
+        );
+
+        // non-factory denoms use the full denom as symbol
+        let metadata = querier.metadata_or_default("uosmo".to_string()).unwrap();
+        assert_eq!(metadata.symbol, Some("UOSMO".to_string()));
+        assert_eq!(metadata.base, Some("uosmo".to_string()));
+    }
+
+    #[test]
+    fn admin_is_propagates_system_errors() {
+        // a chain without token factory queries
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]);
+        let wrapper = QuerierWrapper::new(&mock);
+        let err = TokenQuerier::new(&wrapper)
+            .admin_is(FACTORY_DENOM, &Addr::unchecked("osmo1creator"))
+            .unwrap_err();
+        assert!(err.to_string().contains("Querier system error"), "{}", err);
+    }
+
+    #[test]
+    fn is_supported() {
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(|_| {
+            let res = ParamsResponse {
+                params: Params::default(),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+        });
+        let wrapper = QuerierWrapper::new(&mock);
+        assert!(TokenQuerier::new(&wrapper).is_supported().unwrap());
+
+        // by default, MockQuerier rejects custom queries like a chain without bindings
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]);
+        let wrapper = QuerierWrapper::new(&mock);
+        assert!(!TokenQuerier::new(&wrapper).is_supported().unwrap());
+
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[])
+            .with_custom_handler(|_| SystemResult::Err(SystemError::Unknown {}));
+        let wrapper = QuerierWrapper::new(&mock);
+        assert!(!TokenQuerier::new(&wrapper).is_supported().unwrap());
+
+        // other failures are not hidden
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(|_| {
+            SystemResult::Err(SystemError::InvalidRequest {
+                error: "unknown variant".to_string(),
+                request: Default::default(),
+            })
+        });
+        let wrapper = QuerierWrapper::new(&mock);
+        TokenQuerier::new(&wrapper).is_supported().unwrap_err();
+    }
+
+    #[test]
+    fn assert_created_by() {
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(|query| {
+            let TokenFactoryQuery::Token(query) = query;
+            let denoms = match query {
+                TokenQuery::DenomsByCreator { creator } if creator == "osmo1creator" => {
+                    vec![FACTORY_DENOM.to_string()]
+                }
+                TokenQuery::DenomsByCreator { .. } => vec![],
+                _ => unimplemented!(),
+            };
+            let res = DenomsByCreatorResponse { denoms };
+            SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+        });
+        let wrapper = QuerierWrapper::new(&mock);
+        let querier = TokenQuerier::new(&wrapper);
+        let creator = Addr::unchecked("osmo1creator");
+
+        querier.assert_created_by(FACTORY_DENOM, &creator).unwrap();
+        querier
+            .assert_created_by_existing(FACTORY_DENOM, &creator)
+            .unwrap();
+
+        // another creator
+        let err = querier
+            .assert_created_by(FACTORY_DENOM, &Addr::unchecked("osmo1attacker"))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("created by osmo1creator"),
+            "{}",
+            err
+        );
+        // a denom of the same name from another creator
+        querier
+            .assert_created_by("factory/osmo1attacker/fundz", &creator)
+            .unwrap_err();
+        // not factory denoms
+        querier.assert_created_by("uosmo", &creator).unwrap_err();
+        querier
+            .assert_created_by("factory/osmo1creator/fundz/x", &creator)
+            .unwrap_err();
+
+        // well-formed, but never created
+        querier
+            .assert_created_by("factory/osmo1creator/gems", &creator)
+            .unwrap();
+        querier
+            .assert_created_by_existing("factory/osmo1creator/gems", &creator)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn accepts_borrowed_arguments() {
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(|query| {
+            let TokenFactoryQuery::Token(query) = query;
+            let res = match query {
+                TokenQuery::FullDenom {
+                    creator_addr,
+                    subdenom,
+                } => FullDenomResponse {
+                    denom: format!("factory/{}/{}", creator_addr, subdenom),
+                },
+                _ => unimplemented!(),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+        });
+        let wrapper = QuerierWrapper::new(&mock);
+        let querier = TokenQuerier::new(&wrapper);
+
+        // resolving many denoms of the same creator needs no clones at the call site
+        let creator = Addr::unchecked("osmo1creator");
+        let subdenoms: Vec<String> = (0..50).map(|i| format!("sub{}", i)).collect();
+        for subdenom in &subdenoms {
+            let res = querier.full_denom(&creator, subdenom).unwrap();
+            assert_eq!(res.denom, format!("factory/osmo1creator/{}", subdenom));
+        }
+
+        // owned strings still work
+        let res = querier
+            .full_denom("osmo1creator".to_string(), "fundz".to_string())
+            .unwrap();
+        assert_eq!(res.denom, FACTORY_DENOM);
+    }
+
+    #[test]
+    fn resolve_balances() {
+        let gems = "factory/osmo1creator/gems";
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[(
+            "osmo1holder",
+            &[coin(100, FACTORY_DENOM), coin(7, gems), coin(5, "uosmo")],
+        )]);
+        let wrapper = QuerierWrapper::new(&mock);
+        let querier = TokenQuerier::new(&wrapper);
+
+        let denoms = vec![
+            FACTORY_DENOM.to_string(),
+            gems.to_string(),
+            "factory/osmo1creator/none".to_string(),
+        ];
+        let balances = querier.resolve_balances(&denoms, "osmo1holder").unwrap();
+        assert_eq!(
+            balances,
+            vec![
+                coin(100, FACTORY_DENOM),
+                coin(7, gems),
+                coin(0, "factory/osmo1creator/none"),
+            ]
+        );
+        assert_eq!(
+            querier.resolve_balances(&[], "osmo1holder").unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn features_fall_back() {
+        let features = TokenFactoryFeatures {
+            version: 1,
+            set_metadata: true,
+            force_transfer: true,
+            before_send_hook: false,
+            burn_from: false,
+            lock_metadata: false,
+        };
+        let res = features.clone();
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(move |_| {
+            SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+        });
+        let wrapper = QuerierWrapper::new(&mock);
+        assert_eq!(TokenQuerier::new(&wrapper).features(), features);
+
+        // a chain without the query
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(|_| {
+            SystemResult::Err(SystemError::InvalidRequest {
+                error: "unknown variant `features`".to_string(),
+                request: Default::default(),
+            })
+        });
+        let wrapper = QuerierWrapper::new(&mock);
+        assert_eq!(
+            TokenQuerier::new(&wrapper).features(),
+            TokenFactoryFeatures::conservative()
+        );
+    }
+
+    #[test]
+    fn creation_fee_options_fall_back() {
+        let options = vec![vec![coin(100, "uosmo")], vec![coin(5, "uion")]];
+        let res = CreationFeeOptionsResponse {
+            options: options.clone(),
+        };
+        let mock = MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(move |_| {
+            SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+        });
+        let wrapper = QuerierWrapper::new(&mock);
+        assert_eq!(
+            TokenQuerier::new(&wrapper).creation_fee_options().unwrap(),
+            options
+        );
+
+        // a chain without the query has the fee of its params only
+        let mock =
+            MockQuerier::<TokenFactoryQuery>::new(&[]).with_custom_handler(|query| match query {
+                TokenFactoryQuery::Token(TokenQuery::Params {}) => {
+                    let params = Params {
+                        denom_creation_fee: vec![coin(100, "uosmo")],
+                        enabled: true,
+                    };
+                    let res = to_binary(&ParamsResponse { params }).unwrap();
+                    SystemResult::Ok(ContractResult::Ok(res))
+                }
+                _ => SystemResult::Err(SystemError::InvalidRequest {
+                    error: "unknown variant `creation_fee_options`".to_string(),
+                    request: Default::default(),
+                }),
+            });
+        let wrapper = QuerierWrapper::new(&mock);
+        assert_eq!(
+            TokenQuerier::new(&wrapper).creation_fee_options().unwrap(),
+            vec![vec![coin(100, "uosmo")]]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use crate::types::{Metadata, Params};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CustomQuery, QueryRequest};
+use cosmwasm_std::{Coin, CustomQuery, QueryRequest, Uint128};
 
 #[cw_serde]
 pub enum TokenFactoryQuery {
@@ -25,6 +25,14 @@ pub enum TokenQuery {
     /// of the token factory (like staking tokens)
     #[returns(MetadataResponse)]
     Metadata { denom: String },
+    /// Returns whether metadata is set for this denom, without returning it.
+    /// Like `Metadata`, this also works for native tokens.
+    #[returns(HasMetadataResponse)]
+    HasMetadata { denom: String },
+    /// Returns whether the metadata of this denom was locked by `TokenMsg::LockMetadata`.
+    /// False for denoms which don't exist.
+    #[returns(MetadataLockedResponse)]
+    MetadataLocked { denom: String },
     /// Returns info on admin of the denom, only if created/managed via token factory.
     /// Errors if denom doesn't exist or was created by another module.
     #[returns(AdminResponse)]
@@ -37,6 +45,47 @@ pub enum TokenQuery {
     /// Returns configuration params for TokenFactory modules
     #[returns(ParamsResponse)]
     Params {},
+    /// Returns the amount of `denom` charged when creating a denom, from the first creation
+    /// fee option including it. None if no option includes this denom.
+    #[returns(CreationFeeInResponse)]
+    CreationFeeIn { denom: String },
+    /// Returns every creation fee the chain accepts, of which the creator pays one.
+    /// The first one is the fee of the params.
+    #[returns(CreationFeeOptionsResponse)]
+    CreationFeeOptions {},
+    /// Checks whether `minter` is currently allowed to mint `amount` of `denom`.
+    /// Never errors for a well-formed request, the reason for a refusal is returned instead.
+    #[returns(CanMintResponse)]
+    CanMint {
+        denom: String,
+        minter: String,
+        amount: Uint128,
+    },
+    /// Splits a token factory denom into its components, checking its structure only.
+    /// Errors for malformed denoms. The denom doesn't need to exist.
+    #[returns(ParseDenomResponse)]
+    ParseDenom { denom: String },
+    /// Returns the address which created the denom, which stays the same when the admin
+    /// changes. None for denoms not created by the token factory, which `ParseDenom`
+    /// can't tell apart from factory denoms by their string alone.
+    #[returns(DenomCreatorResponse)]
+    DenomCreator { denom: String },
+    /// Returns the current supply of every denom the creator created, in creation order.
+    /// Denoms whose admin was changed since are included.
+    #[returns(CreatorTotalSupplyResponse)]
+    CreatorTotalSupply { creator: String },
+    /// Returns the characters and maximum length the chain accepts in subdenoms,
+    /// so contracts can check generated subdenoms before creating them.
+    #[returns(SubdenomRulesResponse)]
+    SubdenomRules {},
+    /// Returns the optional features the chain supports. Chains that don't know this query
+    /// reject it, use `TokenQuerier::features` to fall back to the conservative default.
+    #[returns(crate::features::TokenFactoryFeatures)]
+    Features {},
+    /// Returns every metadata the denom had, with the block height it was set at, oldest first.
+    /// Not supported by chains, only by the mock in token-bindings-test when enabled there.
+    #[returns(MetadataHistoryResponse)]
+    MetadataHistory { denom: String },
 }
 
 impl CustomQuery for TokenFactoryQuery {}
@@ -58,6 +107,16 @@ pub struct MetadataResponse {
     pub metadata: Option<Metadata>,
 }
 
+#[cw_serde]
+pub struct HasMetadataResponse {
+    pub has_metadata: bool,
+}
+
+#[cw_serde]
+pub struct MetadataLockedResponse {
+    pub locked: bool,
+}
+
 #[cw_serde]
 pub struct AdminResponse {
     pub admin: String,
@@ -72,3 +131,61 @@ pub struct DenomsByCreatorResponse {
 pub struct ParamsResponse {
     pub params: Params,
 }
+
+#[cw_serde]
+pub struct CanMintResponse {
+    pub allowed: bool,
+    /// Why minting is not allowed, None if it is
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct ParseDenomResponse {
+    pub prefix: String,
+    pub creator: String,
+    pub subdenom: String,
+}
+
+#[cw_serde]
+pub struct DenomCreatorResponse {
+    pub creator: Option<String>,
+}
+
+#[cw_serde]
+pub struct CreationFeeInResponse {
+    pub amount: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct CreationFeeOptionsResponse {
+    /// The accepted fees, each charged in full
+    pub options: Vec<Vec<Coin>>,
+}
+
+#[cw_serde]
+pub struct SubdenomRulesResponse {
+    /// Every character allowed in a subdenom
+    pub allowed_chars: String,
+    /// Maximum length of a subdenom, in bytes
+    pub max_len: u32,
+}
+
+impl SubdenomRulesResponse {
+    /// Whether `subdenom` follows these rules
+    pub fn allows(&self, subdenom: &str) -> bool {
+        subdenom.len() <= self.max_len as usize
+            && subdenom.chars().all(|c| self.allowed_chars.contains(c))
+    }
+}
+
+#[cw_serde]
+pub struct MetadataHistoryResponse {
+    /// (height, metadata) pairs, oldest first
+    pub history: Vec<(u64, Metadata)>,
+}
+
+#[cw_serde]
+pub struct CreatorTotalSupplyResponse {
+    /// One coin per denom, zero for denoms never minted or fully burned
+    pub supply: Vec<Coin>,
+}
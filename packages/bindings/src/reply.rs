@@ -0,0 +1,140 @@
+//! Reply ids for contracts sending many token factory submessages.
+//!
+//! A fixed reply id per message type stops working once a contract sends several
+//! CreateDenom in one execution and needs to know which reply belongs to which denom.
+//! `ReplyDispatcher` hands out unique ids instead, and keeps a context for each
+//! until its reply arrives.
+
+use cosmwasm_std::{from_binary, to_binary, Binary, StdError, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Allocates reply ids from a counter in contract storage, starting at `first_id`,
+/// and stores a serialized context with each until `take_context` is called in the reply.
+///
+/// Keep the fixed reply ids of the contract below `first_id`, so they never collide.
+pub struct ReplyDispatcher<'a> {
+    next_id: Item<'a, u64>,
+    contexts: Map<'a, u64, Binary>,
+    first_id: u64,
+}
+
+impl<'a> ReplyDispatcher<'a> {
+    /// Stores the counter under `counter_key` and the contexts in the map `contexts_namespace`
+    pub const fn new(counter_key: &'a str, contexts_namespace: &'a str, first_id: u64) -> Self {
+        ReplyDispatcher {
+            next_id: Item::new(counter_key),
+            contexts: Map::new(contexts_namespace),
+            first_id,
+        }
+    }
+
+    /// Allocates an id for a submessage, remembering `context` for its reply
+    pub fn allocate<T: Serialize>(&self, storage: &mut dyn Storage, context: &T) -> StdResult<u64> {
+        self.allocate_batch(storage, std::slice::from_ref(context))
+    }
+
+    /// Allocates consecutive ids, one per context, and returns the first of them.
+    /// The context `contexts[i]` belongs to the id `first + i`, which matches
+    /// `create_denoms_submsgs` given the same base id.
+    pub fn allocate_batch<T: Serialize>(
+        &self,
+        storage: &mut dyn Storage,
+        contexts: &[T],
+    ) -> StdResult<u64> {
+        let first = self.next_id.may_load(storage)?.unwrap_or(self.first_id);
+        let mut id = first;
+        for context in contexts {
+            self.contexts.save(storage, id, &to_binary(context)?)?;
+            id = id
+                .checked_add(1)
+                .ok_or_else(|| StdError::generic_err("reply ids exhausted"))?;
+        }
+        self.next_id.save(storage, &id)?;
+        Ok(first)
+    }
+
+    /// Removes and returns the context of `id`, None if the id was not allocated here
+    /// or its context was already taken
+    pub fn may_take_context<T: DeserializeOwned>(
+        &self,
+        storage: &mut dyn Storage,
+        id: u64,
+    ) -> StdResult<Option<T>> {
+        match self.contexts.may_load(storage, id)? {
+            Some(context) => {
+                self.contexts.remove(storage, id);
+                from_binary(&context).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Like `may_take_context`, but errors if there is no context for `id`
+    pub fn take_context<T: DeserializeOwned>(
+        &self,
+        storage: &mut dyn Storage,
+        id: u64,
+    ) -> StdResult<T> {
+        self.may_take_context(storage, id)?
+            .ok_or_else(|| StdError::not_found(format!("reply context {}", id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    const DISPATCHER: ReplyDispatcher = ReplyDispatcher::new("reply_id", "reply_contexts", 100);
+
+    #[test]
+    fn interleaved_replies() {
+        let mut storage = MockStorage::new();
+        let batch = ["fundz".to_string(), "gems".to_string()];
+        assert_eq!(
+            DISPATCHER.allocate_batch(&mut storage, &batch).unwrap(),
+            100
+        );
+        assert_eq!(
+            DISPATCHER
+                .allocate(&mut storage, &"coins".to_string())
+                .unwrap(),
+            102
+        );
+
+        let take = |storage: &mut MockStorage, id| -> String {
+            DISPATCHER.take_context(storage, id).unwrap()
+        };
+        assert_eq!(take(&mut storage, 102), "coins");
+        assert_eq!(take(&mut storage, 101), "gems");
+        // ids are never reused, even once their contexts are taken
+        assert_eq!(DISPATCHER.allocate(&mut storage, &"more").unwrap(), 103);
+        assert_eq!(take(&mut storage, 100), "fundz");
+        assert_eq!(take(&mut storage, 103), "more");
+    }
+
+    #[test]
+    fn missing_context() {
+        let mut storage = MockStorage::new();
+        let id = DISPATCHER.allocate(&mut storage, &"fundz").unwrap();
+
+        // fixed ids below the first one, and ids not allocated yet
+        for unknown in [1, id + 1] {
+            let context: Option<String> =
+                DISPATCHER.may_take_context(&mut storage, unknown).unwrap();
+            assert_eq!(context, None);
+            let err = DISPATCHER
+                .take_context::<String>(&mut storage, unknown)
+                .unwrap_err();
+            assert!(matches!(err, StdError::NotFound { .. }));
+        }
+
+        // each context is taken once
+        DISPATCHER.take_context::<String>(&mut storage, id).unwrap();
+        DISPATCHER
+            .take_context::<String>(&mut storage, id)
+            .unwrap_err();
+    }
+}
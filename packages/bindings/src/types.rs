@@ -1,7 +1,15 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, OverflowError, Uint128};
+use std::cmp::max;
+use std::fmt;
+
+use crate::error::MetadataError;
 
 /// This maps to cosmos.bank.v1beta1.Metadata protobuf struct
+///
+/// Unset fields serialize as explicit `null`s (the serde default). The chain's
+/// JSON decoding reads `null` as the empty string, exactly like an omitted field,
+/// so we keep the default. Omitted fields deserialize as `None` as well.
 #[cw_serde]
 pub struct Metadata {
     pub description: Option<String>,
@@ -16,6 +24,185 @@ pub struct Metadata {
     /// symbol is the token symbol usually shown on exchanges (eg: ATOM). This can
     /// be the same as the display.
     pub symbol: Option<String>,
+    /// URI to a document (on or off-chain) that contains additional information,
+    /// usually the token logo. Only supported since cosmos-sdk 0.46.
+    pub uri: Option<String>,
+    /// SHA256 hash of the document the URI points to, to verify it
+    pub uri_hash: Option<String>,
+}
+
+impl Metadata {
+    /// Builds the metadata for a token with a base unit (exponent 0) and a
+    /// display unit at the given exponent, eg. `uxyz` and `xyz` with exponent 6.
+    pub fn two_unit(base: &str, display: &str, exponent: u32, symbol: &str, name: &str) -> Self {
+        Metadata {
+            description: None,
+            denom_units: vec![
+                DenomUnit {
+                    denom: base.to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: display.to_string(),
+                    exponent,
+                    aliases: vec![],
+                },
+            ],
+            base: Some(base.to_string()),
+            display: Some(display.to_string()),
+            name: Some(name.to_string()),
+            symbol: Some(symbol.to_string()),
+            uri: None,
+            uri_hash: None,
+        }
+    }
+
+    /// Builds the standard metadata of a token with `decimals` decimals: the base unit
+    /// and the display unit at exponent `decimals`. The symbol doubles as name.
+    ///
+    /// With 0 decimals there is nothing to scale, so the base is the only unit, with
+    /// `display` as an alias, and is displayed itself.
+    pub fn from_decimals(base: &str, display: &str, decimals: u32, symbol: &str) -> Self {
+        let mut metadata = Self::two_unit(base, display, decimals, symbol, symbol);
+        if decimals == 0 {
+            metadata.denom_units.truncate(1);
+            if display != base {
+                metadata.denom_units[0].aliases.push(display.to_string());
+            }
+            metadata.display = Some(base.to_string());
+        }
+        metadata
+    }
+
+    /// Finds the unit whose denom or one of its aliases matches `name`, ignoring case
+    pub fn find_unit(&self, name: &str) -> Option<&DenomUnit> {
+        self.denom_units.iter().find(|unit| {
+            std::iter::once(&unit.denom)
+                .chain(unit.aliases.iter())
+                .any(|denom| denom.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// The unit `display` refers to, if it is set and one of the units
+    pub fn display_unit(&self) -> Option<&DenomUnit> {
+        self.display
+            .as_deref()
+            .and_then(|display| self.find_unit(display))
+    }
+
+    /// Lists the fields which differ from `other`, with denom units compared by position
+    pub fn diff(&self, other: &Metadata) -> Vec<MetadataFieldChange> {
+        let mut changes = vec![];
+        let fields = [
+            ("description", &self.description, &other.description),
+            ("base", &self.base, &other.base),
+            ("display", &self.display, &other.display),
+            ("name", &self.name, &other.name),
+            ("symbol", &self.symbol, &other.symbol),
+            ("uri", &self.uri, &other.uri),
+            ("uri_hash", &self.uri_hash, &other.uri_hash),
+        ];
+        for (field, from, to) in fields {
+            if from != to {
+                changes.push(MetadataFieldChange {
+                    field: field.to_string(),
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+
+        let units = max(self.denom_units.len(), other.denom_units.len());
+        for i in 0..units {
+            let from = self.denom_units.get(i);
+            let to = other.denom_units.get(i);
+            if from != to {
+                changes.push(MetadataFieldChange {
+                    field: format!("denom_units[{}]", i),
+                    from: from.map(DenomUnit::to_string),
+                    to: to.map(DenomUnit::to_string),
+                });
+            }
+        }
+        changes
+    }
+}
+
+/// A field which differs between two `Metadata`, as returned by `Metadata::diff`.
+/// Values are None if unset, or if there is no denom unit at that position.
+#[cw_serde]
+pub struct MetadataFieldChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// The fields changed from `old` to `new` with their new values, as `Metadata::diff` lists
+/// them. Made to be added as event attributes of a metadata update, unset values are empty.
+pub fn metadata_diff(old: &Metadata, new: &Metadata) -> Vec<(String, String)> {
+    old.diff(new)
+        .into_iter()
+        .map(|change| (change.field, change.to.unwrap_or_default()))
+        .collect()
+}
+
+/// Performs the same stateless checks as the bank module does on `Metadata`
+/// (see cosmos-sdk x/bank/types/metadata.go)
+pub fn validate_metadata(metadata: &Metadata) -> Result<(), MetadataError> {
+    required_field(&metadata.name, "name")?;
+    required_field(&metadata.symbol, "symbol")?;
+    let base = required_field(&metadata.base, "base")?;
+    let display = required_field(&metadata.display, "display")?;
+
+    match metadata.denom_units.first() {
+        Some(unit) if unit.denom == base && unit.exponent == 0 => {}
+        _ => {
+            return Err(MetadataError::InvalidBaseUnit {
+                base: base.to_string(),
+            })
+        }
+    }
+
+    // units must be sorted by strictly increasing exponent, starting from the base at 0
+    if let Some(pair) = metadata
+        .denom_units
+        .windows(2)
+        .find(|pair| pair[1].exponent <= pair[0].exponent)
+    {
+        return Err(MetadataError::NonMonotonicExponents {
+            denom: pair[1].denom.clone(),
+        });
+    }
+
+    let mut seen: Vec<&str> = vec![];
+    for unit in &metadata.denom_units {
+        for denom in std::iter::once(&unit.denom).chain(unit.aliases.iter()) {
+            if seen.contains(&denom.as_str()) {
+                return Err(MetadataError::DuplicateDenomUnit {
+                    denom: denom.clone(),
+                });
+            }
+            seen.push(denom);
+        }
+    }
+
+    if !metadata.denom_units.iter().any(|u| u.denom == display) {
+        return Err(MetadataError::DisplayNotFound {
+            display: display.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn required_field<'a>(value: &'a Option<String>, field: &str) -> Result<&'a str, MetadataError> {
+    match value.as_deref().map(str::trim) {
+        Some(v) if !v.is_empty() => Ok(v),
+        _ => Err(MetadataError::MissingField {
+            field: field.to_string(),
+        }),
+    }
 }
 
 /// This maps to cosmos.bank.v1beta1.DenomUnit protobuf struct
@@ -28,14 +215,471 @@ pub struct DenomUnit {
     /// 1 denom = 1^exponent base_denom
     /// (e.g. with a base_denom of uatom, one can create a DenomUnit of 'atom' with
     /// exponent = 6, thus: 1 atom = 10^6 uatom).
-    exponent: u32,
+    pub exponent: u32,
     /// aliases is a list of string aliases for the given denom
-    aliases: Vec<String>,
+    pub aliases: Vec<String>,
+}
+
+impl fmt::Display for DenomUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (exponent {}", self.denom, self.exponent)?;
+        if !self.aliases.is_empty() {
+            write!(f, ", aliases {}", self.aliases.join(", "))?;
+        }
+        write!(f, ")")
+    }
 }
 
 /// This maps to osmosis.tokenfactory.v1beta1.Params protobuf struct
 #[cw_serde]
 pub struct Params {
-    /// TODO: verify semantics - does it charge all of these or one of these?
+    /// All of these coins are charged to the creator when creating a denom
     pub denom_creation_fee: Vec<Coin>,
+    /// Whether denoms can be created at all. Not part of the osmosis params,
+    /// so it defaults to true when missing, but lets tests model a chain
+    /// where the module was turned off.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Params {
+    /// The fee for creating `n` denoms, eg. to check the funds of a batch up front.
+    /// Fee coins of the same denom are added up, in order of their first appearance.
+    pub fn total_fee_for(&self, n: u32) -> Result<Vec<Coin>, OverflowError> {
+        let mut total: Vec<Coin> = vec![];
+        for fee in &self.denom_creation_fee {
+            let amount = fee.amount.checked_mul(Uint128::from(n))?;
+            match total.iter_mut().find(|c| c.denom == fee.denom) {
+                Some(coin) => coin.amount = coin.amount.checked_add(amount)?,
+                None => total.push(Coin::new(amount.u128(), &fee.denom)),
+            }
+        }
+        Ok(total)
+    }
+}
+
+/// Whether `funds` cover every coin of `required`, matched by denom in any order.
+/// Coins of the same denom are added up on both sides, extra funds are fine.
+pub fn has_sufficient_funds(funds: &[Coin], required: &[Coin]) -> bool {
+    let sum = |coins: &[Coin], denom: &str| {
+        coins
+            .iter()
+            .filter(|c| c.denom == denom)
+            .try_fold(Uint128::zero(), |acc, c| acc.checked_add(c.amount))
+    };
+    required.iter().all(|req| {
+        // funds too large to add up certainly cover anything that can be required
+        let available = sum(funds, &req.denom).unwrap_or(Uint128::MAX);
+        match sum(required, &req.denom) {
+            Ok(needed) => available >= needed,
+            Err(_) => false,
+        }
+    })
+}
+
+/// Enabled, with no creation fee
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            denom_creation_fee: vec![],
+            enabled: default_enabled(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
+
+    #[test]
+    fn from_decimals() {
+        let base = "factory/osmo1creator/ufundz";
+        let metadata = Metadata::from_decimals(base, "fundz", 6, "FUNDZ");
+        validate_metadata(&metadata).unwrap();
+        assert_eq!(
+            metadata.denom_units,
+            vec![
+                DenomUnit {
+                    denom: base.to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: "fundz".to_string(),
+                    exponent: 6,
+                    aliases: vec![],
+                },
+            ]
+        );
+        assert_eq!(metadata.base.as_deref(), Some(base));
+        assert_eq!(metadata.display.as_deref(), Some("fundz"));
+        assert_eq!(metadata.symbol.as_deref(), Some("FUNDZ"));
+        assert_eq!(metadata.name.as_deref(), Some("FUNDZ"));
+
+        // no decimals
+        let metadata = Metadata::from_decimals(base, "fundz", 0, "FUNDZ");
+        validate_metadata(&metadata).unwrap();
+        assert_eq!(
+            metadata.denom_units,
+            vec![DenomUnit {
+                denom: base.to_string(),
+                exponent: 0,
+                aliases: vec!["fundz".to_string()],
+            }]
+        );
+        assert_eq!(metadata.display.as_deref(), Some(base));
+    }
+
+    #[test]
+    fn two_unit_metadata_validates() {
+        let metadata = Metadata::two_unit("uxyz", "xyz", 6, "XYZ", "Xyz Token");
+        validate_metadata(&metadata).unwrap();
+
+        assert_eq!(metadata.base, Some("uxyz".to_string()));
+        assert_eq!(metadata.display, Some("xyz".to_string()));
+        assert_eq!(
+            metadata.denom_units,
+            vec![
+                DenomUnit {
+                    denom: "uxyz".to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: "xyz".to_string(),
+                    exponent: 6,
+                    aliases: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_metadata_rejects_invalid() {
+        let mut metadata = Metadata::two_unit("uxyz", "xyz", 6, "XYZ", "Xyz Token");
+        metadata.symbol = Some(" ".to_string());
+        assert_eq!(
+            validate_metadata(&metadata).unwrap_err(),
+            MetadataError::MissingField {
+                field: "symbol".to_string()
+            }
+        );
+
+        let mut metadata = Metadata::two_unit("uxyz", "xyz", 6, "XYZ", "Xyz Token");
+        metadata.denom_units.swap(0, 1);
+        assert_eq!(
+            validate_metadata(&metadata).unwrap_err(),
+            MetadataError::InvalidBaseUnit {
+                base: "uxyz".to_string()
+            }
+        );
+
+        let mut metadata = Metadata::two_unit("uxyz", "xyz", 6, "XYZ", "Xyz Token");
+        metadata.display = Some("mxyz".to_string());
+        assert_eq!(
+            validate_metadata(&metadata).unwrap_err(),
+            MetadataError::DisplayNotFound {
+                display: "mxyz".to_string()
+            }
+        );
+
+        let mut metadata = Metadata::two_unit("uxyz", "xyz", 6, "XYZ", "Xyz Token");
+        metadata.denom_units[1].aliases = vec!["uxyz".to_string()];
+        assert_eq!(
+            validate_metadata(&metadata).unwrap_err(),
+            MetadataError::DuplicateDenomUnit {
+                denom: "uxyz".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn validate_metadata_exponent_order() {
+        let unit = |denom: &str, exponent: u32| DenomUnit {
+            denom: denom.to_string(),
+            exponent,
+            aliases: vec![],
+        };
+
+        let mut metadata = Metadata::two_unit("uxyz", "xyz", 6, "XYZ", "Xyz Token");
+        metadata.denom_units = vec![unit("uxyz", 0), unit("mxyz", 3), unit("xyz", 6)];
+        validate_metadata(&metadata).unwrap();
+
+        // reversed
+        metadata.denom_units = vec![unit("uxyz", 0), unit("xyz", 6), unit("mxyz", 3)];
+        assert_eq!(
+            validate_metadata(&metadata).unwrap_err(),
+            MetadataError::NonMonotonicExponents {
+                denom: "mxyz".to_string()
+            }
+        );
+
+        // equal exponents
+        metadata.denom_units = vec![unit("uxyz", 0), unit("mxyz", 6), unit("xyz", 6)];
+        assert_eq!(
+            validate_metadata(&metadata).unwrap_err(),
+            MetadataError::NonMonotonicExponents {
+                denom: "xyz".to_string()
+            }
+        );
+
+        // no other unit can share the base exponent
+        metadata.denom_units = vec![unit("uxyz", 0), unit("xyz", 0)];
+        assert_eq!(
+            validate_metadata(&metadata).unwrap_err(),
+            MetadataError::NonMonotonicExponents {
+                denom: "xyz".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn find_units() {
+        let mut metadata = Metadata::two_unit("uatom", "atom", 6, "ATOM", "Cosmos Atom");
+        metadata.denom_units[0].aliases = vec!["microatom".to_string()];
+
+        assert_eq!(metadata.find_unit("atom").unwrap().exponent, 6);
+        assert_eq!(metadata.find_unit("ATOM").unwrap().exponent, 6);
+        assert_eq!(metadata.find_unit("MicroAtom").unwrap().denom, "uatom");
+        assert_eq!(metadata.find_unit("matom"), None);
+
+        assert_eq!(metadata.display_unit().unwrap().denom, "atom");
+        metadata.display = Some("Atom".to_string());
+        assert_eq!(metadata.display_unit().unwrap().denom, "atom");
+        metadata.display = Some("matom".to_string());
+        assert_eq!(metadata.display_unit(), None);
+        metadata.display = None;
+        assert_eq!(metadata.display_unit(), None);
+    }
+
+    #[test]
+    fn diff_metadata() {
+        let metadata = Metadata::two_unit("uatom", "atom", 6, "ATOM", "Cosmos Atom");
+        assert_eq!(metadata.diff(&metadata), vec![]);
+
+        let mut changed = metadata.clone();
+        changed.symbol = Some("SCAM".to_string());
+        changed.description = Some("changed".to_string());
+        changed.denom_units[1].exponent = 18;
+        changed.denom_units.push(DenomUnit {
+            denom: "gatom".to_string(),
+            exponent: 24,
+            aliases: vec!["gigaatom".to_string()],
+        });
+
+        assert_eq!(
+            metadata.diff(&changed),
+            vec![
+                MetadataFieldChange {
+                    field: "description".to_string(),
+                    from: None,
+                    to: Some("changed".to_string()),
+                },
+                MetadataFieldChange {
+                    field: "symbol".to_string(),
+                    from: Some("ATOM".to_string()),
+                    to: Some("SCAM".to_string()),
+                },
+                MetadataFieldChange {
+                    field: "denom_units[1]".to_string(),
+                    from: Some("atom (exponent 6)".to_string()),
+                    to: Some("atom (exponent 18)".to_string()),
+                },
+                MetadataFieldChange {
+                    field: "denom_units[2]".to_string(),
+                    from: None,
+                    to: Some("gatom (exponent 24, aliases gigaatom)".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_diff_new_values() {
+        let old = Metadata::two_unit("uatom", "atom", 6, "ATOM", "Cosmos Atom");
+        assert!(metadata_diff(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        new.uri = Some("https://cosmos.network/logo.png".to_string());
+        assert_eq!(
+            metadata_diff(&old, &new),
+            vec![(
+                "uri".to_string(),
+                "https://cosmos.network/logo.png".to_string()
+            )]
+        );
+
+        new.name = None;
+        new.denom_units.pop();
+        assert_eq!(
+            metadata_diff(&old, &new),
+            vec![
+                ("name".to_string(), "".to_string()),
+                (
+                    "uri".to_string(),
+                    "https://cosmos.network/logo.png".to_string()
+                ),
+                ("denom_units[1]".to_string(), "".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn total_fee_for_batches() {
+        let params = Params {
+            denom_creation_fee: vec![
+                Coin::new(100, "uosmo"),
+                Coin::new(5, "uion"),
+                Coin::new(20, "uosmo"),
+            ],
+            enabled: true,
+        };
+        assert_eq!(
+            params.total_fee_for(3).unwrap(),
+            vec![Coin::new(360, "uosmo"), Coin::new(15, "uion")]
+        );
+        assert_eq!(
+            params.total_fee_for(0).unwrap(),
+            vec![Coin::new(0, "uosmo"), Coin::new(0, "uion")]
+        );
+        assert_eq!(Params::default().total_fee_for(10).unwrap(), vec![]);
+
+        let expensive = Params {
+            denom_creation_fee: vec![Coin::new(u128::MAX / 2, "uosmo")],
+            enabled: true,
+        };
+        expensive.total_fee_for(2).unwrap();
+        expensive.total_fee_for(3).unwrap_err();
+        let split = Params {
+            denom_creation_fee: vec![
+                Coin::new(u128::MAX / 2, "uosmo"),
+                Coin::new(u128::MAX / 2, "uosmo"),
+            ],
+            enabled: true,
+        };
+        split.total_fee_for(1).unwrap();
+        split.total_fee_for(2).unwrap_err();
+    }
+
+    #[test]
+    fn sufficient_funds() {
+        let required = vec![Coin::new(360, "uosmo"), Coin::new(15, "uion")];
+        // any order, with extra coins
+        let funds = vec![
+            Coin::new(7, "uatom"),
+            Coin::new(15, "uion"),
+            Coin::new(400, "uosmo"),
+        ];
+        assert!(has_sufficient_funds(&funds, &required));
+        assert!(has_sufficient_funds(&funds, &[]));
+
+        // one coin short, or missing
+        let short = vec![Coin::new(14, "uion"), Coin::new(400, "uosmo")];
+        assert!(!has_sufficient_funds(&short, &required));
+        assert!(!has_sufficient_funds(&funds[2..], &required));
+        assert!(!has_sufficient_funds(&[], &required));
+
+        // coins of the same denom add up
+        let split = vec![
+            Coin::new(300, "uosmo"),
+            Coin::new(15, "uion"),
+            Coin::new(60, "uosmo"),
+        ];
+        assert!(has_sufficient_funds(&split, &required));
+        let doubled = vec![Coin::new(15, "uion"), Coin::new(15, "uion")];
+        assert!(!has_sufficient_funds(&[Coin::new(20, "uion")], &doubled));
+    }
+
+    #[test]
+    fn params_enabled_by_default() {
+        let params: Params = from_slice(br#"{"denom_creation_fee":[]}"#).unwrap();
+        assert!(params.enabled);
+
+        let params: Params = from_slice(br#"{"denom_creation_fee":[],"enabled":false}"#).unwrap();
+        assert!(!params.enabled);
+    }
+
+    #[test]
+    fn default_params() {
+        let params = Params::default();
+        assert_eq!(params.denom_creation_fee, vec![]);
+        assert!(params.enabled);
+        // same as deserializing the minimal params
+        assert_eq!(
+            params,
+            from_slice::<Params>(br#"{"denom_creation_fee":[]}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn unset_fields_serialize_as_null() {
+        let metadata = Metadata {
+            description: None,
+            denom_units: vec![],
+            base: Some("uxyz".to_string()),
+            display: None,
+            name: Some("Xyz Token".to_string()),
+            symbol: None,
+            uri: Some("ipfs://xyz".to_string()),
+            uri_hash: None,
+        };
+        let json = String::from_utf8(to_vec(&metadata).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"description":null,"denom_units":[],"base":"uxyz","display":null,"name":"Xyz Token","symbol":null,"uri":"ipfs://xyz","uri_hash":null}"#
+        );
+        assert_eq!(from_slice::<Metadata>(json.as_bytes()).unwrap(), metadata);
+
+        // omitted fields are read as unset
+        let omitted = from_slice::<Metadata>(
+            br#"{"denom_units":[],"base":"uxyz","name":"Xyz Token","uri":"ipfs://xyz"}"#,
+        );
+        assert_eq!(omitted.unwrap(), metadata);
+    }
+
+    #[test]
+    fn metadata_round_trip() {
+        let mut metadata = Metadata::two_unit("uxyz", "xyz", 6, "XYZ", "Xyz Token");
+        metadata.denom_units[1].aliases = vec!["millixyz".to_string()];
+        let json = to_vec(&metadata).unwrap();
+        assert_eq!(from_slice::<Metadata>(&json).unwrap(), metadata);
+
+        metadata.display = None;
+        metadata.symbol = None;
+        let json = to_vec(&metadata).unwrap();
+        assert_eq!(from_slice::<Metadata>(&json).unwrap(), metadata);
+    }
+
+    #[test]
+    fn params_json_shape() {
+        let cases = [
+            (vec![], r#"{"denom_creation_fee":[],"enabled":true}"#),
+            (
+                vec![Coin::new(10_000_000, "uosmo")],
+                r#"{"denom_creation_fee":[{"denom":"uosmo","amount":"10000000"}],"enabled":true}"#,
+            ),
+            (
+                vec![Coin::new(10_000_000, "uosmo"), Coin::new(5, "uion")],
+                r#"{"denom_creation_fee":[{"denom":"uosmo","amount":"10000000"},{"denom":"uion","amount":"5"}],"enabled":true}"#,
+            ),
+        ];
+        for (fee, expected) in cases {
+            let params = Params {
+                denom_creation_fee: fee,
+                enabled: true,
+            };
+            let json = String::from_utf8(to_vec(&params).unwrap()).unwrap();
+            assert_eq!(json, expected);
+            assert_eq!(from_slice::<Params>(json.as_bytes()).unwrap(), params);
+
+            // as sent by osmosis, without our `enabled`
+            let osmosis = expected.replace(r#","enabled":true"#, "");
+            assert_eq!(from_slice::<Params>(osmosis.as_bytes()).unwrap(), params);
+        }
+    }
 }